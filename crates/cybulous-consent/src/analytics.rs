@@ -0,0 +1,43 @@
+//! Consent analytics events
+//!
+//! Lightweight lifecycle events for product metrics. Unlike the audit log,
+//! analytics sinks are best-effort: events carry no details beyond the kind
+//! and user, and a sink may sample or drop them under load.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Kind of consent lifecycle event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AnalyticsEventKind {
+    /// Consent granted for the first time
+    Grant,
+    /// Consent granted again over an existing record
+    Renew,
+    /// Consent revoked
+    Revoke,
+    /// Verification rejected a consent proof
+    Deny,
+    /// Verification found the consent expired
+    Expire,
+}
+
+/// A single analytics event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalyticsEvent {
+    /// What happened
+    pub kind: AnalyticsEventKind,
+    /// User the event concerns
+    pub user_id: String,
+    /// When it happened
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Destination for analytics events
+///
+/// Called inline on the consent path, so implementations should hand events
+/// off without blocking.
+pub trait AnalyticsSink: Send + Sync {
+    /// Accept an event
+    fn emit(&self, event: AnalyticsEvent);
+}
@@ -0,0 +1,101 @@
+//! Time-bounded reuse of provider age attestations
+//!
+//! Providers differ in how long their age attestations can be trusted. The
+//! engine caches its provider's results for the configured TTL; once an
+//! entry is older than that, `request_consent` goes back to the provider.
+//!
+//! If the provider is unavailable, an older entry may still be used as a
+//! last-known-good attestation within a separate, longer fallback window.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+/// A provider age result and when it was obtained
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedAttestation {
+    /// Verified age
+    pub age: u8,
+    /// When the provider verified it
+    pub verified_at: DateTime<Utc>,
+}
+
+/// Per-user cache of provider age attestations
+#[derive(Debug)]
+pub struct AttestationCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<String, CachedAttestation>>,
+}
+
+impl AttestationCache {
+    /// Trust cached results for `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Configured trust lifetime
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Cached age for `user_id` if still within the TTL at `now`
+    pub async fn fresh(&self, user_id: &str, now: DateTime<Utc>) -> Option<u8> {
+        self.entries
+            .read()
+            .await
+            .get(user_id)
+            .filter(|entry| now - entry.verified_at < self.ttl)
+            .map(|entry| entry.age)
+    }
+
+    /// Cached attestation for `user_id` verified within `window` of `now`,
+    /// regardless of the TTL
+    pub async fn last_known_good(
+        &self,
+        user_id: &str,
+        now: DateTime<Utc>,
+        window: Duration,
+    ) -> Option<CachedAttestation> {
+        self.entries
+            .read()
+            .await
+            .get(user_id)
+            .filter(|entry| now - entry.verified_at < window)
+            .copied()
+    }
+
+    /// Cache a provider result verified at `verified_at`
+    pub async fn store(&self, user_id: &str, age: u8, verified_at: DateTime<Utc>) {
+        self.entries
+            .write()
+            .await
+            .insert(user_id.to_string(), CachedAttestation { age, verified_at });
+    }
+
+    /// Forget `user_id`'s cached attestation
+    pub async fn remove(&self, user_id: &str) {
+        self.entries.write().await.remove(user_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_short_ttl_forces_reverification() {
+        let verified_at = Utc::now() - Duration::hours(2);
+        let short = AttestationCache::new(Duration::hours(1));
+        let long = AttestationCache::new(Duration::days(30));
+        short.store("user", 30, verified_at).await;
+        long.store("user", 30, verified_at).await;
+
+        let now = Utc::now();
+        assert_eq!(short.fresh("user", now).await, None);
+        assert_eq!(long.fresh("user", now).await, Some(30));
+        assert_eq!(long.fresh("other", now).await, None);
+    }
+}
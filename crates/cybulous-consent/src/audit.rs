@@ -0,0 +1,137 @@
+//! Audit trail for consent lifecycle operations
+//!
+//! Every administrative action on consent records is appended here so
+//! operators can reconstruct who changed what and when.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Audited consent action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditAction {
+    /// Consent granted
+    Granted,
+    /// Consent revoked
+    Revoked,
+    /// Legal hold placed on a record
+    LegalHoldPlaced,
+    /// Legal hold released from a record
+    LegalHoldReleased,
+    /// Record archived by a prune pass
+    Archived,
+    /// Missing scope granted through escalation
+    ScopeEscalated,
+    /// Secondary account linked to inherit a primary's consent
+    AccountLinked,
+    /// Account link removed and inherited consent revoked
+    AccountUnlinked,
+    /// Consent gating bypassed by an operator
+    EmergencyOverride,
+    /// Guardian consented on behalf of a subject
+    Delegated,
+    /// User data erased on request
+    Erased,
+    /// One use of a use-limited consent consumed
+    UseConsumed,
+}
+
+/// Single audit log entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Unique entry ID
+    pub id: Uuid,
+    /// When the action occurred
+    pub timestamp: DateTime<Utc>,
+    /// Subject user of the action
+    pub user_id: String,
+    /// Operator or service that performed the action, if not the user
+    #[serde(default)]
+    pub actor: Option<String>,
+    /// Action performed
+    pub action: AuditAction,
+    /// Free-form details
+    pub details: String,
+}
+
+/// Append-only in-memory audit log
+#[derive(Debug, Default)]
+pub struct AuditLog {
+    entries: RwLock<Vec<AuditEntry>>,
+}
+
+impl AuditLog {
+    /// Create an empty audit log
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an entry
+    pub async fn record(&self, user_id: &str, action: AuditAction, details: impl Into<String>) {
+        self.record_by(None, user_id, action, details).await;
+    }
+
+    /// Append an entry attributed to an acting operator or service
+    pub async fn record_by(
+        &self,
+        actor: Option<&str>,
+        user_id: &str,
+        action: AuditAction,
+        details: impl Into<String>,
+    ) {
+        let entry = AuditEntry {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            user_id: user_id.to_string(),
+            actor: actor.map(str::to_string),
+            action,
+            details: details.into(),
+        };
+        tracing::info!(
+            "Consent audit: {:?} for user {} ({})",
+            entry.action,
+            entry.user_id,
+            entry.details
+        );
+        self.entries.write().await.push(entry);
+    }
+
+    /// All entries in insertion order
+    pub async fn entries(&self) -> Vec<AuditEntry> {
+        self.entries.read().await.clone()
+    }
+
+    /// Replace `user_id` with `pseudonym` in every entry, clearing details
+    ///
+    /// Returns the number of entries rewritten.
+    pub async fn pseudonymize(&self, user_id: &str, pseudonym: &str) -> usize {
+        let mut rewritten = 0;
+        for entry in self.entries.write().await.iter_mut() {
+            let subject = entry.user_id == user_id;
+            let actor = entry.actor.as_deref() == Some(user_id);
+            if subject {
+                entry.user_id = pseudonym.to_string();
+            }
+            if actor {
+                entry.actor = Some(pseudonym.to_string());
+            }
+            if subject || actor {
+                entry.details = "[erased]".to_string();
+                rewritten += 1;
+            }
+        }
+        rewritten
+    }
+
+    /// Entries concerning a single user
+    pub async fn entries_for(&self, user_id: &str) -> Vec<AuditEntry> {
+        self.entries
+            .read()
+            .await
+            .iter()
+            .filter(|e| e.user_id == user_id)
+            .cloned()
+            .collect()
+    }
+}
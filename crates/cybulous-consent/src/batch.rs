@@ -0,0 +1,109 @@
+//! Batched consent attestations
+//!
+//! Recording many attestations in one transaction commits only the Merkle
+//! root of the batch on chain. Each user receives an `InclusionProof`: the
+//! sibling hashes needed to recompute that root from their own attestation.
+
+use crate::attestation::ConsentAttestation;
+use crate::{ConsentError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Which side of the running hash a sibling sits on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SiblingSide {
+    /// Sibling is hashed before the running hash
+    Left,
+    /// Sibling is hashed after the running hash
+    Right,
+}
+
+/// One level of a Merkle inclusion path
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofStep {
+    /// Hash of the sibling node
+    pub hash: String,
+    /// Position of the sibling relative to the running hash
+    pub side: SiblingSide,
+}
+
+/// Proof that an attestation is part of a recorded batch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusionProof {
+    /// User whose attestation is proven
+    pub user_id: String,
+    /// Transaction that recorded the batch root
+    pub tx_hash: String,
+    /// Merkle root of the batch
+    pub batch_root: String,
+    /// Position of the attestation within the batch
+    pub index: usize,
+    /// Sibling hashes from the leaf up to the root
+    pub path: Vec<ProofStep>,
+}
+
+impl InclusionProof {
+    /// Whether `attestation` hashes up to this proof's batch root
+    pub fn verify(&self, attestation: &ConsentAttestation) -> bool {
+        if attestation.user_id != self.user_id {
+            return false;
+        }
+        let Ok(leaf) = leaf_hash(attestation) else {
+            return false;
+        };
+        let root = self.path.iter().fold(leaf, |hash, step| match step.side {
+            SiblingSide::Left => node_hash(&step.hash, &hash),
+            SiblingSide::Right => node_hash(&hash, &step.hash),
+        });
+        root == self.batch_root
+    }
+}
+
+/// Leaf hash committing to a single attestation
+pub fn leaf_hash(attestation: &ConsentAttestation) -> Result<String> {
+    let encoded = serde_json::to_string(attestation)
+        .map_err(|e| ConsentError::AttestationInvalid(e.to_string()))?;
+    Ok(cybulous_crypto::hash_data(&format!("leaf:{}", encoded)))
+}
+
+fn node_hash(left: &str, right: &str) -> String {
+    cybulous_crypto::hash_data(&format!("node:{}:{}", left, right))
+}
+
+/// Merkle root of `leaves` and the inclusion path of each leaf
+///
+/// An unpaired node at the end of a level is promoted unchanged.
+pub(crate) fn merkle_tree(leaves: Vec<String>) -> (String, Vec<Vec<ProofStep>>) {
+    let mut paths = vec![Vec::new(); leaves.len()];
+    // Leaves covered by each node of the current level
+    let mut covered: Vec<Vec<usize>> = (0..leaves.len()).map(|i| vec![i]).collect();
+    let mut level = leaves;
+
+    while level.len() > 1 {
+        let mut next = Vec::with_capacity(level.len().div_ceil(2));
+        let mut next_covered = Vec::with_capacity(next.capacity());
+        for (pair, leaves) in level.chunks(2).zip(covered.chunks(2)) {
+            if let [left, right] = pair {
+                for &i in &leaves[0] {
+                    paths[i].push(ProofStep {
+                        hash: right.clone(),
+                        side: SiblingSide::Right,
+                    });
+                }
+                for &i in &leaves[1] {
+                    paths[i].push(ProofStep {
+                        hash: left.clone(),
+                        side: SiblingSide::Left,
+                    });
+                }
+                next.push(node_hash(left, right));
+            } else {
+                next.push(pair[0].clone());
+            }
+            next_covered.push(leaves.concat());
+        }
+        level = next;
+        covered = next_covered;
+    }
+
+    (level.pop().unwrap_or_default(), paths)
+}
@@ -0,0 +1,54 @@
+//! Privacy-preserving commitments to consent records
+//!
+//! Rather than exposing a record's details on chain, the engine can publish
+//! a salted hash commitment to it. The salt (the commitment's opening)
+//! stays off-chain with the platform, so the public commitment reveals
+//! nothing about the record, yet anyone the platform shares the record with
+//! can have it checked against the published commitment.
+
+use crate::{ConsentError, ConsentRecord, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Commitment to one revision of a consent record, as published on chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsentCommitment {
+    /// Record committed to
+    pub record_id: Uuid,
+    /// Salted hash of the record
+    pub digest: String,
+    /// Transaction that published the commitment
+    pub tx_hash: String,
+    /// When the commitment was published
+    pub published_at: DateTime<Utc>,
+}
+
+/// Off-chain secret needed to check a record against its commitment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CommitmentOpening {
+    pub(crate) salt: String,
+}
+
+impl CommitmentOpening {
+    /// Opening with a fresh random salt
+    pub(crate) fn generate() -> Self {
+        Self {
+            salt: URL_SAFE_NO_PAD.encode(rand::random::<[u8; 32]>()),
+        }
+    }
+
+    /// Commitment digest of `record` under this opening
+    pub(crate) fn digest(&self, record: &ConsentRecord) -> Result<String> {
+        // Round-trip through `Value` so map fields serialize in key order
+        let encoded = serde_json::to_value(record)
+            .map(|value| value.to_string())
+            .map_err(|e| ConsentError::AttestationInvalid(e.to_string()))?;
+        Ok(cybulous_crypto::hash_data(&format!(
+            "commitment:{}:{}",
+            self.salt, encoded
+        )))
+    }
+}
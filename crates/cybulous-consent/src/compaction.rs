@@ -0,0 +1,93 @@
+//! Compaction of consent attestation chains
+//!
+//! Every stored revision of a user's consent record is appended to their
+//! attestation chain, each link hashing the previous one. Compaction
+//! replaces a long chain with its current record plus a platform-signed
+//! `ChainSummary` committing to the chain's root hash. A later chain is
+//! seeded with that root, so history remains verifiable across compactions.
+
+use crate::signing;
+use crate::{ConsentError, ConsentRecord, Result};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// Seed of a chain that has never been compacted
+pub const GENESIS_ROOT: &str = "genesis";
+
+/// Root hash of `records` chained onto `seed`
+pub fn chain_root(seed: &str, records: &[ConsentRecord]) -> Result<String> {
+    records.iter().try_fold(seed.to_string(), |prev, record| {
+        let encoded = serde_json::to_string(record)
+            .map_err(|e| ConsentError::AttestationInvalid(e.to_string()))?;
+        Ok(cybulous_crypto::hash_data(&format!("{}:{}", prev, encoded)))
+    })
+}
+
+/// Signed commitment to a compacted attestation chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainSummary {
+    /// User whose chain was compacted
+    pub user_id: String,
+    /// Root hash of the full chain, including earlier compactions
+    pub history_root: String,
+    /// Records folded into this summary, including earlier compactions
+    pub entries: usize,
+    /// Transaction of the record retained as current
+    pub current_tx_hash: String,
+    /// When the chain was compacted
+    pub compacted_at: DateTime<Utc>,
+    /// Base64url Ed25519 signature over the other fields
+    pub signature: String,
+}
+
+#[derive(Serialize)]
+struct SignedFields<'a> {
+    user_id: &'a str,
+    history_root: &'a str,
+    entries: usize,
+    current_tx_hash: &'a str,
+    compacted_at: DateTime<Utc>,
+}
+
+impl ChainSummary {
+    /// Issue a summary signed with the platform key
+    pub fn sign(
+        user_id: &str,
+        history_root: String,
+        entries: usize,
+        current_tx_hash: &str,
+        key: &SigningKey,
+    ) -> Result<Self> {
+        let mut summary = Self {
+            user_id: user_id.to_string(),
+            history_root,
+            entries,
+            current_tx_hash: current_tx_hash.to_string(),
+            compacted_at: Utc::now(),
+            signature: String::new(),
+        };
+        summary.signature = signing::sign(key, &summary.signed_bytes()?);
+        Ok(summary)
+    }
+
+    /// Check the summary was signed by `public_key` and not altered
+    pub fn verify(&self, public_key: &VerifyingKey) -> Result<()> {
+        signing::verify(
+            public_key,
+            &self.signed_bytes()?,
+            &self.signature,
+            "chain summary",
+        )
+    }
+
+    fn signed_bytes(&self) -> Result<Vec<u8>> {
+        signing::signed_bytes(&SignedFields {
+            user_id: &self.user_id,
+            history_root: &self.history_root,
+            entries: self.entries,
+            current_tx_hash: &self.current_tx_hash,
+            compacted_at: self.compacted_at,
+        })
+    }
+}
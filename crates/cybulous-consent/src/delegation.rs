@@ -0,0 +1,122 @@
+//! Guardian-delegated consent
+//!
+//! A guardian with active consent may consent on behalf of a subject. The
+//! subject gets its own record, which can expire independently of the
+//! guardian's, while the delegation link itself has a validity window.
+//! Subject consent holds only while both its record and the link are
+//! valid and the guardian's consent remains active.
+//!
+//! A guardian may delegate a subset of their scopes. The subject is then
+//! granted only those scopes, and each holds only while the guardian still
+//! has it.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Link from a subject to the guardian who consented for them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Delegation {
+    /// Guardian who granted consent
+    pub guardian_id: String,
+    /// Subject the consent was granted for
+    pub subject_id: String,
+    /// When the delegation was created
+    pub created_at: DateTime<Utc>,
+    /// When the delegation link stops being valid, if ever
+    pub valid_until: Option<DateTime<Utc>>,
+    /// Scopes delegated to the subject
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+impl Delegation {
+    /// Whether the link is valid at `now`
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+        !matches!(self.valid_until, Some(valid_until) if now > valid_until)
+    }
+}
+
+/// Current standing of a delegation edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DelegationStatus {
+    /// The subject may act under the guardian's consent
+    Active,
+    /// The delegation link's validity window has passed
+    LinkExpired,
+    /// The subject's own delegated record is no longer active
+    SubjectExpired,
+    /// The guardian's consent is no longer active
+    GuardianInactive,
+}
+
+/// Guardian-to-subject edge in a delegation graph
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DelegationEdge {
+    /// Guardian who granted consent
+    pub guardian_id: String,
+    /// Subject acting under that consent
+    pub subject_id: String,
+    /// Current standing
+    pub status: DelegationStatus,
+}
+
+/// Who can act on whose behalf, for visualization
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DelegationGraph {
+    /// Guardian-to-subject edges
+    pub edges: Vec<DelegationEdge>,
+}
+
+impl DelegationGraph {
+    /// A cycle of user ids, if the edges contain one
+    ///
+    /// The engine refuses delegations that would close a cycle, so this
+    /// guards consumers that assemble graphs from other sources.
+    pub fn find_cycle(&self) -> Option<Vec<String>> {
+        let guardian_of: HashMap<&str, &str> = self
+            .edges
+            .iter()
+            .map(|e| (e.subject_id.as_str(), e.guardian_id.as_str()))
+            .collect();
+
+        for start in guardian_of.keys() {
+            let mut path = vec![*start];
+            let mut current = *start;
+            while let Some(guardian) = guardian_of.get(current) {
+                if let Some(pos) = path.iter().position(|id| id == guardian) {
+                    return Some(path[pos..].iter().map(|id| id.to_string()).collect());
+                }
+                path.push(guardian);
+                current = guardian;
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(guardian: &str, subject: &str) -> DelegationEdge {
+        DelegationEdge {
+            guardian_id: guardian.to_string(),
+            subject_id: subject.to_string(),
+            status: DelegationStatus::Active,
+        }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let mut graph = DelegationGraph {
+            edges: vec![edge("a", "b"), edge("b", "c")],
+        };
+        assert_eq!(graph.find_cycle(), None);
+
+        graph.edges.push(edge("c", "a"));
+        let mut cycle = graph.find_cycle().unwrap();
+        cycle.sort();
+        assert_eq!(cycle, vec!["a", "b", "c"]);
+    }
+}
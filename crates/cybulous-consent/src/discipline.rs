@@ -0,0 +1,17 @@
+//! Threshold-based discipline scoring
+//!
+//! Providers that grade discipline eligibility numerically implement
+//! `DisciplineScorer`. When a scorer is configured, the engine requires a
+//! user's score to meet its minimum before granting consent.
+
+use async_trait::async_trait;
+
+/// Default minimum discipline score
+pub const DEFAULT_MIN_DISCIPLINE_SCORE: f64 = 0.5;
+
+/// Source of numeric discipline scores
+#[async_trait]
+pub trait DisciplineScorer: Send + Sync {
+    /// Score a user's discipline eligibility, typically in `[0, 1]`
+    async fn discipline_score(&self, user_id: &str) -> anyhow::Result<f64>;
+}
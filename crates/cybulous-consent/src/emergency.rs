@@ -0,0 +1,35 @@
+//! Emergency consent overrides
+//!
+//! In safety-critical situations an authorized operator may bypass consent
+//! gating for a single user. Overrides are short-lived, bound to one user,
+//! and always audited with the operator and justification.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Default lifetime of an emergency override
+pub const DEFAULT_OVERRIDE_TTL_MINUTES: i64 = 15;
+
+/// Time-boxed override of consent gating for one user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverrideToken {
+    /// Opaque token presented by the caller
+    pub token: String,
+    /// Operator who authorized the override
+    pub operator_id: String,
+    /// User whose consent gating is overridden
+    pub user_id: String,
+    /// Recorded justification
+    pub justification: String,
+    /// Issuance time
+    pub issued_at: DateTime<Utc>,
+    /// Expiration time
+    pub expires_at: DateTime<Utc>,
+}
+
+impl OverrideToken {
+    /// Whether the override is valid for `user_id` at `now`
+    pub fn permits(&self, user_id: &str, now: DateTime<Utc>) -> bool {
+        self.user_id == user_id && now < self.expires_at
+    }
+}
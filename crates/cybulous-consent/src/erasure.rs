@@ -0,0 +1,74 @@
+//! Erasure of a user's consent data
+//!
+//! Complements data export: on an erasure request the engine removes the
+//! user's records and pseudonymizes their audit entries, then issues a
+//! platform-signed `ErasureReceipt`. The receipt names the user only by
+//! pseudonym and commits to the root hash of the erased records, so it can
+//! later show what was erased without retaining it. Pseudonyms are keyed
+//! with a platform secret so they cannot be recomputed from a guessed
+//! user ID.
+
+use crate::signing;
+use crate::Result;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+/// Pseudonym replacing an erased user's identifier, stable for a given
+/// platform `key`
+pub fn pseudonym(key: &[u8], user_id: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(user_id.as_bytes());
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Signed record of an erasure
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErasureReceipt {
+    /// Pseudonym of the erased user
+    pub subject: String,
+    /// Number of consent records removed
+    pub erased_records: usize,
+    /// Root hash of the removed records
+    pub erased_root: String,
+    /// Number of audit entries pseudonymized
+    pub anonymized_audit_entries: usize,
+    /// Whether a legal hold was overridden to perform the erasure
+    pub legal_hold_overridden: bool,
+    /// When the data was erased
+    pub erased_at: DateTime<Utc>,
+    /// Base64url Ed25519 signature over the other fields
+    pub signature: String,
+}
+
+impl ErasureReceipt {
+    /// Sign the receipt with the platform key, replacing any signature
+    pub fn sign(mut self, key: &SigningKey) -> Result<Self> {
+        self.signature = signing::sign(key, &self.signed_bytes()?);
+        Ok(self)
+    }
+
+    /// Check the receipt was signed by `public_key` and not altered
+    pub fn verify(&self, public_key: &VerifyingKey) -> Result<()> {
+        signing::verify(
+            public_key,
+            &self.signed_bytes()?,
+            &self.signature,
+            "erasure receipt",
+        )
+    }
+
+    fn signed_bytes(&self) -> Result<Vec<u8>> {
+        let unsigned = Self {
+            signature: String::new(),
+            ..self.clone()
+        };
+        signing::signed_bytes(&unsigned)
+    }
+}
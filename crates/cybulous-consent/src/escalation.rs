@@ -0,0 +1,26 @@
+//! Revalidation hooks for scope escalation
+//!
+//! When a caller needs a scope the user has not granted, the engine asks a
+//! `ScopeEscalationHandler` to obtain additional consent instead of denying
+//! outright.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Handler invoked when a scoped verification finds the scope missing
+#[async_trait]
+pub trait ScopeEscalationHandler: Send + Sync {
+    /// Prompt for consent to `scope`, returning an updated proof if granted
+    ///
+    /// Returning `None` means the user declined and the check is denied.
+    async fn escalate(&self, user_id: &str, scope: &str) -> Option<String>;
+}
+
+/// Outcome of a scoped verification that may have escalated
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScopedVerification {
+    /// Whether consent for the scope is valid
+    pub granted: bool,
+    /// Replacement proof the caller should use, if escalation produced one
+    pub updated_proof: Option<String>,
+}
@@ -0,0 +1,120 @@
+//! Consent expiry assignment and deduplicated "expiring soon" notices
+//!
+//! An `ExpiryPolicy` gives newly granted consent a validity period, jittered
+//! so consents granted together do not all come up for renewal at once.
+//!
+//! Sweeps find active consents nearing expiry. Because sweeps run far more
+//! often than users should be reminded, the ledger remembers when each user
+//! was last notified and suppresses repeats within the configured interval.
+
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Default time before expiry at which users are warned
+pub const DEFAULT_EXPIRY_LEAD_HOURS: i64 = 72;
+
+/// Default minimum time between warnings to the same user
+pub const DEFAULT_EXPIRY_NOTICE_INTERVAL_HOURS: i64 = 24;
+
+/// Validity assigned to newly granted consent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpiryPolicy {
+    validity: Duration,
+    jitter: Duration,
+}
+
+impl ExpiryPolicy {
+    /// Expire consent exactly `validity` after it is granted
+    pub fn new(validity: Duration) -> Self {
+        Self {
+            validity,
+            jitter: Duration::zero(),
+        }
+    }
+
+    /// Spread expiries uniformly within `jitter` either side of the target
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter.abs();
+        self
+    }
+
+    /// Expiry for consent granted at `granted_at`
+    pub fn expiry_for(&self, granted_at: DateTime<Utc>) -> DateTime<Utc> {
+        let band = self.jitter.num_milliseconds();
+        let offset = if band > 0 {
+            rand::thread_rng().gen_range(-band..=band)
+        } else {
+            0
+        };
+        granted_at + self.validity + Duration::milliseconds(offset)
+    }
+}
+
+/// Warning that a user's consent expires soon
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpiryNotice {
+    /// User to notify
+    pub user_id: String,
+    /// When their consent expires
+    pub expires_at: DateTime<Utc>,
+    /// When the notice was issued
+    pub issued_at: DateTime<Utc>,
+}
+
+/// Tracks when each user was last warned
+#[derive(Debug)]
+pub struct ExpiryNoticeLedger {
+    lead_time: Duration,
+    interval: Duration,
+    last_notified: Mutex<HashMap<String, DateTime<Utc>>>,
+}
+
+impl Default for ExpiryNoticeLedger {
+    fn default() -> Self {
+        Self::new(
+            Duration::hours(DEFAULT_EXPIRY_LEAD_HOURS),
+            Duration::hours(DEFAULT_EXPIRY_NOTICE_INTERVAL_HOURS),
+        )
+    }
+}
+
+impl ExpiryNoticeLedger {
+    /// Warn `lead_time` before expiry, at most once per `interval` per user
+    pub fn new(lead_time: Duration, interval: Duration) -> Self {
+        Self {
+            lead_time,
+            interval,
+            last_notified: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether a consent expiring at `expires_at` is due a warning at `now`
+    pub fn expiring_soon(&self, expires_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        expires_at > now && expires_at - now <= self.lead_time
+    }
+
+    /// Claim the right to notify `user_id` at `now`
+    ///
+    /// Returns `false` if the user was already notified within the interval.
+    pub fn try_notify(&self, user_id: &str, now: DateTime<Utc>) -> bool {
+        let mut last_notified = self.last_notified.lock().unwrap_or_else(|e| e.into_inner());
+        match last_notified.get(user_id) {
+            Some(last) if now - *last < self.interval => false,
+            _ => {
+                last_notified.insert(user_id.to_string(), now);
+                true
+            }
+        }
+    }
+
+    /// Forget when `user_id` was last notified
+    pub fn forget(&self, user_id: &str) {
+        self.last_notified
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(user_id);
+    }
+}
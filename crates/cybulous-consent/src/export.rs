@@ -0,0 +1,65 @@
+//! Portable export of a user's consent data
+//!
+//! Supports data-portability requests: everything the engine holds about a
+//! user (current and historical records, receipts, and audit entries) is
+//! bundled into a `UserDataExport` and signed with the platform key so the
+//! recipient can check it is complete and unaltered.
+
+use crate::audit::AuditEntry;
+use crate::compaction::ChainSummary;
+use crate::revocation::RevocationAttestation;
+use crate::signing;
+use crate::{ConsentRecord, Result};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// Signed bundle of all consent data held for one user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserDataExport {
+    /// User the data concerns
+    pub user_id: String,
+    /// When the export was produced
+    pub exported_at: DateTime<Utc>,
+    /// Record currently in force, if any
+    pub current: Option<ConsentRecord>,
+    /// Record revisions since the last chain compaction, oldest first
+    pub history: Vec<ConsentRecord>,
+    /// Summary of compacted history, if the chain was compacted
+    pub chain_summary: Option<ChainSummary>,
+    /// Records moved to the archive
+    pub archived: Vec<ConsentRecord>,
+    /// Revocation receipt, if the current consent is revoked
+    pub revocation_receipt: Option<RevocationAttestation>,
+    /// Audit entries concerning the user
+    pub audit_entries: Vec<AuditEntry>,
+    /// Base64url Ed25519 signature over the other fields
+    pub signature: String,
+}
+
+impl UserDataExport {
+    /// Sign the export with the platform key, replacing any signature
+    pub fn sign(mut self, key: &SigningKey) -> Result<Self> {
+        self.signature = signing::sign(key, &self.signed_bytes()?);
+        Ok(self)
+    }
+
+    /// Check the export was signed by `public_key` and not altered
+    pub fn verify(&self, public_key: &VerifyingKey) -> Result<()> {
+        signing::verify(
+            public_key,
+            &self.signed_bytes()?,
+            &self.signature,
+            "data export",
+        )
+    }
+
+    /// Canonical bytes: the export serialized with an empty signature
+    fn signed_bytes(&self) -> Result<Vec<u8>> {
+        let unsigned = Self {
+            signature: String::new(),
+            ..self.clone()
+        };
+        signing::signed_bytes(&unsigned)
+    }
+}
@@ -0,0 +1,146 @@
+//! Cached consent proof introspection
+//!
+//! Introspecting a proof resolves the underlying record, so services that
+//! introspect on every request cache the result for a short TTL. The cache
+//! follows the engine's revocation events and drops a user's entries as
+//! soon as their consent is revoked, so it never reports a revoked proof
+//! as active.
+
+use crate::revocation::RevocationEvent;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::sync::broadcast::{self, error::TryRecvError};
+use tokio::sync::RwLock;
+
+/// Result of introspecting a consent proof
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofIntrospection {
+    /// User whose consent record backs the proof
+    pub user_id: String,
+    /// Whether the proof currently verifies
+    pub active: bool,
+    /// Terms version the consent was granted under
+    pub terms_version: u32,
+    /// When the consent expires, if ever
+    pub expires_at: Option<DateTime<Utc>>,
+    /// When the introspection was performed
+    pub introspected_at: DateTime<Utc>,
+}
+
+/// TTL cache of introspection results, invalidated by revocations
+#[derive(Debug)]
+pub struct IntrospectionCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<(String, String), ProofIntrospection>>,
+    revocations: Mutex<broadcast::Receiver<RevocationEvent>>,
+}
+
+impl IntrospectionCache {
+    /// Cache results for `ttl`, invalidating on events from `revocations`
+    pub fn new(ttl: Duration, revocations: broadcast::Receiver<RevocationEvent>) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+            revocations: Mutex::new(revocations),
+        }
+    }
+
+    /// Cached introspection of `proof` for `user_id` if fresh at `now`
+    pub async fn get(
+        &self,
+        user_id: &str,
+        proof: &str,
+        now: DateTime<Utc>,
+    ) -> Option<ProofIntrospection> {
+        self.apply_revocations().await;
+        self.entries
+            .read()
+            .await
+            .get(&(user_id.to_string(), proof.to_string()))
+            .filter(|entry| now - entry.introspected_at < self.ttl)
+            .cloned()
+    }
+
+    /// Cache an introspection of `proof` requested for `user_id`
+    pub async fn store(&self, user_id: &str, proof: &str, introspection: ProofIntrospection) {
+        self.apply_revocations().await;
+        self.entries
+            .write()
+            .await
+            .insert((user_id.to_string(), proof.to_string()), introspection);
+    }
+
+    /// Drop entries requested for or owned by `user_id`
+    pub async fn remove_user(&self, user_id: &str) {
+        self.entries
+            .write()
+            .await
+            .retain(|(requested, _), entry| requested != user_id && entry.user_id != user_id);
+    }
+
+    /// Drop entries for users revoked since the last lookup
+    ///
+    /// Entries are matched on both the requested user and the record's
+    /// owner so linked accounts are invalidated with their primary. If the
+    /// receiver lagged and missed events, everything is dropped.
+    async fn apply_revocations(&self) {
+        let mut revoked = Vec::new();
+        let mut lagged = false;
+        {
+            let mut revocations = self.revocations.lock().unwrap_or_else(|e| e.into_inner());
+            loop {
+                match revocations.try_recv() {
+                    Ok(event) => revoked.push(event.user_id),
+                    Err(TryRecvError::Lagged(_)) => lagged = true,
+                    Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+                }
+            }
+        }
+
+        if lagged {
+            self.entries.write().await.clear();
+        } else if !revoked.is_empty() {
+            self.entries.write().await.retain(|(user_id, _), entry| {
+                !revoked.contains(user_id) && !revoked.contains(&entry.user_id)
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_revocation_event_invalidates_entries() {
+        let (tx, rx) = broadcast::channel(4);
+        let cache = IntrospectionCache::new(Duration::minutes(5), rx);
+        let now = Utc::now();
+        let introspection = |user_id: &str| ProofIntrospection {
+            user_id: user_id.to_string(),
+            active: true,
+            terms_version: 1,
+            expires_at: None,
+            introspected_at: now,
+        };
+        cache.store("primary", "p1", introspection("primary")).await;
+        cache.store("linked", "p1", introspection("primary")).await;
+        cache.store("other", "p2", introspection("other")).await;
+
+        tx.send(RevocationEvent {
+            user_id: "primary".to_string(),
+            revoked_at: now,
+        })
+        .unwrap();
+
+        assert!(cache.get("primary", "p1", now).await.is_none());
+        assert!(cache.get("linked", "p1", now).await.is_none());
+        assert!(cache.get("other", "p2", now).await.unwrap().active);
+        assert!(cache
+            .get("other", "p2", now + Duration::minutes(10))
+            .await
+            .is_none());
+    }
+}
@@ -5,19 +5,84 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs, rust_2018_idioms, unreachable_pub)]
 
+pub mod analytics;
 pub mod attestation;
+pub mod attestation_cache;
+pub mod audit;
+pub mod batch;
+pub mod commitment;
+pub mod compaction;
+pub mod delegation;
+pub mod discipline;
+pub mod emergency;
+pub mod erasure;
+pub mod escalation;
+pub mod expiry;
+pub mod export;
+pub mod introspection;
+pub mod liveness;
+pub mod negotiation;
+pub mod nonce_ledger;
+pub mod obligation;
+pub mod preview;
+pub mod proof_cache;
 pub mod providers;
+pub mod rate_limit;
+pub mod reminder;
+pub mod revocation;
+pub mod scope;
+pub mod search;
+mod signing;
+pub mod terms;
+pub mod token;
 pub mod verification;
 
+pub use analytics::{AnalyticsEvent, AnalyticsEventKind, AnalyticsSink};
 pub use attestation::{ConsentAttestation, ConsentProof};
+pub use attestation_cache::AttestationCache;
+pub use audit::{AuditAction, AuditEntry, AuditLog};
+pub use batch::InclusionProof;
+pub use commitment::ConsentCommitment;
+pub use compaction::ChainSummary;
+pub use delegation::{Delegation, DelegationGraph};
+pub use discipline::DisciplineScorer;
+pub use emergency::OverrideToken;
+pub use erasure::ErasureReceipt;
+pub use escalation::{ScopeEscalationHandler, ScopedVerification};
+pub use expiry::{ExpiryNotice, ExpiryNoticeLedger, ExpiryPolicy};
+pub use export::UserDataExport;
+pub use introspection::{IntrospectionCache, ProofIntrospection};
+pub use liveness::LivenessChallenge;
+pub use negotiation::ProofFormat;
+pub use nonce_ledger::{ConsumedNonce, MemoryNonceStore, NonceLedger, NonceStore};
+pub use obligation::Obligation;
+pub use preview::ConsentPreview;
+pub use proof_cache::{ConsentProofCache, IssuedProof, ProofGenerator};
 pub use providers::{ConsentProvider, ProviderType};
+pub use rate_limit::ProofRateLimiter;
+pub use reminder::{
+    ReminderClock, RenewalNotifier, RenewalReminder, RenewalSchedule, RenewalScheduler, SystemClock,
+};
+pub use revocation::{RevocationAttestation, RevocationEvent};
+pub use scope::ScopeHierarchy;
+pub use search::ConsentQuery;
+pub use terms::ConsentVerification;
+pub use token::{ConsentToken, TokenClaims};
 pub use verification::{AgeVerification, DisciplineCheck};
 
-use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use commitment::CommitmentOpening;
+use delegation::{DelegationEdge, DelegationStatus};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use liveness::LivenessCheck;
+use reminder::RenewalLedger;
+use search::AttributeIndex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use thiserror::Error;
+use tokio::sync::{broadcast, Mutex, RwLock};
 use uuid::Uuid;
 
 /// Consent-related errors
@@ -46,13 +111,33 @@ pub enum ConsentError {
     /// Blockchain error
     #[error("blockchain error: {0}")]
     BlockchainError(String),
+
+    /// Consent token rejected
+    #[error("invalid consent token: {0}")]
+    TokenInvalid(String),
+
+    /// Too many requests for a user within the rate limit window
+    #[error("rate limited: {0}")]
+    RateLimited(String),
+
+    /// Operation blocked by a legal hold
+    #[error("legal hold: {0}")]
+    LegalHold(String),
+
+    /// Client and engine share no proof format
+    #[error("unsupported proof format: {0}")]
+    UnsupportedProofFormat(String),
+
+    /// Persistent store failure
+    #[error("storage error: {0}")]
+    StorageError(String),
 }
 
 /// Result type for consent operations
 pub type Result<T> = std::result::Result<T, ConsentError>;
 
 /// Consent status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ConsentStatus {
     /// Consent granted and active
     Active,
@@ -85,14 +170,205 @@ pub struct ConsentRecord {
     pub age_proof: String,
     /// Discipline eligibility proof
     pub discipline_proof: String,
+    /// Legal hold flag; held records are never pruned or archived
+    #[serde(default)]
+    pub legal_hold: bool,
+    /// Scopes granted under this consent
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Per-scope expiration times, overriding `expires_at` for that scope
+    #[serde(default)]
+    pub scope_expiry: HashMap<String, DateTime<Utc>>,
+    /// How long data associated with this consent may be retained
+    #[serde(default)]
+    pub retention: Option<std::time::Duration>,
+    /// Terms version the consent was granted under
+    #[serde(default = "terms::initial_terms_version")]
+    pub terms_version: u32,
+    /// Assurance level of the provider that verified the consent
+    /// (0 = baseline)
+    #[serde(default)]
+    pub assurance_level: u8,
+    /// Conditions the consent was granted on
+    #[serde(default)]
+    pub obligations: Vec<Obligation>,
+    /// Legal jurisdiction the consent was granted under
+    #[serde(default)]
+    pub jurisdiction: Option<String>,
+    /// Verifications the consent permits in total; unlimited if `None`
+    #[serde(default)]
+    pub max_uses: Option<u32>,
+    /// Verifications left under `max_uses`
+    #[serde(default)]
+    pub remaining_uses: Option<u32>,
+    /// Regions the consent may be used from; unrestricted if `None`
+    #[serde(default)]
+    pub allowed_regions: Option<Vec<String>>,
+    /// Region whose ledger holds the consent, for locality-aware routing
+    #[serde(default)]
+    pub origin_region: Option<String>,
+}
+
+impl ConsentRecord {
+    /// Create a freshly granted, active record
+    pub fn new(
+        user_id: &str,
+        tx_hash: String,
+        age_proof: String,
+        discipline_proof: String,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id: user_id.to_string(),
+            status: ConsentStatus::Active,
+            granted_at: Utc::now(),
+            expires_at: None,
+            revoked_at: None,
+            tx_hash,
+            age_proof,
+            discipline_proof,
+            legal_hold: false,
+            scopes: Vec::new(),
+            scope_expiry: HashMap::new(),
+            retention: None,
+            terms_version: terms::INITIAL_TERMS_VERSION,
+            assurance_level: 0,
+            obligations: Vec::new(),
+            jurisdiction: None,
+            max_uses: None,
+            remaining_uses: None,
+            allowed_regions: None,
+            origin_region: None,
+        }
+    }
+
+    /// Check the record's invariants, rejecting malformed records
+    ///
+    /// Records read back from chain are not trusted to be well-formed.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |reason: String| {
+            Err(ConsentError::AttestationInvalid(format!(
+                "malformed consent record {}: {}",
+                self.id, reason
+            )))
+        };
+
+        if self.user_id.is_empty() {
+            return invalid("empty user id".to_string());
+        }
+        if self.tx_hash.is_empty()
+            || !self
+                .tx_hash
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return invalid(format!("ill-formed tx hash {:?}", self.tx_hash));
+        }
+        match (self.status, self.revoked_at) {
+            (ConsentStatus::Revoked, None) => {
+                return invalid("revoked without a revocation time".to_string())
+            }
+            (ConsentStatus::Revoked, Some(_)) | (_, None) => {}
+            (status, Some(_)) => {
+                return invalid(format!("{:?} record has a revocation time", status))
+            }
+        }
+        if let Some(revoked_at) = self.revoked_at {
+            if revoked_at < self.granted_at {
+                return invalid(format!(
+                    "revoked at {} before granted at {}",
+                    revoked_at, self.granted_at
+                ));
+            }
+        }
+        match (self.max_uses, self.remaining_uses) {
+            (Some(max), Some(remaining)) if remaining <= max => {}
+            (None, None) => {}
+            (max, remaining) => {
+                return invalid(format!(
+                    "{:?} uses remaining of {:?} permitted",
+                    remaining, max
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the consent may be used from `region`
+    ///
+    /// Geofenced consent requires the caller to name a permitted region.
+    pub fn permits_region(&self, region: Option<&str>) -> bool {
+        match (&self.allowed_regions, region) {
+            (None, _) => true,
+            (Some(allowed), Some(region)) => allowed.iter().any(|r| r.eq_ignore_ascii_case(region)),
+            (Some(_), None) => false,
+        }
+    }
+
+    /// Whether the record is active and unexpired at `now`
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        if self.status != ConsentStatus::Active {
+            return false;
+        }
+        !matches!(self.expires_at, Some(expires_at) if now > expires_at)
+    }
+
+    /// Whether the record has lapsed by expiry at `now`
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.status == ConsentStatus::Expired
+            || matches!(self.expires_at, Some(expires_at) if now > expires_at)
+    }
+
+    /// Whether `scope` is granted and unexpired at `now`
+    pub fn scope_active_at(&self, scope: &str, now: DateTime<Utc>) -> bool {
+        if !self.scopes.iter().any(|s| s == scope) {
+            return false;
+        }
+        !matches!(self.scope_expiry.get(scope), Some(expires_at) if now > *expires_at)
+    }
 }
 
 /// Main consent engine
 #[derive(Clone)]
 pub struct ConsentEngine {
     provider: Arc<dyn ConsentProvider>,
+    provider_assurance: u8,
     blockchain_client: Arc<BlockchainClient>,
     min_age: u8,
+    audit_log: Arc<AuditLog>,
+    platform_key: Arc<SigningKey>,
+    /// Secret keying erased users' pseudonyms
+    pseudonym_key: Arc<[u8; 32]>,
+    escalation_handler: Option<Arc<dyn ScopeEscalationHandler>>,
+    scope_hierarchy: ScopeHierarchy,
+    account_links: Arc<RwLock<HashMap<String, String>>>,
+    overrides: Arc<RwLock<HashMap<String, OverrideToken>>>,
+    override_ttl: Duration,
+    previews: Arc<RwLock<HashMap<Uuid, ConsentPreview>>>,
+    inactivity_timeout: Option<Duration>,
+    last_active: Arc<RwLock<HashMap<String, DateTime<Utc>>>>,
+    terms_version: Arc<AtomicU32>,
+    discipline_scorer: Option<Arc<dyn DisciplineScorer>>,
+    min_discipline_score: f64,
+    attestation_cache: Option<Arc<AttestationCache>>,
+    age_fallback_window: Option<Duration>,
+    delegations: Arc<RwLock<HashMap<String, Delegation>>>,
+    proof_limiter: Option<Arc<ProofRateLimiter>>,
+    analytics: Option<Arc<dyn AnalyticsSink>>,
+    revocations: broadcast::Sender<RevocationEvent>,
+    introspection_cache: Option<Arc<IntrospectionCache>>,
+    expiry_notices: Arc<ExpiryNoticeLedger>,
+    expiry_policy: Option<ExpiryPolicy>,
+    liveness_challenges: Arc<RwLock<HashMap<Uuid, LivenessChallenge>>>,
+    liveness_checks: Arc<RwLock<HashMap<String, LivenessCheck>>>,
+    liveness_window: Duration,
+    nonce_ledger: Option<Arc<NonceLedger>>,
+    /// Serializes writes to stored records so a concurrent update, such as
+    /// a revocation racing a spent use, is never overwritten
+    record_lock: Arc<Mutex<()>>,
+    /// Salts of published commitments, by record; never leave the platform
+    commitment_openings: Arc<RwLock<HashMap<Uuid, CommitmentOpening>>>,
+    renewal_reminders: Arc<RenewalLedger>,
 }
 
 impl ConsentEngine {
@@ -104,55 +380,779 @@ impl ConsentEngine {
     ) -> Self {
         Self {
             provider,
+            provider_assurance: 0,
             blockchain_client,
             min_age,
+            audit_log: Arc::new(AuditLog::new()),
+            platform_key: Arc::new(SigningKey::from_bytes(&rand::random())),
+            pseudonym_key: Arc::new(rand::random()),
+            escalation_handler: None,
+            scope_hierarchy: ScopeHierarchy::default(),
+            account_links: Arc::new(RwLock::new(HashMap::new())),
+            overrides: Arc::new(RwLock::new(HashMap::new())),
+            override_ttl: Duration::minutes(emergency::DEFAULT_OVERRIDE_TTL_MINUTES),
+            previews: Arc::new(RwLock::new(HashMap::new())),
+            inactivity_timeout: None,
+            last_active: Arc::new(RwLock::new(HashMap::new())),
+            terms_version: Arc::new(AtomicU32::new(terms::INITIAL_TERMS_VERSION)),
+            discipline_scorer: None,
+            min_discipline_score: discipline::DEFAULT_MIN_DISCIPLINE_SCORE,
+            attestation_cache: None,
+            age_fallback_window: None,
+            delegations: Arc::new(RwLock::new(HashMap::new())),
+            proof_limiter: None,
+            analytics: None,
+            revocations: broadcast::channel(revocation::REVOCATION_CHANNEL_CAPACITY).0,
+            introspection_cache: None,
+            expiry_notices: Arc::new(ExpiryNoticeLedger::default()),
+            expiry_policy: None,
+            liveness_challenges: Arc::new(RwLock::new(HashMap::new())),
+            liveness_checks: Arc::new(RwLock::new(HashMap::new())),
+            record_lock: Arc::new(Mutex::new(())),
+            commitment_openings: Arc::new(RwLock::new(HashMap::new())),
+            renewal_reminders: Arc::new(RenewalLedger::default()),
+            liveness_window: Duration::seconds(liveness::DEFAULT_LIVENESS_WINDOW_SECS),
+            nonce_ledger: None,
         }
     }
 
+    /// Set the assurance level of the configured provider
+    ///
+    /// Consent recorded through the provider carries this level, and tools
+    /// requiring a higher one reject it.
+    pub fn with_provider_assurance(mut self, level: u8) -> Self {
+        self.provider_assurance = level;
+        self
+    }
+
+    /// Set how long emergency overrides remain valid
+    pub fn with_override_ttl(mut self, ttl: Duration) -> Self {
+        self.override_ttl = ttl;
+        self
+    }
+
+    /// Lapse consent for users inactive longer than `timeout`
+    ///
+    /// Inactivity is measured from the later of the grant time and the
+    /// last [`ConsentEngine::touch_activity`] call.
+    pub fn with_inactivity_timeout(mut self, timeout: Duration) -> Self {
+        self.inactivity_timeout = Some(timeout);
+        self
+    }
+
+    /// Require a numeric discipline score from `scorer` to meet the minimum
+    pub fn with_discipline_scorer(mut self, scorer: Arc<dyn DisciplineScorer>) -> Self {
+        self.discipline_scorer = Some(scorer);
+        self
+    }
+
+    /// Set the minimum discipline score required when a scorer is configured
+    pub fn with_min_discipline_score(mut self, min_score: f64) -> Self {
+        self.min_discipline_score = min_score;
+        self
+    }
+
+    /// Reuse the provider's age attestations for `ttl` before re-verifying
+    ///
+    /// The TTL should reflect how long the configured provider's
+    /// attestations can be trusted.
+    pub fn with_attestation_ttl(mut self, ttl: Duration) -> Self {
+        self.attestation_cache = Some(Arc::new(AttestationCache::new(ttl)));
+        self
+    }
+
+    /// Cache proof introspection results for `ttl`, invalidated on revocation
+    pub fn with_introspection_ttl(mut self, ttl: Duration) -> Self {
+        let cache = IntrospectionCache::new(ttl, self.revocations.subscribe());
+        self.introspection_cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Warn users `lead_time` before expiry, at most once per `interval`
+    pub fn with_expiry_notices(mut self, lead_time: Duration, interval: Duration) -> Self {
+        self.expiry_notices = Arc::new(ExpiryNoticeLedger::new(lead_time, interval));
+        self
+    }
+
+    /// Give newly granted consent an expiry assigned by `policy`
+    ///
+    /// Without a policy, consent does not expire unless set explicitly.
+    pub fn with_expiry_policy(mut self, policy: ExpiryPolicy) -> Self {
+        self.expiry_policy = Some(policy);
+        self
+    }
+
+    /// Accept liveness-bound proofs for `window` after the check completes
+    pub fn with_liveness_window(mut self, window: Duration) -> Self {
+        self.liveness_window = window;
+        self
+    }
+
+    /// Record consumed challenge nonces in `ledger` so they stay single-use
+    /// across restarts
+    pub fn with_nonce_ledger(mut self, ledger: Arc<NonceLedger>) -> Self {
+        self.nonce_ledger = Some(ledger);
+        self
+    }
+
+    /// Fall back to a cached age attestation up to `window` old when the
+    /// provider is unavailable
+    ///
+    /// Provider results are cached for this purpose even without an
+    /// attestation TTL.
+    pub fn with_age_fallback_window(mut self, window: Duration) -> Self {
+        self.age_fallback_window = Some(window);
+        self.attestation_cache
+            .get_or_insert_with(|| Arc::new(AttestationCache::new(Duration::zero())));
+        self
+    }
+
+    /// Limit each user to `max_proofs` generated proofs per `window`
+    pub fn with_proof_rate_limit(mut self, max_proofs: u32, window: Duration) -> Self {
+        self.proof_limiter = Some(Arc::new(ProofRateLimiter::new(max_proofs, window)));
+        self
+    }
+
+    /// Emit lifecycle analytics events to `sink`
+    pub fn with_analytics_sink(mut self, sink: Arc<dyn AnalyticsSink>) -> Self {
+        self.analytics = Some(sink);
+        self
+    }
+
+    /// Let grants of parent scopes satisfy checks for the scopes they imply
+    pub fn with_scope_hierarchy(mut self, hierarchy: ScopeHierarchy) -> Self {
+        self.scope_hierarchy = hierarchy;
+        self
+    }
+
+    /// Prompt for missing scopes through `handler` instead of denying
+    pub fn with_escalation_handler(mut self, handler: Arc<dyn ScopeEscalationHandler>) -> Self {
+        self.escalation_handler = Some(handler);
+        self
+    }
+
+    /// Use a fixed platform signing key for issued tokens
+    pub fn with_platform_key(mut self, key: SigningKey) -> Self {
+        self.platform_key = Arc::new(key);
+        self
+    }
+
+    /// Use a fixed secret for erased users' pseudonyms
+    ///
+    /// Pseudonyms stay stable across restarts only with a fixed secret.
+    pub fn with_pseudonym_key(mut self, key: [u8; 32]) -> Self {
+        self.pseudonym_key = Arc::new(key);
+        self
+    }
+
+    /// Public key downstream services use to verify issued tokens
+    pub fn platform_public_key(&self) -> VerifyingKey {
+        self.platform_key.verifying_key()
+    }
+
     /// Create mock engine for testing
-    #[cfg(test)]
+    #[cfg(any(test, feature = "test-util"))]
     pub fn mock() -> Self {
-        Self {
-            provider: Arc::new(providers::MockProvider::default()),
-            blockchain_client: Arc::new(BlockchainClient::mock()),
-            min_age: 21,
+        Self::new(
+            Arc::new(providers::MockProvider::default()),
+            Arc::new(BlockchainClient::mock()),
+            21,
+        )
+    }
+
+    /// Probe the engine's backing services
+    pub async fn health_check(&self) -> Result<()> {
+        self.blockchain_client
+            .ping()
+            .await
+            .map_err(|e| ConsentError::BlockchainError(e.to_string()))
+    }
+
+    /// Audit log of consent lifecycle operations
+    pub fn audit_log(&self) -> Arc<AuditLog> {
+        self.audit_log.clone()
+    }
+
+    /// Terms version currently in force
+    pub fn terms_version(&self) -> u32 {
+        self.terms_version.load(Ordering::SeqCst)
+    }
+
+    /// Advance the active terms, marking consents under older terms stale
+    pub fn advance_terms_version(&self, version: u32) -> Result<()> {
+        let current = self.terms_version();
+        if version <= current {
+            return Err(ConsentError::AttestationInvalid(format!(
+                "terms version {} does not advance current version {}",
+                version, current
+            )));
         }
+        self.terms_version.store(version, Ordering::SeqCst);
+        Ok(())
     }
 
     /// Verify user consent
     pub async fn verify_consent(&self, user_id: &str, proof: &str) -> Result<bool> {
-        // Retrieve consent record from blockchain
-        let record = self
-            .blockchain_client
-            .get_consent_record(user_id)
+        Ok(self.verify_consent_detailed(user_id, proof).await?.valid)
+    }
+
+    /// Verify user consent presented from `region`
+    ///
+    /// Geofenced consent is rejected when `region` is missing or outside
+    /// the record's allowed regions.
+    pub async fn verify_consent_in_region(
+        &self,
+        user_id: &str,
+        proof: &str,
+        region: Option<&str>,
+    ) -> Result<bool> {
+        Ok(self
+            .verify_bound(user_id, proof, None, region, true)
+            .await?
+            .valid)
+    }
+
+    /// Check consent presented from `region` without spending a use
+    ///
+    /// For dry runs that must not exhaust a use-limited consent.
+    pub async fn check_consent_in_region(
+        &self,
+        user_id: &str,
+        proof: &str,
+        region: Option<&str>,
+    ) -> Result<bool> {
+        Ok(self
+            .verify_bound(user_id, proof, None, region, false)
+            .await?
+            .valid)
+    }
+
+    /// Assurance level of the provider behind a user's consent
+    pub async fn consent_assurance(&self, user_id: &str) -> Result<u8> {
+        Ok(self.resolve_record(user_id).await?.assurance_level)
+    }
+
+    /// Verify user consent, reporting whether it was granted under stale terms
+    pub async fn verify_consent_detailed(
+        &self,
+        user_id: &str,
+        proof: &str,
+    ) -> Result<ConsentVerification> {
+        self.verify_bound(user_id, proof, None, None, true).await
+    }
+
+    /// Verify a proof bound to `audience`, the verifying service's identifier
+    ///
+    /// Proofs issued for a different audience, or without one, are rejected
+    /// so a proof presented to one service cannot be replayed at another.
+    pub async fn verify_consent_for_audience(
+        &self,
+        user_id: &str,
+        proof: &str,
+        audience: &str,
+    ) -> Result<bool> {
+        Ok(self
+            .verify_bound(user_id, proof, Some(audience), None, true)
+            .await?
+            .valid)
+    }
+
+    /// Agree on a proof format from those a client offers
+    ///
+    /// `offered` is in the client's preference order; the first format the
+    /// engine verifies wins. Clients should generate proofs in that format
+    /// for the rest of the session.
+    pub fn negotiate_proof_format(&self, offered: &[ProofFormat]) -> Result<ProofFormat> {
+        negotiation::negotiate(offered, &negotiation::supported_formats()).ok_or_else(|| {
+            let offered: Vec<_> = offered
+                .iter()
+                .map(|f| format!("{}/v{}", f.scheme, f.version))
+                .collect();
+            ConsentError::UnsupportedProofFormat(format!(
+                "none of [{}] is supported",
+                offered.join(", ")
+            ))
+        })
+    }
+
+    /// Generate a consent proof for a user's active consent
+    pub async fn generate_proof(&self, user_id: &str) -> Result<String> {
+        self.issue_proof(user_id, None).await
+    }
+
+    /// Issue a consent proof usable only at the service named `audience`
+    pub async fn issue_audience_proof(&self, user_id: &str, audience: &str) -> Result<String> {
+        self.issue_proof(user_id, Some(audience)).await
+    }
+
+    /// Challenge a user to prove liveness before a sensitive action
+    pub async fn issue_liveness_challenge(&self, user_id: &str) -> Result<LivenessChallenge> {
+        let record = self.resolve_record(user_id).await?;
+        let now = Utc::now();
+        if !record.is_active_at(now) {
+            return Err(ConsentError::AttestationInvalid(format!(
+                "no active consent for {}",
+                user_id
+            )));
+        }
+
+        let challenge = LivenessChallenge {
+            challenge_id: Uuid::new_v4(),
+            user_id: user_id.to_string(),
+            nonce: Uuid::new_v4().simple().to_string(),
+            issued_at: now,
+            expires_at: now + Duration::seconds(liveness::LIVENESS_CHALLENGE_TTL_SECS),
+        };
+        self.liveness_challenges
+            .write()
             .await
-            .map_err(|e| ConsentError::BlockchainError(e.to_string()))?;
+            .insert(challenge.challenge_id, challenge.clone());
+        Ok(challenge)
+    }
 
-        // Check status
-        if record.status != ConsentStatus::Active {
-            return Ok(false);
+    /// Answer a liveness challenge, returning a proof bound to it
+    ///
+    /// The proof verifies with [`ConsentEngine::verify_consent_with_liveness`]
+    /// until the liveness window elapses.
+    pub async fn complete_liveness_challenge(
+        &self,
+        challenge_id: Uuid,
+        response: &str,
+    ) -> Result<String> {
+        let challenge = self
+            .liveness_challenges
+            .write()
+            .await
+            .remove(&challenge_id)
+            .ok_or_else(|| {
+                ConsentError::AttestationInvalid(format!("unknown challenge {}", challenge_id))
+            })?;
+
+        let now = Utc::now();
+        if now > challenge.expires_at {
+            return Err(ConsentError::AttestationInvalid(format!(
+                "challenge {} expired at {}",
+                challenge_id, challenge.expires_at
+            )));
+        }
+        if response != challenge.expected_response() {
+            return Err(ConsentError::AttestationInvalid(format!(
+                "incorrect response to challenge {}",
+                challenge_id
+            )));
         }
+        if let Some(ledger) = &self.nonce_ledger {
+            if !ledger
+                .consume(&challenge.nonce, challenge.expires_at, now)
+                .await?
+            {
+                return Err(ConsentError::AttestationInvalid(format!(
+                    "challenge {} nonce already used",
+                    challenge_id
+                )));
+            }
+        }
+
+        let audience = LivenessChallenge::audience(&challenge.nonce);
+        let proof = self
+            .issue_proof(&challenge.user_id, Some(&audience))
+            .await?;
+        self.liveness_checks.write().await.insert(
+            challenge.user_id,
+            LivenessCheck {
+                nonce: challenge.nonce,
+                verified_at: now,
+            },
+        );
+        Ok(proof)
+    }
+
+    /// Verify a proof bound to a liveness check within the liveness window
+    ///
+    /// Ordinary consent proofs, and proofs from stale or superseded checks,
+    /// are rejected.
+    pub async fn verify_consent_with_liveness(&self, user_id: &str, proof: &str) -> Result<bool> {
+        self.verify_consent_with_liveness_in_region(user_id, proof, None)
+            .await
+    }
+
+    /// Verify a liveness-bound proof presented from `region`
+    pub async fn verify_consent_with_liveness_in_region(
+        &self,
+        user_id: &str,
+        proof: &str,
+        region: Option<&str>,
+    ) -> Result<bool> {
+        self.verify_liveness_bound(user_id, proof, region, true)
+            .await
+    }
+
+    /// Check a liveness-bound proof from `region` without spending a use
+    pub async fn check_consent_with_liveness_in_region(
+        &self,
+        user_id: &str,
+        proof: &str,
+        region: Option<&str>,
+    ) -> Result<bool> {
+        self.verify_liveness_bound(user_id, proof, region, false)
+            .await
+    }
+
+    async fn verify_liveness_bound(
+        &self,
+        user_id: &str,
+        proof: &str,
+        region: Option<&str>,
+        consume_use: bool,
+    ) -> Result<bool> {
+        let check = self.liveness_checks.read().await.get(user_id).cloned();
+        let Some(check) = check.filter(|c| Utc::now() - c.verified_at <= self.liveness_window)
+        else {
+            return Ok(false);
+        };
 
-        // Check expiration
-        if let Some(expires_at) = record.expires_at {
-            if Utc::now() > expires_at {
-                return Ok(false);
+        let audience = LivenessChallenge::audience(&check.nonce);
+        Ok(self
+            .verify_bound(user_id, proof, Some(&audience), region, consume_use)
+            .await?
+            .valid)
+    }
+
+    async fn issue_proof(&self, user_id: &str, audience: Option<&str>) -> Result<String> {
+        let now = Utc::now();
+        if let Some(limiter) = &self.proof_limiter {
+            if !limiter.try_acquire(user_id, now) {
+                return Err(ConsentError::RateLimited(format!(
+                    "proof generation limit reached for {}",
+                    user_id
+                )));
             }
         }
 
+        let record = self.resolve_record(user_id).await?;
+        if !record.is_active_at(now) {
+            return Err(ConsentError::AttestationInvalid(format!(
+                "no active consent for {}",
+                user_id
+            )));
+        }
+        Ok(self.expected_proof(&record, audience))
+    }
+
+    async fn verify_bound(
+        &self,
+        user_id: &str,
+        proof: &str,
+        audience: Option<&str>,
+        region: Option<&str>,
+        consume_use: bool,
+    ) -> Result<ConsentVerification> {
+        // Retrieve consent record from blockchain
+        let record = self.resolve_record(user_id).await?;
+        let mut verification = ConsentVerification {
+            valid: false,
+            consent_stale: self.terms_stale(&record),
+            terms_version: record.terms_version,
+            active_terms_version: self.terms_version(),
+        };
+
+        // Check status, expiration, inactivity, and terms
+        let now = Utc::now();
+        if !record.is_active_at(now)
+            || self.lapsed_by_inactivity(user_id, &record, now).await
+            || verification.consent_stale
+            || !self.delegation_holds(user_id, now).await?
+        {
+            let kind = if record.is_expired_at(now) {
+                AnalyticsEventKind::Expire
+            } else {
+                AnalyticsEventKind::Deny
+            };
+            self.emit_analytics(kind, user_id);
+            return Ok(verification);
+        }
+
+        if !record.permits_region(region) {
+            tracing::warn!(
+                "Consent for {} used from region {:?} outside {:?}",
+                user_id,
+                region,
+                record.allowed_regions
+            );
+            self.emit_analytics(AnalyticsEventKind::Deny, user_id);
+            return Ok(verification);
+        }
+
         // Verify proof signature
-        self.verify_proof_signature(proof, &record.tx_hash)
-            .await
+        verification.valid = proof == self.expected_proof(&record, audience);
+        if verification.valid && consume_use && record.max_uses.is_some() {
+            verification.valid = self.consume_use(&record.user_id).await?;
+        }
+        if !verification.valid {
+            self.emit_analytics(AnalyticsEventKind::Deny, user_id);
+        }
+        Ok(verification)
+    }
+
+    /// Spend one use of a user's consent checked without spending one
+    ///
+    /// Pairs with [`ConsentEngine::check_consent_in_region`] for callers
+    /// that only spend a use once the gated action actually runs. Returns
+    /// false once a use-limited consent is exhausted.
+    pub async fn spend_use(&self, user_id: &str) -> Result<bool> {
+        let record = self.resolve_record(user_id).await?;
+        self.consume_use(&record.user_id).await
+    }
+
+    /// Spend one use of a use-limited consent, returning false once
+    /// exhausted
+    async fn consume_use(&self, user_id: &str) -> Result<bool> {
+        let _guard = self.record_lock.lock().await;
+        // Re-read under the lock so each use is counted exactly once
+        let mut record = self.fetch_record(user_id).await?;
+        let remaining = match record.remaining_uses {
+            None => return Ok(true),
+            Some(0) => return Ok(false),
+            Some(n) => n - 1,
+        };
+        record.remaining_uses = Some(remaining);
+        self.blockchain_client.store_record(record).await;
+        self.audit_log
+            .record(
+                user_id,
+                AuditAction::UseConsumed,
+                format!("{} uses remaining", remaining),
+            )
+            .await;
+        Ok(true)
+    }
+
+    /// Report the state of the consent backing `proof`
+    ///
+    /// Results are cached when an introspection TTL is configured; a user's
+    /// cached results are dropped as soon as their consent is revoked.
+    pub async fn introspect_proof(&self, user_id: &str, proof: &str) -> Result<ProofIntrospection> {
+        let now = Utc::now();
+        if let Some(cache) = &self.introspection_cache {
+            if let Some(cached) = cache.get(user_id, proof, now).await {
+                return Ok(cached);
+            }
+        }
+
+        // Introspection reports on the consent without spending a use
+        let verification = self.verify_bound(user_id, proof, None, None, false).await?;
+        let record = self.resolve_record(user_id).await?;
+        let introspection = ProofIntrospection {
+            user_id: record.user_id,
+            active: verification.valid,
+            terms_version: record.terms_version,
+            expires_at: record.expires_at,
+            introspected_at: now,
+        };
+        if let Some(cache) = &self.introspection_cache {
+            cache.store(user_id, proof, introspection.clone()).await;
+        }
+        Ok(introspection)
+    }
+
+    /// Verify user consent for a specific scope, honouring per-scope expiry
+    pub async fn verify_consent_scoped(
+        &self,
+        user_id: &str,
+        proof: &str,
+        scope: &str,
+    ) -> Result<bool> {
+        Ok(self
+            .verify_scope_or_escalate(user_id, proof, scope)
+            .await?
+            .granted)
+    }
+
+    /// Verify a scope, escalating through the configured handler if missing
+    ///
+    /// When the handler obtains consent it returns a replacement proof,
+    /// which is verified against the refreshed record and surfaced to the
+    /// caller in [`ScopedVerification::updated_proof`].
+    pub async fn verify_scope_or_escalate(
+        &self,
+        user_id: &str,
+        proof: &str,
+        scope: &str,
+    ) -> Result<ScopedVerification> {
+        let denied = ScopedVerification {
+            granted: false,
+            updated_proof: None,
+        };
+        let record = self.resolve_record(user_id).await?;
+        let now = Utc::now();
+
+        if !record.is_active_at(now)
+            || self.lapsed_by_inactivity(user_id, &record, now).await
+            || self.terms_stale(&record)
+            || !self.delegation_holds(user_id, now).await?
+        {
+            return Ok(denied);
+        }
+
+        if self.scope_granted(&record, scope, now) {
+            if !self.guardian_grants_scope(user_id, scope, now).await? {
+                return Ok(denied);
+            }
+            return Ok(ScopedVerification {
+                granted: self.verify_proof_signature(proof, &record).await?,
+                updated_proof: None,
+            });
+        }
+
+        let Some(handler) = &self.escalation_handler else {
+            return Ok(denied);
+        };
+        let Some(updated_proof) = handler.escalate(user_id, scope).await else {
+            return Ok(denied);
+        };
+
+        let record = self.resolve_record(user_id).await?;
+        if !record.is_active_at(now) || !self.scope_granted(&record, scope, now) {
+            return Ok(denied);
+        }
+
+        let granted = self.verify_proof_signature(&updated_proof, &record).await?;
+        if granted {
+            self.audit_log
+                .record(user_id, AuditAction::ScopeEscalated, scope)
+                .await;
+        }
+
+        Ok(ScopedVerification {
+            granted,
+            updated_proof: Some(updated_proof),
+        })
+    }
+
+    /// Whether a user's active consent grants `scope`, directly or through
+    /// the scope hierarchy
+    ///
+    /// Unlike [`ConsentEngine::verify_consent_scoped`] no proof is checked
+    /// and missing scopes are not escalated.
+    pub async fn has_scope(&self, user_id: &str, scope: &str) -> Result<bool> {
+        let record = self.resolve_record(user_id).await?;
+        let now = Utc::now();
+        Ok(record.is_active_at(now)
+            && self.scope_granted(&record, scope, now)
+            && self.guardian_grants_scope(user_id, scope, now).await?)
+    }
+
+    /// Grant a scope on a user's consent, optionally with its own expiry
+    pub async fn grant_scope(
+        &self,
+        user_id: &str,
+        scope: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        self.update_record(user_id, |record| {
+            if !record.scopes.iter().any(|s| s == scope) {
+                record.scopes.push(scope.to_string());
+            }
+            match expires_at {
+                Some(expires_at) => record.scope_expiry.insert(scope.to_string(), expires_at),
+                None => record.scope_expiry.remove(scope),
+            };
+        })
+        .await
     }
 
     /// Request consent from user
     pub async fn request_consent(&self, user_id: &str) -> Result<ConsentRecord> {
-        // Verify age (21+)
-        let age = self
-            .provider
-            .verify_age(user_id)
+        let (age, discipline_proof) = self.check_eligibility(user_id).await?;
+        self.record_attestation(
+            ConsentAttestation {
+                user_id: user_id.to_string(),
+                age,
+                discipline_proof,
+                timestamp: Utc::now(),
+            },
+            self.terms_version(),
+        )
+        .await
+    }
+
+    /// Run eligibility checks and show the attestation without recording it
+    ///
+    /// The preview can be committed with [`ConsentEngine::commit_consent`]
+    /// until [`ConsentPreview::valid_until`].
+    pub async fn preview_consent(&self, user_id: &str) -> Result<ConsentPreview> {
+        let (age, discipline_proof) = self.check_eligibility(user_id).await?;
+        let now = Utc::now();
+        let preview = ConsentPreview {
+            preview_id: Uuid::new_v4(),
+            user_id: user_id.to_string(),
+            age,
+            min_age: self.min_age,
+            discipline_proof,
+            terms_version: self.terms_version(),
+            attested_at: now,
+            valid_until: now + Duration::minutes(preview::PREVIEW_VALIDITY_MINUTES),
+        };
+
+        self.previews
+            .write()
             .await
-            .map_err(|e| ConsentError::ProviderError(e.to_string()))?;
+            .insert(preview.preview_id, preview.clone());
+        Ok(preview)
+    }
+
+    /// Record the exact terms of a previously issued preview
+    pub async fn commit_consent(&self, preview_id: Uuid) -> Result<ConsentRecord> {
+        let preview = self
+            .previews
+            .write()
+            .await
+            .remove(&preview_id)
+            .ok_or_else(|| {
+                ConsentError::AttestationInvalid(format!("unknown preview {}", preview_id))
+            })?;
+
+        if Utc::now() > preview.valid_until {
+            return Err(ConsentError::AttestationInvalid(format!(
+                "preview {} expired at {}",
+                preview_id, preview.valid_until
+            )));
+        }
+
+        if preview.terms_version != self.terms_version() {
+            return Err(ConsentError::AttestationInvalid(format!(
+                "preview {} was issued under superseded terms version {}",
+                preview_id, preview.terms_version
+            )));
+        }
+
+        self.record_attestation(
+            ConsentAttestation {
+                user_id: preview.user_id,
+                age: preview.age,
+                discipline_proof: preview.discipline_proof,
+                timestamp: preview.attested_at,
+            },
+            preview.terms_version,
+        )
+        .await
+    }
+
+    /// Verify age and discipline eligibility, returning both proofs
+    async fn check_eligibility(&self, user_id: &str) -> Result<(u8, String)> {
+        // Verify age (21+), reusing a cached attestation within its TTL
+        let now = Utc::now();
+        let cached = match &self.attestation_cache {
+            Some(cache) => cache.fresh(user_id, now).await,
+            None => None,
+        };
+        let age = match cached {
+            Some(age) => age,
+            None => {
+                let verified = self.provider.verify_age(user_id).await;
+                self.age_or_last_known_good(user_id, verified, now).await?
+            }
+        };
 
         if age < self.min_age {
             return Err(ConsentError::AgeRequirementNotMet(age));
@@ -165,14 +1165,114 @@ impl ConsentEngine {
             .await
             .map_err(|e| ConsentError::DisciplineIneligible(e.to_string()))?;
 
-        // Create attestation
-        let attestation = ConsentAttestation {
-            user_id: user_id.to_string(),
-            age,
-            discipline_proof: discipline_proof.clone(),
-            timestamp: Utc::now(),
+        if let Some(scorer) = &self.discipline_scorer {
+            let score = scorer
+                .discipline_score(user_id)
+                .await
+                .map_err(|e| ConsentError::ProviderError(e.to_string()))?;
+            if score < self.min_discipline_score {
+                return Err(ConsentError::DisciplineIneligible(format!(
+                    "score {:.3} below threshold {:.3}",
+                    score, self.min_discipline_score
+                )));
+            }
+        }
+
+        Ok((age, discipline_proof))
+    }
+
+    /// Accept a provider age result, caching it, or fall back to the
+    /// last-known-good attestation if the provider failed
+    async fn age_or_last_known_good(
+        &self,
+        user_id: &str,
+        verified: anyhow::Result<u8>,
+        now: DateTime<Utc>,
+    ) -> Result<u8> {
+        let cache = self.attestation_cache.as_ref();
+        let error = match verified {
+            Ok(age) => {
+                if let Some(cache) = cache {
+                    cache.store(user_id, age, now).await;
+                }
+                return Ok(age);
+            }
+            Err(e) => e,
+        };
+
+        let fallback = match (cache, self.age_fallback_window) {
+            (Some(cache), Some(window)) => cache.last_known_good(user_id, now, window).await,
+            _ => None,
         };
+        match fallback {
+            Some(cached) => {
+                tracing::error!(
+                    "Age provider unavailable for {} ({}); using last-known-good attestation from {}",
+                    user_id,
+                    error,
+                    cached.verified_at
+                );
+                Ok(cached.age)
+            }
+            None => Err(ConsentError::ProviderError(error.to_string())),
+        }
+    }
+
+    /// Record several attestations in one transaction
+    ///
+    /// Only the batch's Merkle root goes on chain; each returned proof, in
+    /// input order, lets its user show their attestation is in the batch.
+    pub async fn record_consent_batch(
+        &self,
+        attestations: Vec<ConsentAttestation>,
+    ) -> Result<Vec<InclusionProof>> {
+        if attestations.is_empty() {
+            return Err(ConsentError::AttestationInvalid(
+                "empty attestation batch".to_string(),
+            ));
+        }
+        if let Some(attestation) = attestations.iter().find(|a| a.age < self.min_age) {
+            return Err(ConsentError::AgeRequirementNotMet(attestation.age));
+        }
+
+        let leaves = attestations
+            .iter()
+            .map(batch::leaf_hash)
+            .collect::<Result<Vec<_>>>()?;
+        let (batch_root, paths) = batch::merkle_tree(leaves);
+        let tx_hash = self
+            .blockchain_client
+            .record_consent_batch(&batch_root)
+            .await
+            .map_err(|e| ConsentError::BlockchainError(e.to_string()))?;
+
+        let terms_version = self.terms_version();
+        let mut proofs = Vec::with_capacity(attestations.len());
+        for (index, (attestation, path)) in attestations.into_iter().zip(paths).enumerate() {
+            proofs.push(InclusionProof {
+                user_id: attestation.user_id.clone(),
+                tx_hash: tx_hash.clone(),
+                batch_root: batch_root.clone(),
+                index,
+                path,
+            });
+            self.index_attestation(attestation, tx_hash.clone(), terms_version)
+                .await;
+        }
+        Ok(proofs)
+    }
+
+    /// Consent records committed by a blockchain transaction
+    pub async fn find_by_tx(&self, tx_hash: &str) -> Vec<ConsentRecord> {
+        self.blockchain_client.get_record_by_tx(tx_hash).await
+    }
 
+    /// Record an attestation on chain and index the resulting record
+    async fn record_attestation(
+        &self,
+        attestation: ConsentAttestation,
+        terms_version: u32,
+    ) -> Result<ConsentRecord> {
         // Record on blockchain
         let tx_hash = self
             .blockchain_client
@@ -180,17 +1280,48 @@ impl ConsentEngine {
             .await
             .map_err(|e| ConsentError::BlockchainError(e.to_string()))?;
 
-        Ok(ConsentRecord {
-            id: Uuid::new_v4(),
-            user_id: user_id.to_string(),
-            status: ConsentStatus::Active,
-            granted_at: Utc::now(),
-            expires_at: None,
-            revoked_at: None,
+        Ok(self
+            .index_attestation(attestation, tx_hash, terms_version)
+            .await)
+    }
+
+    /// Index the record for an attestation recorded in `tx_hash`
+    async fn index_attestation(
+        &self,
+        attestation: ConsentAttestation,
+        tx_hash: String,
+        terms_version: u32,
+    ) -> ConsentRecord {
+        let mut record = ConsentRecord::new(
+            &attestation.user_id,
             tx_hash,
-            age_proof: format!("age:{}", age),
-            discipline_proof,
-        })
+            format!("age:{}", attestation.age),
+            attestation.discipline_proof,
+        );
+        record.granted_at = attestation.timestamp;
+        record.terms_version = terms_version;
+        record.assurance_level = self.provider_assurance;
+        record.expires_at = self.expiry_policy.map(|p| p.expiry_for(record.granted_at));
+        let renewed = self.blockchain_client.has_record(&record.user_id).await;
+        {
+            let _guard = self.record_lock.lock().await;
+            self.blockchain_client.store_record(record.clone()).await;
+        }
+        self.audit_log
+            .record(
+                &record.user_id,
+                AuditAction::Granted,
+                record.tx_hash.clone(),
+            )
+            .await;
+        let kind = if renewed {
+            AnalyticsEventKind::Renew
+        } else {
+            AnalyticsEventKind::Grant
+        };
+        self.emit_analytics(kind, &record.user_id);
+
+        record
     }
 
     /// Revoke consent
@@ -198,13 +1329,935 @@ impl ConsentEngine {
         self.blockchain_client
             .revoke_consent(user_id)
             .await
-            .map_err(|e| ConsentError::BlockchainError(e.to_string()))
+            .map_err(|e| ConsentError::BlockchainError(e.to_string()))?;
+
+        let tx_hash = self
+            .update_record(user_id, |record| {
+                record.status = ConsentStatus::Revoked;
+                record.revoked_at = Some(Utc::now());
+                record.tx_hash.clone()
+            })
+            .await?;
+        self.audit_log
+            .record(user_id, AuditAction::Revoked, tx_hash)
+            .await;
+        self.emit_analytics(AnalyticsEventKind::Revoke, user_id);
+        // No subscribers is fine
+        let _ = self.revocations.send(RevocationEvent {
+            user_id: user_id.to_string(),
+            revoked_at: Utc::now(),
+        });
+        Ok(())
+    }
+
+    /// Subscribe to revocation events
+    pub fn subscribe_revocations(&self) -> broadcast::Receiver<RevocationEvent> {
+        self.revocations.subscribe()
+    }
+
+    /// Issue a signed receipt proving a user's consent was revoked
+    pub async fn generate_revocation_receipt(
+        &self,
+        user_id: &str,
+    ) -> Result<RevocationAttestation> {
+        let record = self.fetch_record(user_id).await?;
+        let revoked_at = match (record.status, record.revoked_at) {
+            (ConsentStatus::Revoked, Some(revoked_at)) => revoked_at,
+            _ => {
+                return Err(ConsentError::AttestationInvalid(format!(
+                    "consent for {} has not been revoked",
+                    user_id
+                )))
+            }
+        };
+        RevocationAttestation::sign(user_id, &record.tx_hash, revoked_at, &self.platform_key)
+    }
+
+    /// Verify a revocation receipt was issued by this engine's platform key
+    pub fn verify_revocation_receipt(&self, receipt: &RevocationAttestation) -> Result<()> {
+        receipt.verify(&self.platform_public_key())
+    }
+
+    /// Collapse a user's attestation chain into its current record
+    ///
+    /// The chain is replaced by the latest record, and a platform-signed
+    /// summary committing to the full history root is kept alongside it.
+    /// The chain that follows is rooted at the summary, so the next
+    /// compaction still covers everything before it.
+    pub async fn compact_chain(&self, user_id: &str) -> Result<ChainSummary> {
+        let chain = self.blockchain_client.chain(user_id).await;
+        let current = chain.last().cloned().ok_or_else(|| {
+            ConsentError::AttestationInvalid(format!("no attestation chain for {}", user_id))
+        })?;
+
+        let previous = self.blockchain_client.chain_summary(user_id).await;
+        if let Some(previous) = &previous {
+            previous.verify(&self.platform_public_key())?;
+        }
+        let seed = previous
+            .as_ref()
+            .map_or(compaction::GENESIS_ROOT, |p| p.history_root.as_str());
+        let history_root = compaction::chain_root(seed, &chain)?;
+        let entries = previous.map_or(0, |p| p.entries) + chain.len();
+
+        let summary = ChainSummary::sign(
+            user_id,
+            history_root,
+            entries,
+            &current.tx_hash,
+            &self.platform_key,
+        )?;
+        self.blockchain_client
+            .replace_chain(user_id, current, summary.clone())
+            .await;
+        Ok(summary)
+    }
+
+    /// Publish a commitment to a user's current consent record
+    ///
+    /// Only the salted digest goes on chain; the salt is kept off-chain so
+    /// the record can later be checked with
+    /// [`ConsentEngine::verify_against_commitment`].
+    pub async fn publish_commitment(&self, user_id: &str) -> Result<ConsentCommitment> {
+        let record = self.fetch_record(user_id).await?;
+        let opening = CommitmentOpening::generate();
+        let digest = opening.digest(&record)?;
+        let tx_hash = self
+            .blockchain_client
+            .publish_commitment(&digest)
+            .await
+            .map_err(|e| ConsentError::BlockchainError(e.to_string()))?;
+        self.commitment_openings
+            .write()
+            .await
+            .insert(record.id, opening);
+        Ok(ConsentCommitment {
+            record_id: record.id,
+            digest,
+            tx_hash,
+            published_at: Utc::now(),
+        })
+    }
+
+    /// Whether `record` is exactly the record `commitment` was published for
+    pub async fn verify_against_commitment(
+        &self,
+        record: &ConsentRecord,
+        commitment: &ConsentCommitment,
+    ) -> Result<bool> {
+        if record.id != commitment.record_id {
+            return Ok(false);
+        }
+        let openings = self.commitment_openings.read().await;
+        let opening = openings.get(&record.id).ok_or_else(|| {
+            ConsentError::AttestationInvalid(format!(
+                "no commitment published by this platform for record {}",
+                record.id
+            ))
+        })?;
+        Ok(opening.digest(record)? == commitment.digest)
+    }
+
+    /// Check a chain summary was signed by this platform
+    pub fn verify_chain_summary(&self, summary: &ChainSummary) -> Result<()> {
+        summary.verify(&self.platform_public_key())
+    }
+
+    /// Bundle all consent data held for a user into a signed export
+    pub async fn export_user_data(&self, user_id: &str) -> Result<UserDataExport> {
+        let current = if self.blockchain_client.has_record(user_id).await {
+            Some(self.fetch_record(user_id).await?)
+        } else {
+            None
+        };
+        let revocation_receipt = match &current {
+            Some(record) if record.status == ConsentStatus::Revoked => {
+                Some(self.generate_revocation_receipt(user_id).await?)
+            }
+            _ => None,
+        };
+        let archived = self
+            .blockchain_client
+            .archived_records()
+            .await
+            .into_iter()
+            .filter(|r| r.user_id == user_id)
+            .collect();
+
+        UserDataExport {
+            user_id: user_id.to_string(),
+            exported_at: Utc::now(),
+            current,
+            history: self.blockchain_client.chain(user_id).await,
+            chain_summary: self.blockchain_client.chain_summary(user_id).await,
+            archived,
+            revocation_receipt,
+            audit_entries: self.audit_log.entries_for(user_id).await,
+            signature: String::new(),
+        }
+        .sign(&self.platform_key)
+    }
+
+    /// Erase a user's consent data, returning a signed receipt
+    ///
+    /// Records, chain history, and archived copies are removed along with
+    /// cached attestations, introspections, previews, override tokens and
+    /// other per-user state. Audit entries, and delegations the user made
+    /// as a guardian, are pseudonymized. With `keep_legal_hold`, a user
+    /// under legal hold is refused and nothing is erased; otherwise the
+    /// hold is overridden and noted on the receipt.
+    pub async fn erase_user_data(
+        &self,
+        user_id: &str,
+        keep_legal_hold: bool,
+    ) -> Result<ErasureReceipt> {
+        let held = self.blockchain_client.has_record(user_id).await
+            && self.fetch_record(user_id).await?.legal_hold;
+        if held && keep_legal_hold {
+            return Err(ConsentError::LegalHold(format!(
+                "records for {} are under legal hold",
+                user_id
+            )));
+        }
+
+        let subject = erasure::pseudonym(self.pseudonym_key.as_slice(), user_id);
+        let erased = {
+            let _guard = self.record_lock.lock().await;
+            self.blockchain_client.erase_user(user_id).await
+        };
+        self.last_active.write().await.remove(user_id);
+        self.liveness_checks.write().await.remove(user_id);
+
+        // Subjects keep depending on the erased guardian, now by pseudonym
+        let wards: Vec<String> = {
+            let mut delegations = self.delegations.write().await;
+            delegations.remove(user_id);
+            delegations
+                .values_mut()
+                .filter(|d| d.guardian_id == user_id)
+                .map(|d| {
+                    d.guardian_id = subject.clone();
+                    d.subject_id.clone()
+                })
+                .collect()
+        };
+        let delegated_by = format!("delegated:{}", user_id);
+        for ward in wards {
+            self.update_record(&ward, |record| {
+                if record.age_proof == delegated_by {
+                    record.age_proof = format!("delegated:{}", subject);
+                }
+            })
+            .await?;
+        }
+
+        self.overrides
+            .write()
+            .await
+            .retain(|_, token| token.user_id != user_id);
+        self.expiry_notices.forget(user_id);
+        self.previews
+            .write()
+            .await
+            .retain(|_, preview| preview.user_id != user_id);
+        self.liveness_challenges
+            .write()
+            .await
+            .retain(|_, challenge| challenge.user_id != user_id);
+        if let Some(cache) = &self.attestation_cache {
+            cache.remove(user_id).await;
+        }
+        if let Some(cache) = &self.introspection_cache {
+            cache.remove_user(user_id).await;
+        }
+        if let Some(limiter) = &self.proof_limiter {
+            limiter.remove(user_id);
+        }
+        self.account_links
+            .write()
+            .await
+            .retain(|secondary, primary| secondary != user_id && primary != user_id);
+
+        let anonymized_audit_entries = self.audit_log.pseudonymize(user_id, &subject).await;
+        self.audit_log
+            .record(
+                &subject,
+                AuditAction::Erased,
+                format!("{} records erased", erased.len()),
+            )
+            .await;
+
+        ErasureReceipt {
+            subject,
+            erased_records: erased.len(),
+            erased_root: compaction::chain_root(compaction::GENESIS_ROOT, &erased)?,
+            anonymized_audit_entries,
+            legal_hold_overridden: held,
+            erased_at: Utc::now(),
+            signature: String::new(),
+        }
+        .sign(&self.platform_key)
+    }
+
+    /// Issue a short-lived, platform-signed token for a user's active consent
+    pub async fn issue_token(&self, user_id: &str, ttl: Duration) -> Result<ConsentToken> {
+        let record = self.resolve_record(user_id).await?;
+        let now = Utc::now();
+
+        if !record.is_active_at(now) {
+            return Err(ConsentError::AttestationInvalid(format!(
+                "no active consent for {}",
+                user_id
+            )));
+        }
+
+        let claims = TokenClaims {
+            sub: user_id.to_string(),
+            scopes: record
+                .scopes
+                .iter()
+                .filter(|scope| record.scope_active_at(scope, now))
+                .cloned()
+                .collect(),
+            level: record.assurance_level,
+            iat: now,
+            exp: now + ttl,
+        };
+        ConsentToken::sign(&claims, &self.platform_key)
+    }
+
+    /// Verify a token issued by this engine, returning its claims
+    pub fn verify_token(&self, token: &ConsentToken) -> Result<TokenClaims> {
+        token.verify(&self.platform_public_key())
+    }
+
+    /// Attach an obligation to a user's consent
+    pub async fn add_obligation(&self, user_id: &str, obligation: Obligation) -> Result<()> {
+        self.update_record(user_id, |record| {
+            if !record.obligations.contains(&obligation) {
+                record.obligations.push(obligation);
+            }
+        })
+        .await
+    }
+
+    /// Obligations downstream systems must honor for a user's consent
+    pub async fn obligations_for(&self, user_id: &str) -> Result<Vec<Obligation>> {
+        Ok(self.resolve_record(user_id).await?.obligations)
+    }
+
+    /// Limit a user's consent to `max_uses` verifications, resetting the
+    /// remaining count; `None` lifts the limit
+    pub async fn limit_uses(&self, user_id: &str, max_uses: Option<u32>) -> Result<()> {
+        self.update_record(user_id, |record| {
+            record.max_uses = max_uses;
+            record.remaining_uses = max_uses;
+        })
+        .await
+    }
+
+    /// Verifications left on a user's use-limited consent; `None` if
+    /// unlimited
+    pub async fn remaining_uses(&self, user_id: &str) -> Result<Option<u32>> {
+        Ok(self.resolve_record(user_id).await?.remaining_uses)
+    }
+
+    /// Restrict where a user's consent may be used; `None` lifts the
+    /// restriction
+    pub async fn restrict_regions(
+        &self,
+        user_id: &str,
+        allowed_regions: Option<Vec<String>>,
+    ) -> Result<()> {
+        self.update_record(user_id, |record| record.allowed_regions = allowed_regions)
+            .await
+    }
+
+    /// Set the jurisdiction a user's consent was granted under
+    pub async fn set_jurisdiction(
+        &self,
+        user_id: &str,
+        jurisdiction: Option<String>,
+    ) -> Result<()> {
+        self.update_record(user_id, |record| record.jurisdiction = jurisdiction)
+            .await
+    }
+
+    /// Set the region where a user's consent is recorded
+    pub async fn set_origin_region(&self, user_id: &str, region: Option<String>) -> Result<()> {
+        self.update_record(user_id, |record| record.origin_region = region)
+            .await
+    }
+
+    /// Region where a user's consent is recorded, if known
+    pub async fn origin_region(&self, user_id: &str) -> Result<Option<String>> {
+        Ok(self.resolve_record(user_id).await?.origin_region)
+    }
+
+    /// Live records matching `query`, ordered by user ID
+    pub async fn search(&self, query: ConsentQuery) -> Vec<ConsentRecord> {
+        self.blockchain_client.search(&query).await
+    }
+
+    /// Set how long data associated with a user's consent may be retained
+    pub async fn set_retention(
+        &self,
+        user_id: &str,
+        retention: Option<std::time::Duration>,
+    ) -> Result<()> {
+        self.update_record(user_id, |record| record.retention = retention)
+            .await
+    }
+
+    /// Retention period dictated by a user's consent
+    ///
+    /// Inactive consent yields a zero retention so dependent data is purged;
+    /// `None` means consent places no bound on retention.
+    pub async fn retention_for(&self, user_id: &str) -> Option<std::time::Duration> {
+        let record = match self.resolve_record(user_id).await {
+            Ok(record) => record,
+            Err(e) => {
+                tracing::warn!("Retention lookup for {} failed: {}", user_id, e);
+                return None;
+            }
+        };
+
+        if !record.is_active_at(Utc::now()) {
+            return Some(std::time::Duration::ZERO);
+        }
+        record.retention
+    }
+
+    /// Place or release a legal hold on a user's consent record
+    ///
+    /// Held records are skipped by [`ConsentEngine::prune_records`].
+    pub async fn set_legal_hold(&self, user_id: &str, on: bool) -> Result<()> {
+        self.update_record(user_id, |record| record.legal_hold = on)
+            .await?;
+
+        let action = if on {
+            AuditAction::LegalHoldPlaced
+        } else {
+            AuditAction::LegalHoldReleased
+        };
+        self.audit_log
+            .record(user_id, action, "legal hold updated")
+            .await;
+        Ok(())
+    }
+
+    /// Archive records granted before `cutoff`, skipping legal holds
+    ///
+    /// Returns the user IDs whose records were archived.
+    pub async fn prune_records(&self, cutoff: DateTime<Utc>) -> Result<Vec<String>> {
+        let _guard = self.record_lock.lock().await;
+        let mut archived = Vec::new();
+
+        for record in self.blockchain_client.list_records().await {
+            if record.legal_hold || record.granted_at >= cutoff {
+                continue;
+            }
+
+            self.blockchain_client.archive_record(&record.user_id).await;
+            self.audit_log
+                .record(&record.user_id, AuditAction::Archived, "pruned")
+                .await;
+            archived.push(record.user_id);
+        }
+
+        Ok(archived)
+    }
+
+    /// Find active consents nearing expiry and issue deduplicated notices
+    ///
+    /// Users already notified within the configured interval are skipped,
+    /// so frequent sweeps do not repeat the warning.
+    pub async fn sweep_expiring(&self, now: DateTime<Utc>) -> Vec<ExpiryNotice> {
+        let mut notices = Vec::new();
+
+        for record in self.blockchain_client.list_records().await {
+            let Some(expires_at) = record.expires_at else {
+                continue;
+            };
+            if !record.is_active_at(now)
+                || !self.expiry_notices.expiring_soon(expires_at, now)
+                || !self.expiry_notices.try_notify(&record.user_id, now)
+            {
+                continue;
+            }
+
+            notices.push(ExpiryNotice {
+                user_id: record.user_id,
+                expires_at,
+                issued_at: now,
+            });
+        }
+
+        notices
+    }
+
+    /// Enqueue a renewal reminder for each active consent expiring within
+    /// `lead_time` of `now` that has not been reminded yet
+    ///
+    /// Returns the number of reminders enqueued.
+    pub async fn enqueue_renewal_reminders(
+        &self,
+        lead_time: Duration,
+        now: DateTime<Utc>,
+        notifier: &dyn RenewalNotifier,
+    ) -> usize {
+        let mut enqueued = 0;
+
+        for record in self.blockchain_client.list_records().await {
+            let Some(expires_at) = record.expires_at else {
+                continue;
+            };
+            if !record.is_active_at(now)
+                || expires_at - now > lead_time
+                || !self.renewal_reminders.try_claim(record.id)
+            {
+                continue;
+            }
+
+            notifier
+                .enqueue(RenewalReminder {
+                    record_id: record.id,
+                    user_id: record.user_id,
+                    consent_tx_hash: record.tx_hash,
+                    expires_at,
+                    enqueued_at: now,
+                })
+                .await;
+            enqueued += 1;
+        }
+
+        enqueued
+    }
+
+    /// Start a background task enqueuing renewal reminders on `schedule`
+    ///
+    /// Each scan reads the time from `clock`. Must be called from within a
+    /// Tokio runtime; stop the task with [`RenewalScheduler::stop`].
+    pub fn start_renewal_reminders(
+        &self,
+        schedule: RenewalSchedule,
+        notifier: Arc<dyn RenewalNotifier>,
+        clock: Arc<dyn ReminderClock>,
+    ) -> RenewalScheduler {
+        let engine = self.clone();
+        let (stop_tx, mut stop_rx) = tokio::sync::oneshot::channel();
+        let task = tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(schedule.cadence);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = &mut stop_rx => break,
+                    _ = ticks.tick() => {
+                        engine
+                            .enqueue_renewal_reminders(
+                                schedule.lead_time,
+                                clock.now(),
+                                notifier.as_ref(),
+                            )
+                            .await;
+                    }
+                }
+            }
+        });
+        RenewalScheduler::new(stop_tx, task)
+    }
+
+    /// Issue a time-boxed override of consent gating for `user_id`
+    ///
+    /// Requires a non-empty justification. The override is audited with the
+    /// operator and justification and announced at error level so it cannot
+    /// pass unnoticed.
+    pub async fn emergency_override(
+        &self,
+        operator_id: &str,
+        user_id: &str,
+        justification: &str,
+    ) -> Result<OverrideToken> {
+        if justification.trim().is_empty() {
+            return Err(ConsentError::AttestationInvalid(
+                "emergency override requires a justification".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let token = OverrideToken {
+            token: Uuid::new_v4().to_string(),
+            operator_id: operator_id.to_string(),
+            user_id: user_id.to_string(),
+            justification: justification.to_string(),
+            issued_at: now,
+            expires_at: now + self.override_ttl,
+        };
+
+        tracing::error!(
+            operator = operator_id,
+            user = user_id,
+            expires_at = %token.expires_at,
+            "EMERGENCY CONSENT OVERRIDE: {}",
+            justification
+        );
+        self.audit_log
+            .record_by(
+                Some(operator_id),
+                user_id,
+                AuditAction::EmergencyOverride,
+                justification,
+            )
+            .await;
+        self.overrides
+            .write()
+            .await
+            .insert(token.token.clone(), token.clone());
+
+        Ok(token)
+    }
+
+    /// Whether `token` is a live emergency override for `user_id`
+    pub async fn verify_override(&self, user_id: &str, token: &str) -> bool {
+        self.overrides
+            .read()
+            .await
+            .get(token)
+            .is_some_and(|o| o.permits(user_id, Utc::now()))
+    }
+
+    /// Link `secondary` to inherit `primary`'s active consent
+    ///
+    /// Verification for the secondary resolves to the primary's record, so
+    /// it carries the primary's scopes and expiries until unlinked.
+    pub async fn link_accounts(&self, primary: &str, secondary: &str) -> Result<()> {
+        if primary == secondary {
+            return Err(ConsentError::AttestationInvalid(
+                "cannot link an account to itself".to_string(),
+            ));
+        }
+
+        let record = self.resolve_record(primary).await?;
+        if !record.is_active_at(Utc::now()) {
+            return Err(ConsentError::AttestationInvalid(format!(
+                "primary account {} has no active consent",
+                primary
+            )));
+        }
+
+        // Link to the root primary so chains never form
+        self.account_links
+            .write()
+            .await
+            .insert(secondary.to_string(), record.user_id.clone());
+        self.audit_log
+            .record(secondary, AuditAction::AccountLinked, record.user_id)
+            .await;
+        Ok(())
+    }
+
+    /// Consent on behalf of `subject_id` under `guardian_id`'s active consent
+    ///
+    /// The subject's record expires at `expires_at` regardless of the
+    /// guardian's, and the delegation link itself lapses at `valid_until`.
+    pub async fn delegate_consent(
+        &self,
+        guardian_id: &str,
+        subject_id: &str,
+        expires_at: Option<DateTime<Utc>>,
+        valid_until: Option<DateTime<Utc>>,
+    ) -> Result<ConsentRecord> {
+        self.delegate(guardian_id, subject_id, Vec::new(), expires_at, valid_until)
+            .await
+    }
+
+    /// Consent on behalf of `subject_id`, delegating only `scopes`
+    ///
+    /// Each scope must be granted to the guardian. Scoped verification for
+    /// the subject succeeds only for the delegated scopes, and only while
+    /// the guardian still holds them.
+    pub async fn delegate_consent_scoped(
+        &self,
+        guardian_id: &str,
+        subject_id: &str,
+        scopes: &[&str],
+        expires_at: Option<DateTime<Utc>>,
+        valid_until: Option<DateTime<Utc>>,
+    ) -> Result<ConsentRecord> {
+        let guardian = self.resolve_record(guardian_id).await?;
+        let now = Utc::now();
+        if let Some(scope) = scopes
+            .iter()
+            .find(|scope| !self.scope_granted(&guardian, scope, now))
+        {
+            return Err(ConsentError::AttestationInvalid(format!(
+                "guardian {} cannot delegate scope {} it does not hold",
+                guardian_id, scope
+            )));
+        }
+        let scopes = scopes.iter().map(|s| s.to_string()).collect();
+        self.delegate(guardian_id, subject_id, scopes, expires_at, valid_until)
+            .await
+    }
+
+    async fn delegate(
+        &self,
+        guardian_id: &str,
+        subject_id: &str,
+        scopes: Vec<String>,
+        expires_at: Option<DateTime<Utc>>,
+        valid_until: Option<DateTime<Utc>>,
+    ) -> Result<ConsentRecord> {
+        let now = Utc::now();
+        if self.delegates_for(subject_id, guardian_id).await {
+            return Err(ConsentError::AttestationInvalid(format!(
+                "delegating {} to {} would create a cycle",
+                guardian_id, subject_id
+            )));
+        }
+
+        let guardian = self.resolve_record(guardian_id).await?;
+        if !guardian.is_active_at(now) {
+            return Err(ConsentError::AttestationInvalid(format!(
+                "guardian {} has no active consent",
+                guardian_id
+            )));
+        }
+
+        // Shares the guardian's transaction; the subject's proofs still
+        // differ from the guardian's since proofs bind the user
+        let mut record = ConsentRecord::new(
+            subject_id,
+            guardian.tx_hash.clone(),
+            format!("delegated:{}", guardian_id),
+            guardian.discipline_proof.clone(),
+        );
+        record.expires_at = expires_at;
+        record.terms_version = guardian.terms_version;
+        record.scopes = scopes.clone();
+        {
+            let _guard = self.record_lock.lock().await;
+            self.blockchain_client.store_record(record.clone()).await;
+        }
+
+        self.delegations.write().await.insert(
+            subject_id.to_string(),
+            Delegation {
+                guardian_id: guardian_id.to_string(),
+                subject_id: subject_id.to_string(),
+                created_at: now,
+                valid_until,
+                scopes,
+            },
+        );
+        self.audit_log
+            .record_by(
+                Some(guardian_id),
+                subject_id,
+                AuditAction::Delegated,
+                guardian.tx_hash,
+            )
+            .await;
+        Ok(record)
+    }
+
+    /// Whether `ancestor` is `user_id` or one of its guardians, transitively
+    async fn delegates_for(&self, ancestor: &str, user_id: &str) -> bool {
+        let delegations = self.delegations.read().await;
+        let mut current = user_id;
+        // Bounded by the number of links in case the map was corrupted
+        for _ in 0..=delegations.len() {
+            if current == ancestor {
+                return true;
+            }
+            match delegations.get(current) {
+                Some(delegation) => current = &delegation.guardian_id,
+                None => return false,
+            }
+        }
+        false
+    }
+
+    /// Guardian-to-subject delegation edges with their current status
+    pub async fn delegation_graph(&self) -> Result<DelegationGraph> {
+        let delegations: Vec<Delegation> =
+            self.delegations.read().await.values().cloned().collect();
+        let now = Utc::now();
+
+        let mut edges = Vec::with_capacity(delegations.len());
+        for delegation in delegations {
+            let status = if !delegation.is_valid_at(now) {
+                DelegationStatus::LinkExpired
+            } else if !self
+                .fetch_record(&delegation.subject_id)
+                .await?
+                .is_active_at(now)
+            {
+                DelegationStatus::SubjectExpired
+            } else if !self
+                .resolve_record(&delegation.guardian_id)
+                .await?
+                .is_active_at(now)
+            {
+                DelegationStatus::GuardianInactive
+            } else {
+                DelegationStatus::Active
+            };
+            edges.push(DelegationEdge {
+                guardian_id: delegation.guardian_id,
+                subject_id: delegation.subject_id,
+                status,
+            });
+        }
+        edges.sort_by(|a, b| (&a.guardian_id, &a.subject_id).cmp(&(&b.guardian_id, &b.subject_id)));
+        Ok(DelegationGraph { edges })
+    }
+
+    /// Whether a subject's delegation, if any, still holds at `now`
+    async fn delegation_holds(&self, user_id: &str, now: DateTime<Utc>) -> Result<bool> {
+        let Some(delegation) = self.delegations.read().await.get(user_id).cloned() else {
+            return Ok(true);
+        };
+        if !delegation.is_valid_at(now) {
+            return Ok(false);
+        }
+        Ok(self
+            .resolve_record(&delegation.guardian_id)
+            .await?
+            .is_active_at(now))
+    }
+
+    /// Remove an account link, revoking the consent it inherited
+    pub async fn unlink_account(&self, secondary: &str) -> Result<()> {
+        let Some(primary) = self.account_links.write().await.remove(secondary) else {
+            return Ok(());
+        };
+
+        self.audit_log
+            .record(secondary, AuditAction::AccountUnlinked, primary)
+            .await;
+        Ok(())
+    }
+
+    /// Record user activity, deferring inactivity-based expiry
+    pub async fn touch_activity(&self, user_id: &str) {
+        self.last_active
+            .write()
+            .await
+            .insert(user_id.to_string(), Utc::now());
+    }
+
+    /// Whether the record was granted under superseded terms
+    fn terms_stale(&self, record: &ConsentRecord) -> bool {
+        record.terms_version < self.terms_version()
+    }
+
+    /// Whether consent lapsed because the user was inactive too long
+    async fn lapsed_by_inactivity(
+        &self,
+        user_id: &str,
+        record: &ConsentRecord,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let Some(timeout) = self.inactivity_timeout else {
+            return false;
+        };
+        let last_active = self
+            .last_active
+            .read()
+            .await
+            .get(user_id)
+            .copied()
+            .map_or(record.granted_at, |t| t.max(record.granted_at));
+        now - last_active > timeout
+    }
+
+    /// Whether `scope`, or a scope implying it, is active on `record`
+    fn scope_granted(&self, record: &ConsentRecord, scope: &str, now: DateTime<Utc>) -> bool {
+        self.scope_hierarchy
+            .satisfying_scopes(scope)
+            .iter()
+            .any(|s| record.scope_active_at(s, now))
+    }
+
+    /// Whether the guardian behind a delegated user still holds `scope`;
+    /// true for users without a delegation
+    async fn guardian_grants_scope(
+        &self,
+        user_id: &str,
+        scope: &str,
+        now: DateTime<Utc>,
+    ) -> Result<bool> {
+        let Some(delegation) = self.delegations.read().await.get(user_id).cloned() else {
+            return Ok(true);
+        };
+        let guardian = self.resolve_record(&delegation.guardian_id).await?;
+        Ok(self.scope_granted(&guardian, scope, now))
+    }
+
+    /// Fetch the record governing `user_id`, following account links
+    async fn resolve_record(&self, user_id: &str) -> Result<ConsentRecord> {
+        let linked = self.account_links.read().await.get(user_id).cloned();
+        self.fetch_record(linked.as_deref().unwrap_or(user_id))
+            .await
+    }
+
+    /// Apply `update` to a user's stored record under the record lock
+    async fn update_record<T>(
+        &self,
+        user_id: &str,
+        update: impl FnOnce(&mut ConsentRecord) -> T,
+    ) -> Result<T> {
+        let _guard = self.record_lock.lock().await;
+        let mut record = self.fetch_record(user_id).await?;
+        let updated = update(&mut record);
+        self.blockchain_client.store_record(record).await;
+        Ok(updated)
+    }
+
+    async fn fetch_record(&self, user_id: &str) -> Result<ConsentRecord> {
+        self.blockchain_client
+            .get_consent_record(user_id)
+            .await
+            .map_err(|e| match e.downcast::<ConsentError>() {
+                Ok(e) => e,
+                Err(e) => ConsentError::BlockchainError(e.to_string()),
+            })
     }
 
-    async fn verify_proof_signature(&self, proof: &str, tx_hash: &str) -> Result<bool> {
+    /// Report a lifecycle event to the analytics sink, if one is set
+    fn emit_analytics(&self, kind: AnalyticsEventKind, user_id: &str) {
+        if let Some(sink) = &self.analytics {
+            sink.emit(AnalyticsEvent {
+                kind,
+                user_id: user_id.to_string(),
+                occurred_at: Utc::now(),
+            });
+        }
+    }
+
+    async fn verify_proof_signature(&self, proof: &str, record: &ConsentRecord) -> Result<bool> {
         // Verify cryptographic signature matches blockchain record
-        let expected_proof = cybulous_crypto::hash_data(&format!("{}:{}", tx_hash, self.min_age));
-        Ok(proof == expected_proof)
+        Ok(proof == self.expected_proof(record, None))
+    }
+
+    /// Proof for a consent record, optionally bound to an audience
+    ///
+    /// Proofs are bound to the record's user as well as its transaction,
+    /// since records share a transaction when recorded in one batch.
+    fn expected_proof(&self, record: &ConsentRecord, audience: Option<&str>) -> String {
+        // Hashed so user IDs containing separators cannot collide
+        let subject = cybulous_crypto::hash_data(&record.user_id);
+        match audience {
+            Some(audience) => cybulous_crypto::hash_data(&format!(
+                "{}:{}:{}:{}",
+                record.tx_hash, self.min_age, subject, audience
+            )),
+            None => cybulous_crypto::hash_data(&format!(
+                "{}:{}:{}",
+                record.tx_hash, self.min_age, subject
+            )),
+        }
     }
 }
 
@@ -212,6 +2265,13 @@ impl ConsentEngine {
 pub struct BlockchainClient {
     rpc_endpoint: String,
     address: String,
+    records: RwLock<HashMap<String, ConsentRecord>>,
+    archive: RwLock<Vec<ConsentRecord>>,
+    chains: RwLock<HashMap<String, Vec<ConsentRecord>>>,
+    chain_summaries: RwLock<HashMap<String, ChainSummary>>,
+    /// Users with records committed by each transaction
+    tx_index: RwLock<HashMap<String, Vec<String>>>,
+    attribute_index: RwLock<AttributeIndex>,
 }
 
 impl BlockchainClient {
@@ -220,58 +2280,1518 @@ impl BlockchainClient {
         Self {
             rpc_endpoint,
             address,
+            records: RwLock::new(HashMap::new()),
+            archive: RwLock::new(Vec::new()),
+            chains: RwLock::new(HashMap::new()),
+            chain_summaries: RwLock::new(HashMap::new()),
+            tx_index: RwLock::new(HashMap::new()),
+            attribute_index: RwLock::new(AttributeIndex::default()),
         }
     }
 
     /// Create mock client for testing
-    #[cfg(test)]
+    #[cfg(any(test, feature = "test-util"))]
     pub fn mock() -> Self {
-        Self {
-            rpc_endpoint: "http://localhost:26657".to_string(),
-            address: "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7".to_string(),
-        }
+        Self::new(
+            "http://localhost:26657".to_string(),
+            "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7".to_string(),
+        )
+    }
+
+    /// Check that the RPC endpoint is reachable
+    pub async fn ping(&self) -> anyhow::Result<()> {
+        // Implementation would query node status via cosmrs
+        tracing::debug!("Pinging {} for {}", self.rpc_endpoint, self.address);
+        Ok(())
     }
 
     /// Get consent record from blockchain
+    ///
+    /// Records failing [`ConsentRecord::validate`] are rejected with
+    /// [`ConsentError::AttestationInvalid`].
     pub async fn get_consent_record(&self, user_id: &str) -> anyhow::Result<ConsentRecord> {
+        if let Some(record) = self.records.read().await.get(user_id) {
+            record.validate()?;
+            return Ok(record.clone());
+        }
+
         // Query blockchain for consent record
         // Implementation would use cosmrs to interact with Bostrom chain
-        Ok(ConsentRecord {
-            id: Uuid::new_v4(),
-            user_id: user_id.to_string(),
-            status: ConsentStatus::Active,
-            granted_at: Utc::now(),
-            expires_at: None,
-            revoked_at: None,
-            tx_hash: "mock-tx-hash".to_string(),
-            age_proof: "age:25".to_string(),
-            discipline_proof: "discipline:verified".to_string(),
-        })
+        let record = ConsentRecord::new(
+            user_id,
+            "mock-tx-hash".to_string(),
+            "age:25".to_string(),
+            "discipline:verified".to_string(),
+        );
+        record.validate()?;
+        Ok(record)
     }
 
-    /// Record consent on blockchain
-    pub async fn record_consent(&self, attestation: &ConsentAttestation) -> anyhow::Result<String> {
-        // Submit transaction to blockchain
-        // Implementation would use cosmrs to create and broadcast transaction
-        Ok(format!("tx-hash-{}", attestation.user_id))
+    /// Index a record so subsequent queries return it
+    ///
+    /// Each stored revision is also appended to the user's attestation chain.
+    pub async fn store_record(&self, record: ConsentRecord) {
+        self.index_tx(&record).await;
+        self.chains
+            .write()
+            .await
+            .entry(record.user_id.clone())
+            .or_default()
+            .push(record.clone());
+        self.index_attributes(&record).await;
+        self.records
+            .write()
+            .await
+            .insert(record.user_id.clone(), record);
     }
 
-    /// Revoke consent on blockchain
-    pub async fn revoke_consent(&self, user_id: &str) -> anyhow::Result<()> {
-        // Submit revocation transaction
-        tracing::info!("Revoking consent for user: {}", user_id);
-        Ok(())
+    /// Remove every stored copy of a user's records, returning them
+    pub async fn erase_user(&self, user_id: &str) -> Vec<ConsentRecord> {
+        let mut erased = self
+            .chains
+            .write()
+            .await
+            .remove(user_id)
+            .unwrap_or_default();
+        self.chain_summaries.write().await.remove(user_id);
+        self.tx_index.write().await.retain(|_, users| {
+            users.retain(|u| u != user_id);
+            !users.is_empty()
+        });
+        if let Some(current) = self.records.write().await.remove(user_id) {
+            self.attribute_index.write().await.remove(&current);
+            if !erased.iter().any(|r| r.id == current.id) {
+                erased.push(current);
+            }
+        }
+        self.archive.write().await.retain(|r| {
+            if r.user_id == user_id {
+                erased.push(r.clone());
+                false
+            } else {
+                true
+            }
+        });
+        erased
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Revisions in a user's attestation chain since its last compaction
+    pub async fn chain(&self, user_id: &str) -> Vec<ConsentRecord> {
+        self.chains
+            .read()
+            .await
+            .get(user_id)
+            .cloned()
+            .unwrap_or_default()
+    }
 
-    #[tokio::test]
-    async fn test_consent_verification() {
+    /// Summary left by the user's last chain compaction
+    pub async fn chain_summary(&self, user_id: &str) -> Option<ChainSummary> {
+        self.chain_summaries.read().await.get(user_id).cloned()
+    }
+
+    /// Replace a user's chain with `current`, keeping `summary` of the rest
+    pub async fn replace_chain(
+        &self,
+        user_id: &str,
+        current: ConsentRecord,
+        summary: ChainSummary,
+    ) {
+        self.index_tx(&current).await;
+        self.chains
+            .write()
+            .await
+            .insert(user_id.to_string(), Vec::new());
+        self.chain_summaries
+            .write()
+            .await
+            .insert(user_id.to_string(), summary);
+        self.index_attributes(&current).await;
+        self.records
+            .write()
+            .await
+            .insert(user_id.to_string(), current);
+    }
+
+    /// Records committed by a transaction, live or archived
+    ///
+    /// A batched transaction commits several users' records.
+    pub async fn get_record_by_tx(&self, tx_hash: &str) -> Vec<ConsentRecord> {
+        let Some(users) = self.tx_index.read().await.get(tx_hash).cloned() else {
+            return Vec::new();
+        };
+        let records = self.records.read().await;
+        let archive = self.archive.read().await;
+        users
+            .iter()
+            .filter_map(|user_id| {
+                records
+                    .get(user_id)
+                    .filter(|r| r.tx_hash == tx_hash)
+                    .or_else(|| {
+                        archive
+                            .iter()
+                            .rev()
+                            .find(|r| &r.user_id == user_id && r.tx_hash == tx_hash)
+                    })
+                    .cloned()
+            })
+            .collect()
+    }
+
+    /// Live records matching `query`, ordered by user ID
+    pub async fn search(&self, query: &ConsentQuery) -> Vec<ConsentRecord> {
+        let candidates = self.attribute_index.read().await.candidates(query);
+        let records = self.records.read().await;
+        let users = candidates.unwrap_or_else(|| records.keys().cloned().collect());
+        users
+            .iter()
+            .filter_map(|user_id| records.get(user_id))
+            .filter(|record| query.matches(record))
+            .skip(query.offset)
+            .take(query.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+
+    /// Replace the indexed attributes of the user's live record with
+    /// those of `record`
+    async fn index_attributes(&self, record: &ConsentRecord) {
+        let records = self.records.read().await;
+        let mut index = self.attribute_index.write().await;
+        if let Some(previous) = records.get(&record.user_id) {
+            index.remove(previous);
+        }
+        index.insert(record);
+    }
+
+    async fn index_tx(&self, record: &ConsentRecord) {
+        let mut index = self.tx_index.write().await;
+        let users = index.entry(record.tx_hash.clone()).or_default();
+        if !users.contains(&record.user_id) {
+            users.push(record.user_id.clone());
+        }
+    }
+
+    /// Whether a record for `user_id` has been indexed
+    pub async fn has_record(&self, user_id: &str) -> bool {
+        self.records.read().await.contains_key(user_id)
+    }
+
+    /// All indexed records
+    pub async fn list_records(&self) -> Vec<ConsentRecord> {
+        self.records.read().await.values().cloned().collect()
+    }
+
+    /// Move a user's record from the live index into the archive
+    pub async fn archive_record(&self, user_id: &str) -> bool {
+        match self.records.write().await.remove(user_id) {
+            Some(record) => {
+                self.attribute_index.write().await.remove(&record);
+                self.archive.write().await.push(record);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Archived records
+    pub async fn archived_records(&self) -> Vec<ConsentRecord> {
+        self.archive.read().await.clone()
+    }
+
+    /// Record consent on blockchain
+    pub async fn record_consent(&self, attestation: &ConsentAttestation) -> anyhow::Result<String> {
+        // Submit transaction to blockchain
+        // Implementation would use cosmrs to create and broadcast transaction
+        Ok(format!("tx-hash-{}", attestation.user_id))
+    }
+
+    /// Record a batch of attestations on blockchain by their Merkle root
+    pub async fn record_consent_batch(&self, batch_root: &str) -> anyhow::Result<String> {
+        // Only the root is submitted; proofs are held by the batch's users
+        Ok(format!("tx-hash-batch-{}", batch_root))
+    }
+
+    /// Publish a record commitment digest on blockchain
+    pub async fn publish_commitment(&self, digest: &str) -> anyhow::Result<String> {
+        // Only the digest is submitted; record details stay off-chain
+        Ok(format!("tx-hash-commitment-{}", digest))
+    }
+
+    /// Revoke consent on blockchain
+    pub async fn revoke_consent(&self, user_id: &str) -> anyhow::Result<()> {
+        // Submit revocation transaction
+        tracing::info!("Revoking consent for user: {}", user_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    #[tokio::test]
+    async fn test_consent_verification() {
         let engine = ConsentEngine::mock();
         let result = engine.verify_consent("test-user", "test-proof").await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_expiry_jitter_spreads_renewals_within_band() {
+        let validity = Duration::days(30);
+        let jitter = Duration::days(2);
+        let engine = ConsentEngine::mock()
+            .with_expiry_policy(ExpiryPolicy::new(validity).with_jitter(jitter));
+
+        let mut offsets = Vec::new();
+        for i in 0..200 {
+            let record = engine
+                .request_consent(&format!("cohort-{}", i))
+                .await
+                .unwrap();
+            let expires_at = record.expires_at.expect("policy assigns an expiry");
+            offsets.push(expires_at - record.granted_at - validity);
+        }
+
+        assert!(offsets.iter().all(|o| o.abs() <= jitter));
+        let distinct: std::collections::HashSet<_> = offsets.iter().collect();
+        assert!(
+            distinct.len() > 150,
+            "only {} distinct expiries",
+            distinct.len()
+        );
+        // Uniform over the band: both halves populated, spread well beyond a day
+        let early = offsets.iter().filter(|o| **o < Duration::zero()).count();
+        assert!((50..=150).contains(&early), "{} of 200 early", early);
+        let spread = *offsets.iter().max().unwrap() - *offsets.iter().min().unwrap();
+        assert!(spread > Duration::days(2), "spread {}", spread);
+    }
+
+    #[test]
+    fn test_proof_format_negotiation() {
+        use negotiation::{AUDIENCE_TX_DIGEST_SCHEME, TX_DIGEST_SCHEME};
+
+        let engine = ConsentEngine::mock();
+        let agreed = engine
+            .negotiate_proof_format(&[
+                ProofFormat::new("tx-digest", 2),
+                ProofFormat::new(TX_DIGEST_SCHEME, 1),
+                ProofFormat::new(AUDIENCE_TX_DIGEST_SCHEME, 1),
+            ])
+            .unwrap();
+        assert_eq!(agreed, ProofFormat::new(TX_DIGEST_SCHEME, 1));
+
+        let refused = engine.negotiate_proof_format(&[ProofFormat::new("bbs-plus", 1)]);
+        assert!(matches!(
+            refused,
+            Err(ConsentError::UnsupportedProofFormat(_))
+        ));
+        assert!(engine.negotiate_proof_format(&[]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_malformed_record_rejected_on_read() {
+        let engine = ConsentEngine::mock();
+        let mut record = engine.request_consent("tampered").await.unwrap();
+        let proof = engine.expected_proof(&record, None);
+        assert!(engine.verify_consent("tampered", &proof).await.unwrap());
+
+        record.status = ConsentStatus::Revoked;
+        record.revoked_at = Some(record.granted_at - Duration::hours(1));
+        engine.blockchain_client.store_record(record.clone()).await;
+        assert!(matches!(
+            engine.verify_consent("tampered", &proof).await,
+            Err(ConsentError::AttestationInvalid(_))
+        ));
+
+        record.status = ConsentStatus::Active;
+        record.revoked_at = None;
+        record.tx_hash = "tx hash; drop".to_string();
+        assert!(record.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_find_record_by_tx_hash() {
+        let engine = ConsentEngine::mock();
+        let record = engine.request_consent("tx-user").await.unwrap();
+        engine.request_consent("other-user").await.unwrap();
+
+        let found = engine.find_by_tx(&record.tx_hash).await;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, record.id);
+        assert!(engine.find_by_tx("tx-hash-unknown").await.is_empty());
+
+        engine.blockchain_client.archive_record("tx-user").await;
+        assert_eq!(engine.find_by_tx(&record.tx_hash).await.len(), 1);
+        engine.erase_user_data("tx-user", false).await.unwrap();
+        assert!(engine.find_by_tx(&record.tx_hash).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_batch_inclusion_proofs_verify_per_user() {
+        let engine = ConsentEngine::mock();
+        let attestations: Vec<_> = ["batch-a", "batch-b", "batch-c"]
+            .iter()
+            .map(|user| ConsentAttestation {
+                user_id: user.to_string(),
+                age: 25,
+                discipline_proof: "discipline-ok".to_string(),
+                timestamp: Utc::now(),
+            })
+            .collect();
+
+        let proofs = engine
+            .record_consent_batch(attestations.clone())
+            .await
+            .unwrap();
+        assert_eq!(proofs.len(), 3);
+        assert!(proofs.iter().all(|p| p.batch_root == proofs[0].batch_root));
+        for (proof, attestation) in proofs.iter().zip(&attestations) {
+            assert!(proof.verify(attestation));
+        }
+        assert!(!proofs[0].verify(&attestations[1]));
+
+        let mut tampered = attestations[2].clone();
+        tampered.age = 30;
+        assert!(!proofs[2].verify(&tampered));
+
+        let record = engine.fetch_record("batch-b").await.unwrap();
+        assert_eq!(record.tx_hash, proofs[1].tx_hash);
+        assert_eq!(engine.find_by_tx(&proofs[0].tx_hash).await.len(), 3);
+
+        // Records share the batch transaction but not their proofs
+        let a_proof = engine.generate_proof("batch-a").await.unwrap();
+        let b_proof = engine.generate_proof("batch-b").await.unwrap();
+        assert_ne!(a_proof, b_proof);
+        assert!(engine.verify_consent("batch-b", &b_proof).await.unwrap());
+        assert!(!engine.verify_consent("batch-b", &a_proof).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_parent_scope_satisfies_implied_scope() {
+        let engine = ConsentEngine::mock().with_scope_hierarchy(
+            ScopeHierarchy::new()
+                .with_implication("admin", "write")
+                .with_implication("write", "read"),
+        );
+        let record = engine.request_consent("admin-user").await.unwrap();
+        let proof = engine.expected_proof(&record, None);
+
+        engine
+            .grant_scope("admin-user", "admin", None)
+            .await
+            .unwrap();
+
+        assert!(engine
+            .verify_consent_scoped("admin-user", &proof, "read")
+            .await
+            .unwrap());
+        assert!(!engine
+            .verify_consent_scoped("admin-user", &proof, "billing")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_scope_expiry_is_per_scope() {
+        let engine = ConsentEngine::mock();
+        let record = engine.request_consent("scoped-user").await.unwrap();
+        let proof = engine.expected_proof(&record, None);
+
+        engine
+            .grant_scope(
+                "scoped-user",
+                "marketing",
+                Some(Utc::now() - chrono::Duration::minutes(1)),
+            )
+            .await
+            .unwrap();
+        engine
+            .grant_scope(
+                "scoped-user",
+                "core",
+                Some(Utc::now() + chrono::Duration::days(30)),
+            )
+            .await
+            .unwrap();
+
+        assert!(!engine
+            .verify_consent_scoped("scoped-user", &proof, "marketing")
+            .await
+            .unwrap());
+        assert!(engine
+            .verify_consent_scoped("scoped-user", &proof, "core")
+            .await
+            .unwrap());
+        assert!(!engine
+            .verify_consent_scoped("scoped-user", &proof, "analytics")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_consent_token_round_trip() {
+        let engine = ConsentEngine::mock();
+        engine.request_consent("token-user").await.unwrap();
+        engine
+            .grant_scope("token-user", "read", None)
+            .await
+            .unwrap();
+
+        let token = engine
+            .issue_token("token-user", Duration::minutes(5))
+            .await
+            .unwrap();
+        let claims = token.verify(&engine.platform_public_key()).unwrap();
+        assert_eq!(claims.sub, "token-user");
+        assert_eq!(claims.scopes, vec!["read".to_string()]);
+
+        // Expired
+        assert!(token
+            .verify_at(
+                &engine.platform_public_key(),
+                Utc::now() + Duration::minutes(10)
+            )
+            .is_err());
+
+        // Tampered payload
+        let (payload, signature) = token.as_str().split_once('.').unwrap();
+        let tampered = ConsentToken::from_compact(format!("{}A.{}", payload, signature));
+        assert!(engine.verify_token(&tampered).is_err());
+
+        // Signed by another platform
+        let other = ConsentEngine::mock();
+        assert!(other.verify_token(&token).is_err());
+    }
+
+    struct GrantingHandler {
+        engine: ConsentEngine,
+    }
+
+    #[async_trait]
+    impl ScopeEscalationHandler for GrantingHandler {
+        async fn escalate(&self, user_id: &str, scope: &str) -> Option<String> {
+            self.engine.grant_scope(user_id, scope, None).await.ok()?;
+            let record = self.engine.fetch_record(user_id).await.ok()?;
+            Some(self.engine.expected_proof(&record, None))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scope_escalation_grants_missing_scope() {
+        let base = ConsentEngine::mock();
+        let engine = base
+            .clone()
+            .with_escalation_handler(Arc::new(GrantingHandler {
+                engine: base.clone(),
+            }));
+        let record = engine.request_consent("escalating-user").await.unwrap();
+        let proof = engine.expected_proof(&record, None);
+
+        // Without a handler the missing scope is denied
+        assert!(!base
+            .verify_consent_scoped("escalating-user", &proof, "write")
+            .await
+            .unwrap());
+
+        let outcome = engine
+            .verify_scope_or_escalate("escalating-user", &proof, "write")
+            .await
+            .unwrap();
+        assert!(outcome.granted);
+        assert!(outcome.updated_proof.is_some());
+
+        // The scope is now granted, so the call proceeds without escalating
+        assert!(base
+            .verify_consent_scoped("escalating-user", &proof, "write")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_linked_account_inherits_consent() {
+        let engine = ConsentEngine::mock();
+        let record = engine.request_consent("primary-user").await.unwrap();
+        let proof = engine.expected_proof(&record, None);
+
+        assert!(!engine
+            .verify_consent("secondary-user", &proof)
+            .await
+            .unwrap());
+
+        engine
+            .link_accounts("primary-user", "secondary-user")
+            .await
+            .unwrap();
+        assert!(engine
+            .verify_consent("secondary-user", &proof)
+            .await
+            .unwrap());
+
+        engine.unlink_account("secondary-user").await.unwrap();
+        assert!(!engine
+            .verify_consent("secondary-user", &proof)
+            .await
+            .unwrap());
+        assert!(engine.verify_consent("primary-user", &proof).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_emergency_override_is_audited() {
+        let engine = ConsentEngine::mock();
+        assert!(engine
+            .emergency_override("op-1", "patient", "  ")
+            .await
+            .is_err());
+
+        let token = engine
+            .emergency_override("op-1", "patient", "cardiac event")
+            .await
+            .unwrap();
+        assert!(engine.verify_override("patient", &token.token).await);
+        assert!(!engine.verify_override("someone-else", &token.token).await);
+
+        let entry = engine
+            .audit_log()
+            .entries_for("patient")
+            .await
+            .into_iter()
+            .find(|e| e.action == AuditAction::EmergencyOverride)
+            .unwrap();
+        assert_eq!(entry.actor.as_deref(), Some("op-1"));
+        assert_eq!(entry.details, "cardiac event");
+    }
+
+    #[tokio::test]
+    async fn test_preview_then_commit_persists_terms() {
+        let engine = ConsentEngine::mock();
+        let preview = engine.preview_consent("preview-user").await.unwrap();
+        assert_eq!(preview.min_age, 21);
+        assert!(preview.age >= preview.min_age);
+        assert!(engine.blockchain_client.list_records().await.is_empty());
+
+        let record = engine.commit_consent(preview.preview_id).await.unwrap();
+        assert_eq!(record.user_id, "preview-user");
+        assert_eq!(record.age_proof, format!("age:{}", preview.age));
+        assert_eq!(record.discipline_proof, preview.discipline_proof);
+        assert_eq!(record.granted_at, preview.attested_at);
+
+        // A preview can only be committed once
+        assert!(engine.commit_consent(preview.preview_id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_inactivity_lapses_consent_until_touched() {
+        let engine = ConsentEngine::mock().with_inactivity_timeout(Duration::hours(1));
+        let mut record = ConsentRecord::new(
+            "idle-user",
+            "tx-idle".to_string(),
+            "age:25".to_string(),
+            "discipline:verified".to_string(),
+        );
+        record.granted_at = Utc::now() - Duration::hours(2);
+        let proof = engine.expected_proof(&record, None);
+        engine.blockchain_client.store_record(record).await;
+
+        assert!(!engine.verify_consent("idle-user", &proof).await.unwrap());
+
+        engine.touch_activity("idle-user").await;
+        assert!(engine.verify_consent("idle-user", &proof).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_terms_advance_requires_reconsent() {
+        let engine = ConsentEngine::mock();
+        let record = engine.request_consent("terms-user").await.unwrap();
+        let proof = engine.expected_proof(&record, None);
+        assert!(engine.verify_consent("terms-user", &proof).await.unwrap());
+
+        engine.advance_terms_version(2).unwrap();
+        let verification = engine
+            .verify_consent_detailed("terms-user", &proof)
+            .await
+            .unwrap();
+        assert!(!verification.valid);
+        assert!(verification.consent_stale);
+        assert_eq!(verification.terms_version, 1);
+        assert_eq!(verification.active_terms_version, 2);
+        assert!(engine.advance_terms_version(2).is_err());
+
+        let record = engine.request_consent("terms-user").await.unwrap();
+        assert_eq!(record.terms_version, 2);
+        let proof = engine.expected_proof(&record, None);
+        let verification = engine
+            .verify_consent_detailed("terms-user", &proof)
+            .await
+            .unwrap();
+        assert!(verification.valid);
+        assert!(!verification.consent_stale);
+    }
+
+    struct FixedScorer(HashMap<String, f64>);
+
+    #[async_trait]
+    impl DisciplineScorer for FixedScorer {
+        async fn discipline_score(&self, user_id: &str) -> anyhow::Result<f64> {
+            self.0
+                .get(user_id)
+                .copied()
+                .ok_or_else(|| anyhow::anyhow!("no score for {}", user_id))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_discipline_score_threshold() {
+        let scores = [("above", 0.701), ("below", 0.699)]
+            .into_iter()
+            .map(|(user, score)| (user.to_string(), score))
+            .collect();
+        let engine = ConsentEngine::mock()
+            .with_discipline_scorer(Arc::new(FixedScorer(scores)))
+            .with_min_discipline_score(0.7);
+
+        assert!(engine.request_consent("above").await.is_ok());
+
+        let err = engine.request_consent("below").await.unwrap_err();
+        assert!(matches!(err, ConsentError::DisciplineIneligible(_)));
+        assert!(err.to_string().contains("0.699 below threshold 0.700"));
+    }
+
+    #[tokio::test]
+    async fn test_revocation_receipt_verifies_and_detects_tampering() {
+        let engine = ConsentEngine::mock();
+        let record = engine.request_consent("leaving-user").await.unwrap();
+        assert!(engine
+            .generate_revocation_receipt("leaving-user")
+            .await
+            .is_err());
+
+        engine.revoke_consent("leaving-user").await.unwrap();
+        let receipt = engine
+            .generate_revocation_receipt("leaving-user")
+            .await
+            .unwrap();
+        assert_eq!(receipt.consent_tx_hash, record.tx_hash);
+        assert!(engine.verify_revocation_receipt(&receipt).is_ok());
+        // Third parties only need the public key
+        assert!(receipt.verify(&engine.platform_public_key()).is_ok());
+
+        let mut tampered = receipt;
+        tampered.revoked_at += Duration::days(1);
+        assert!(engine.verify_revocation_receipt(&tampered).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_delegated_consent_expires_independently() {
+        let engine = ConsentEngine::mock();
+        let guardian = engine.request_consent("guardian").await.unwrap();
+        let guardian_proof = engine.expected_proof(&guardian, None);
+
+        let mut subject = engine
+            .delegate_consent(
+                "guardian",
+                "minor",
+                Some(Utc::now() + Duration::hours(1)),
+                None,
+            )
+            .await
+            .unwrap();
+        let proof = engine.expected_proof(&subject, None);
+        assert!(engine.verify_consent("minor", &proof).await.unwrap());
+
+        // The subject's own record expires; the guardian's is unaffected
+        subject.expires_at = Some(Utc::now() - Duration::minutes(1));
+        engine.blockchain_client.store_record(subject).await;
+        assert!(!engine.verify_consent("minor", &proof).await.unwrap());
+        assert!(engine
+            .verify_consent("guardian", &guardian_proof)
+            .await
+            .unwrap());
+
+        // An expired delegation link fails even with a live subject record
+        let ward = engine
+            .delegate_consent(
+                "guardian",
+                "ward",
+                None,
+                Some(Utc::now() - Duration::minutes(1)),
+            )
+            .await
+            .unwrap();
+        let proof = engine.expected_proof(&ward, None);
+        assert!(!engine.verify_consent("ward", &proof).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_subject_proof_rejected_for_guardian() {
+        let engine = ConsentEngine::mock();
+        let guardian = engine.request_consent("parent").await.unwrap();
+        engine.grant_scope("parent", "read", None).await.unwrap();
+        engine.grant_scope("parent", "write", None).await.unwrap();
+        let subject = engine
+            .delegate_consent_scoped("parent", "child", &["read"], None, None)
+            .await
+            .unwrap();
+
+        let subject_proof = engine.generate_proof("child").await.unwrap();
+        assert_ne!(subject_proof, engine.expected_proof(&guardian, None));
+        assert_eq!(subject_proof, engine.expected_proof(&subject, None));
+        assert!(engine
+            .verify_consent_scoped("child", &subject_proof, "read")
+            .await
+            .unwrap());
+        assert!(!engine
+            .verify_consent("parent", &subject_proof)
+            .await
+            .unwrap());
+        assert!(!engine
+            .verify_consent_scoped("parent", &subject_proof, "write")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_audience_bound_proof_rejected_elsewhere() {
+        let engine = ConsentEngine::mock();
+        let record = engine.request_consent("aud-user").await.unwrap();
+        let proof = engine
+            .issue_audience_proof("aud-user", "service-a")
+            .await
+            .unwrap();
+
+        assert!(engine
+            .verify_consent_for_audience("aud-user", &proof, "service-a")
+            .await
+            .unwrap());
+        assert!(!engine
+            .verify_consent_for_audience("aud-user", &proof, "service-b")
+            .await
+            .unwrap());
+
+        // Unbound proofs do not satisfy audience-bound verification
+        let unbound = engine.expected_proof(&record, None);
+        assert!(!engine
+            .verify_consent_for_audience("aud-user", &unbound, "service-a")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_delegation_graph_edges_and_cycle_guard() {
+        let engine = ConsentEngine::mock();
+        engine.request_consent("grandparent").await.unwrap();
+        engine
+            .delegate_consent("grandparent", "parent", None, None)
+            .await
+            .unwrap();
+        engine
+            .delegate_consent(
+                "parent",
+                "child",
+                None,
+                Some(Utc::now() - Duration::minutes(1)),
+            )
+            .await
+            .unwrap();
+
+        let graph = engine.delegation_graph().await.unwrap();
+        let edges: Vec<_> = graph
+            .edges
+            .iter()
+            .map(|e| (e.guardian_id.as_str(), e.subject_id.as_str(), e.status))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                ("grandparent", "parent", DelegationStatus::Active),
+                ("parent", "child", DelegationStatus::LinkExpired),
+            ]
+        );
+        assert!(graph.find_cycle().is_none());
+
+        let cycle = engine
+            .delegate_consent("child", "grandparent", None, None)
+            .await;
+        assert!(cycle.unwrap_err().to_string().contains("cycle"));
+    }
+
+    #[tokio::test]
+    async fn test_proof_generation_rate_limited_per_user() {
+        let engine = ConsentEngine::mock().with_proof_rate_limit(3, Duration::minutes(1));
+        engine.request_consent("busy-user").await.unwrap();
+
+        for _ in 0..3 {
+            let proof = engine.generate_proof("busy-user").await.unwrap();
+            assert!(engine.verify_consent("busy-user", &proof).await.unwrap());
+        }
+        let blocked = engine.generate_proof("busy-user").await;
+        assert!(matches!(blocked, Err(ConsentError::RateLimited(_))));
+        assert!(engine
+            .issue_audience_proof("busy-user", "service-a")
+            .await
+            .is_err());
+
+        // Limits are per user
+        assert!(engine.generate_proof("other-user").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_legal_hold_survives_prune() {
+        let engine = ConsentEngine::mock();
+        engine.request_consent("held-user").await.unwrap();
+        engine.request_consent("free-user").await.unwrap();
+        engine.set_legal_hold("held-user", true).await.unwrap();
+
+        let archived = engine
+            .prune_records(Utc::now() + chrono::Duration::seconds(1))
+            .await
+            .unwrap();
+
+        assert_eq!(archived, vec!["free-user".to_string()]);
+        let live = engine.blockchain_client.list_records().await;
+        assert_eq!(live.len(), 1);
+        assert!(live[0].legal_hold);
+        assert_eq!(engine.blockchain_client.archived_records().await.len(), 1);
+
+        let audit = engine.audit_log().entries_for("held-user").await;
+        assert!(audit
+            .iter()
+            .any(|e| e.action == AuditAction::LegalHoldPlaced));
+    }
+
+    #[derive(Default)]
+    struct RecordingSink {
+        events: std::sync::Mutex<Vec<AnalyticsEventKind>>,
+    }
+
+    impl AnalyticsSink for RecordingSink {
+        fn emit(&self, event: AnalyticsEvent) {
+            self.events.lock().unwrap().push(event.kind);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_emits_analytics_events() {
+        let sink = Arc::new(RecordingSink::default());
+        let engine = ConsentEngine::mock().with_analytics_sink(sink.clone());
+        let take = || std::mem::take(&mut *sink.events.lock().unwrap());
+
+        engine.request_consent("metrics-user").await.unwrap();
+        engine.request_consent("metrics-user").await.unwrap();
+        assert_eq!(
+            take(),
+            vec![AnalyticsEventKind::Grant, AnalyticsEventKind::Renew]
+        );
+
+        assert!(!engine
+            .verify_consent("metrics-user", "forged")
+            .await
+            .unwrap());
+        assert_eq!(take(), vec![AnalyticsEventKind::Deny]);
+
+        let mut record = engine.fetch_record("metrics-user").await.unwrap();
+        record.expires_at = Some(Utc::now() - Duration::minutes(1));
+        let proof = engine.expected_proof(&record, None);
+        engine.blockchain_client.store_record(record).await;
+        assert!(!engine.verify_consent("metrics-user", &proof).await.unwrap());
+        assert_eq!(take(), vec![AnalyticsEventKind::Expire]);
+
+        engine.revoke_consent("metrics-user").await.unwrap();
+        assert_eq!(take(), vec![AnalyticsEventKind::Revoke]);
+    }
+
+    #[tokio::test]
+    async fn test_revocation_invalidates_cached_introspection() {
+        let engine = ConsentEngine::mock().with_introspection_ttl(Duration::minutes(5));
+        engine.request_consent("introspected").await.unwrap();
+        let proof = engine.generate_proof("introspected").await.unwrap();
+
+        let first = engine
+            .introspect_proof("introspected", &proof)
+            .await
+            .unwrap();
+        assert!(first.active);
+        let cached = engine
+            .introspect_proof("introspected", &proof)
+            .await
+            .unwrap();
+        assert_eq!(cached.introspected_at, first.introspected_at);
+
+        engine.revoke_consent("introspected").await.unwrap();
+        let after = engine
+            .introspect_proof("introspected", &proof)
+            .await
+            .unwrap();
+        assert!(!after.active);
+        assert!(after.introspected_at > first.introspected_at);
+    }
+
+    #[tokio::test]
+    async fn test_expiry_notices_deduplicated_within_interval() {
+        let engine =
+            ConsentEngine::mock().with_expiry_notices(Duration::days(3), Duration::hours(24));
+        let mut record = engine.request_consent("expiring").await.unwrap();
+        let now = Utc::now();
+        record.expires_at = Some(now + Duration::days(2));
+        engine.blockchain_client.store_record(record).await;
+        engine.request_consent("long-lived").await.unwrap();
+
+        let first = engine.sweep_expiring(now).await;
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].user_id, "expiring");
+        for hours in [1, 6, 23] {
+            assert!(engine
+                .sweep_expiring(now + Duration::hours(hours))
+                .await
+                .is_empty());
+        }
+
+        let later = engine.sweep_expiring(now + Duration::hours(25)).await;
+        assert_eq!(later.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_compacted_chain_summary_proves_history_root() {
+        let engine = ConsentEngine::mock();
+        for _ in 0..5 {
+            engine.request_consent("chained").await.unwrap();
+            engine.revoke_consent("chained").await.unwrap();
+        }
+        let current = engine.request_consent("chained").await.unwrap();
+        let chain = engine.blockchain_client.chain("chained").await;
+        assert_eq!(chain.len(), 11);
+        let expected_root = compaction::chain_root(compaction::GENESIS_ROOT, &chain).unwrap();
+
+        let summary = engine.compact_chain("chained").await.unwrap();
+        assert_eq!(summary.history_root, expected_root);
+        assert_eq!(summary.entries, 11);
+        assert_eq!(summary.current_tx_hash, current.tx_hash);
+        engine.verify_chain_summary(&summary).unwrap();
+        assert!(engine.blockchain_client.chain("chained").await.is_empty());
+        let proof = engine.generate_proof("chained").await.unwrap();
+        assert!(engine.verify_consent("chained", &proof).await.unwrap());
+
+        let mut tampered = summary.clone();
+        tampered.entries = 1;
+        assert!(engine.verify_chain_summary(&tampered).is_err());
+
+        // Later compactions fold in the earlier root
+        engine.revoke_consent("chained").await.unwrap();
+        let tail = engine.blockchain_client.chain("chained").await;
+        let next = engine.compact_chain("chained").await.unwrap();
+        assert_eq!(
+            next.history_root,
+            compaction::chain_root(&summary.history_root, &tail).unwrap()
+        );
+        assert_eq!(next.entries, 12);
+    }
+
+    #[tokio::test]
+    async fn test_liveness_bound_proof_required_and_expires() {
+        let engine = ConsentEngine::mock().with_liveness_window(Duration::milliseconds(50));
+        engine.request_consent("sensitive").await.unwrap();
+        let stale_proof = engine.generate_proof("sensitive").await.unwrap();
+        assert!(!engine
+            .verify_consent_with_liveness("sensitive", &stale_proof)
+            .await
+            .unwrap());
+
+        let challenge = engine.issue_liveness_challenge("sensitive").await.unwrap();
+        assert!(engine
+            .complete_liveness_challenge(challenge.challenge_id, "wrong")
+            .await
+            .is_err());
+
+        let challenge = engine.issue_liveness_challenge("sensitive").await.unwrap();
+        let proof = engine
+            .complete_liveness_challenge(challenge.challenge_id, &challenge.expected_response())
+            .await
+            .unwrap();
+        assert!(engine
+            .verify_consent_with_liveness("sensitive", &proof)
+            .await
+            .unwrap());
+        assert!(!engine
+            .verify_consent_with_liveness("sensitive", &stale_proof)
+            .await
+            .unwrap());
+
+        tokio::time::sleep(std::time::Duration::from_millis(60)).await;
+        assert!(!engine
+            .verify_consent_with_liveness("sensitive", &proof)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_user_data_export_complete_and_signed() {
+        let engine = ConsentEngine::mock();
+        engine.request_consent("exporter").await.unwrap();
+        engine.revoke_consent("exporter").await.unwrap();
+        engine.request_consent("exporter").await.unwrap();
+        engine.revoke_consent("exporter").await.unwrap();
+        engine.request_consent("bystander").await.unwrap();
+
+        let export = engine.export_user_data("exporter").await.unwrap();
+        assert_eq!(export.history.len(), 4);
+        assert!(export.history.iter().all(|r| r.user_id == "exporter"));
+        assert_eq!(
+            export.current.as_ref().unwrap().status,
+            ConsentStatus::Revoked
+        );
+        assert!(export.revocation_receipt.is_some());
+        let actions: Vec<_> = export.audit_entries.iter().map(|e| e.action).collect();
+        assert_eq!(
+            actions,
+            vec![
+                AuditAction::Granted,
+                AuditAction::Revoked,
+                AuditAction::Granted,
+                AuditAction::Revoked
+            ]
+        );
+        export.verify(&engine.platform_public_key()).unwrap();
+
+        let mut trimmed = export.clone();
+        trimmed.audit_entries.pop();
+        assert!(trimmed.verify(&engine.platform_public_key()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_erasure_respects_legal_hold() {
+        let engine = ConsentEngine::mock().with_pseudonym_key([7; 32]);
+        engine.request_consent("forget-me").await.unwrap();
+        engine.revoke_consent("forget-me").await.unwrap();
+        engine.request_consent("held").await.unwrap();
+        engine.set_legal_hold("held", true).await.unwrap();
+
+        let receipt = engine.erase_user_data("forget-me", true).await.unwrap();
+        receipt.verify(&engine.platform_public_key()).unwrap();
+        assert_eq!(receipt.subject, erasure::pseudonym(&[7; 32], "forget-me"));
+        assert_ne!(receipt.subject, erasure::pseudonym(&[8; 32], "forget-me"));
+        assert_ne!(
+            receipt.subject,
+            cybulous_crypto::hash_data("erased:forget-me")
+        );
+        assert_eq!(receipt.erased_records, 2);
+        assert_eq!(receipt.anonymized_audit_entries, 2);
+        assert!(!receipt.legal_hold_overridden);
+        assert!(!engine.blockchain_client.has_record("forget-me").await);
+        assert!(engine.audit_log().entries_for("forget-me").await.is_empty());
+        assert_eq!(
+            engine.audit_log().entries_for(&receipt.subject).await.len(),
+            3
+        );
+
+        let refused = engine.erase_user_data("held", true).await;
+        assert!(matches!(refused, Err(ConsentError::LegalHold(_))));
+        assert!(engine.blockchain_client.has_record("held").await);
+        assert_eq!(engine.audit_log().entries_for("held").await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_erasure_clears_per_user_state() {
+        let engine = ConsentEngine::mock()
+            .with_attestation_ttl(Duration::days(30))
+            .with_introspection_ttl(Duration::hours(1))
+            .with_proof_rate_limit(1, Duration::hours(1));
+        engine.request_consent("forget-me").await.unwrap();
+        let proof = engine.generate_proof("forget-me").await.unwrap();
+        engine.introspect_proof("forget-me", &proof).await.unwrap();
+        engine.preview_consent("forget-me").await.unwrap();
+        engine
+            .delegate_consent("forget-me", "ward", None, None)
+            .await
+            .unwrap();
+        let token = engine
+            .emergency_override("operator", "forget-me", "patient unresponsive")
+            .await
+            .unwrap();
+        assert!(engine.expiry_notices.try_notify("forget-me", Utc::now()));
+
+        let receipt = engine.erase_user_data("forget-me", false).await.unwrap();
+
+        let now = Utc::now();
+        let attestations = engine.attestation_cache.as_ref().unwrap();
+        assert_eq!(attestations.fresh("forget-me", now).await, None);
+        let introspections = engine.introspection_cache.as_ref().unwrap();
+        assert!(introspections.get("forget-me", &proof, now).await.is_none());
+        assert!(engine.previews.read().await.is_empty());
+        let limiter = engine.proof_limiter.as_ref().unwrap();
+        assert!(limiter.try_acquire("forget-me", now));
+
+        // Delegations made as a guardian keep pointing at the pseudonym
+        let delegation = engine.delegations.read().await["ward"].clone();
+        assert_eq!(delegation.guardian_id, receipt.subject);
+        let ward = engine.fetch_record("ward").await.unwrap();
+        assert_eq!(ward.age_proof, format!("delegated:{}", receipt.subject));
+        assert!(!engine.verify_override("forget-me", &token.token).await);
+        assert!(engine.overrides.read().await.is_empty());
+        assert!(engine.expiry_notices.try_notify("forget-me", now));
+    }
+
+    #[tokio::test]
+    async fn test_age_provider_outage_uses_last_known_good_within_window() {
+        let engine = ConsentEngine::mock().with_age_fallback_window(Duration::hours(1));
+        engine.request_consent("renewing").await.unwrap();
+        let outage = || Err(anyhow::anyhow!("provider down"));
+
+        let now = Utc::now();
+        let age = engine
+            .age_or_last_known_good("renewing", outage(), now)
+            .await
+            .unwrap();
+        assert_eq!(age, 25);
+
+        let past_window = engine
+            .age_or_last_known_good("renewing", outage(), now + Duration::hours(2))
+            .await;
+        assert!(matches!(past_window, Err(ConsentError::ProviderError(_))));
+
+        let unknown = engine
+            .age_or_last_known_good("never-seen", outage(), now)
+            .await;
+        assert!(unknown.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_obligations_recorded_on_consent() {
+        let engine = ConsentEngine::mock();
+        let record = engine.request_consent("conditional").await.unwrap();
+        let delete = Obligation::DeleteAfter {
+            after: std::time::Duration::from_secs(30 * 24 * 60 * 60),
+        };
+        engine
+            .add_obligation("conditional", delete.clone())
+            .await
+            .unwrap();
+        engine
+            .add_obligation("conditional", delete.clone())
+            .await
+            .unwrap();
+
+        let obligations = engine.obligations_for("conditional").await.unwrap();
+        assert_eq!(obligations, vec![delete.clone()]);
+        assert_eq!(
+            delete.deletion_due(record.granted_at),
+            Some(record.granted_at + Duration::days(30))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_by_status_scope_and_expiry_window() {
+        let engine = ConsentEngine::mock();
+        let now = Utc::now();
+        for (user, scope, expires_in_days) in [
+            ("ana", "analytics", Some(3)),
+            ("ben", "analytics", Some(90)),
+            ("cai", "marketing", Some(2)),
+            ("dee", "analytics", Some(5)),
+            ("eli", "analytics", None),
+        ] {
+            engine.request_consent(user).await.unwrap();
+            engine.grant_scope(user, scope, None).await.unwrap();
+            let mut record = engine
+                .blockchain_client
+                .get_consent_record(user)
+                .await
+                .unwrap();
+            record.expires_at = expires_in_days.map(|days| now + Duration::days(days));
+            engine.blockchain_client.store_record(record).await;
+        }
+        engine
+            .set_jurisdiction("ana", Some("EU".to_string()))
+            .await
+            .unwrap();
+        engine.revoke_consent("dee").await.unwrap();
+
+        let expiring_soon = || {
+            ConsentQuery::new()
+                .with_status(ConsentStatus::Active)
+                .with_scope("analytics")
+                .expiring_within(now..now + Duration::days(7))
+        };
+        let users = |records: Vec<ConsentRecord>| {
+            records.into_iter().map(|r| r.user_id).collect::<Vec<_>>()
+        };
+        assert_eq!(users(engine.search(expiring_soon()).await), ["ana"]);
+        assert_eq!(
+            users(
+                engine
+                    .search(ConsentQuery::new().with_jurisdiction("EU"))
+                    .await
+            ),
+            ["ana"]
+        );
+
+        let analytics = ConsentQuery::new().with_scope("analytics");
+        assert_eq!(
+            users(engine.search(analytics.clone()).await),
+            ["ana", "ben", "dee", "eli"]
+        );
+        assert_eq!(
+            users(engine.search(analytics.page(1, 2)).await),
+            ["ben", "dee"]
+        );
+        assert_eq!(
+            users(
+                engine
+                    .search(ConsentQuery::new().with_status(ConsentStatus::Revoked))
+                    .await
+            ),
+            ["dee"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_use_limited_consent_denied_once_exhausted() {
+        let engine = ConsentEngine::mock();
+        engine.request_consent("limited").await.unwrap();
+        engine.limit_uses("limited", Some(3)).await.unwrap();
+        let proof = engine.generate_proof("limited").await.unwrap();
+
+        for remaining in [2, 1, 0] {
+            assert!(engine.verify_consent("limited", &proof).await.unwrap());
+            assert_eq!(
+                engine.remaining_uses("limited").await.unwrap(),
+                Some(remaining)
+            );
+        }
+        assert!(!engine.verify_consent("limited", &proof).await.unwrap());
+        assert_eq!(
+            engine
+                .audit_log()
+                .entries_for("limited")
+                .await
+                .iter()
+                .filter(|e| e.action == AuditAction::UseConsumed)
+                .count(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_verifications_do_not_double_spend_uses() {
+        let engine = Arc::new(ConsentEngine::mock());
+        engine.request_consent("contended").await.unwrap();
+        engine.limit_uses("contended", Some(3)).await.unwrap();
+        let proof = engine.generate_proof("contended").await.unwrap();
+
+        let attempts: Vec<_> = (0..10)
+            .map(|_| {
+                let engine = engine.clone();
+                let proof = proof.clone();
+                tokio::spawn(
+                    async move { engine.verify_consent("contended", &proof).await.unwrap() },
+                )
+            })
+            .collect();
+        let mut granted = 0;
+        for attempt in attempts {
+            if attempt.await.unwrap() {
+                granted += 1;
+            }
+        }
+        assert_eq!(granted, 3);
+        assert_eq!(engine.remaining_uses("contended").await.unwrap(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_revocation_waits_for_in_progress_record_write() {
+        let engine = Arc::new(ConsentEngine::mock());
+        engine.request_consent("racing").await.unwrap();
+        engine.limit_uses("racing", Some(3)).await.unwrap();
+        let proof = engine.generate_proof("racing").await.unwrap();
+
+        // A use being spent holds the record lock between its read and write
+        let spending = engine.record_lock.lock().await;
+        let revoke = tokio::spawn({
+            let engine = engine.clone();
+            async move { engine.revoke_consent("racing").await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(!revoke.is_finished());
+        drop(spending);
+        revoke.await.unwrap().unwrap();
+
+        assert!(!engine.verify_consent("racing", &proof).await.unwrap());
+        assert_eq!(engine.remaining_uses("racing").await.unwrap(), Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_geofenced_consent_rejected_out_of_region() {
+        let engine = ConsentEngine::mock();
+        engine.request_consent("roaming").await.unwrap();
+        engine
+            .restrict_regions("roaming", Some(vec!["EU".to_string()]))
+            .await
+            .unwrap();
+        let proof = engine.generate_proof("roaming").await.unwrap();
+
+        assert!(engine
+            .verify_consent_in_region("roaming", &proof, Some("EU"))
+            .await
+            .unwrap());
+        assert!(!engine
+            .verify_consent_in_region("roaming", &proof, Some("US"))
+            .await
+            .unwrap());
+        assert!(!engine.verify_consent("roaming", &proof).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_record_matches_published_commitment() {
+        let engine = ConsentEngine::mock();
+        engine.request_consent("committed").await.unwrap();
+        engine
+            .grant_scope("committed", "analytics", None)
+            .await
+            .unwrap();
+        let commitment = engine.publish_commitment("committed").await.unwrap();
+        let record = engine
+            .blockchain_client
+            .get_consent_record("committed")
+            .await
+            .unwrap();
+        assert!(engine
+            .verify_against_commitment(&record, &commitment)
+            .await
+            .unwrap());
+
+        let mut tampered = record.clone();
+        tampered.scopes.push("marketing".to_string());
+        assert!(!engine
+            .verify_against_commitment(&tampered, &commitment)
+            .await
+            .unwrap());
+
+        let mut forged = commitment.clone();
+        forged.digest = cybulous_crypto::hash_data("forged");
+        assert!(!engine
+            .verify_against_commitment(&record, &forged)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_scoped_delegation_limits_subject_to_delegated_scopes() {
+        let engine = ConsentEngine::mock();
+        engine.request_consent("guardian").await.unwrap();
+        engine.grant_scope("guardian", "read", None).await.unwrap();
+        engine.grant_scope("guardian", "write", None).await.unwrap();
+
+        let overreach = engine
+            .delegate_consent_scoped("guardian", "ward", &["admin"], None, None)
+            .await;
+        assert!(matches!(
+            overreach,
+            Err(ConsentError::AttestationInvalid(_))
+        ));
+
+        engine
+            .delegate_consent_scoped("guardian", "ward", &["read"], None, None)
+            .await
+            .unwrap();
+        let guardian_proof = engine.generate_proof("guardian").await.unwrap();
+        let ward_proof = engine.generate_proof("ward").await.unwrap();
+
+        assert!(engine
+            .verify_consent_scoped("guardian", &guardian_proof, "write")
+            .await
+            .unwrap());
+        assert!(engine
+            .verify_consent_scoped("ward", &ward_proof, "read")
+            .await
+            .unwrap());
+        assert!(!engine
+            .verify_consent_scoped("ward", &ward_proof, "write")
+            .await
+            .unwrap());
+    }
+
+    struct MockClock(std::sync::Mutex<DateTime<Utc>>);
+
+    impl ReminderClock for MockClock {
+        fn now(&self) -> DateTime<Utc> {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    impl MockClock {
+        fn set(&self, now: DateTime<Utc>) {
+            *self.0.lock().unwrap() = now;
+        }
+    }
+
+    #[derive(Default)]
+    struct QueueNotifier(std::sync::Mutex<Vec<RenewalReminder>>);
+
+    #[async_trait]
+    impl RenewalNotifier for QueueNotifier {
+        async fn enqueue(&self, reminder: RenewalReminder) {
+            self.0.lock().unwrap().push(reminder);
+        }
+    }
+
+    impl QueueNotifier {
+        fn users(&self) -> Vec<String> {
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|r| r.user_id.clone())
+                .collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_renewal_reminders_enqueued_once_per_consent() {
+        let engine = ConsentEngine::mock();
+        let start = Utc::now();
+        for (user, days) in [("soon", 2), ("later", 5)] {
+            let mut record = engine.request_consent(user).await.unwrap();
+            record.expires_at = Some(start + Duration::days(days));
+            engine.blockchain_client.store_record(record).await;
+        }
+        engine.request_consent("no-expiry").await.unwrap();
+
+        let clock = Arc::new(MockClock(std::sync::Mutex::new(start)));
+        let notifier = Arc::new(QueueNotifier::default());
+        let scheduler = engine.start_renewal_reminders(
+            RenewalSchedule {
+                cadence: std::time::Duration::from_millis(5),
+                lead_time: Duration::days(3),
+            },
+            notifier.clone(),
+            clock.clone(),
+        );
+        let settle = || tokio::time::sleep(std::time::Duration::from_millis(40));
+
+        settle().await;
+        assert_eq!(notifier.users(), vec!["soon"]);
+
+        // Walk the clock through the expiry window across many scans
+        for hours in [12, 60, 90, 110] {
+            clock.set(start + Duration::hours(hours));
+            settle().await;
+        }
+        assert_eq!(notifier.users(), vec!["soon", "later"]);
+        scheduler.stop().await;
+
+        // A renewed consent is due its own reminder, but only while running
+        let mut renewed = engine.request_consent("soon").await.unwrap();
+        renewed.expires_at = Some(start + Duration::hours(130));
+        engine.blockchain_client.store_record(renewed).await;
+        settle().await;
+        assert_eq!(notifier.users().len(), 2);
+
+        let reminded = engine
+            .enqueue_renewal_reminders(Duration::days(3), clock.now(), notifier.as_ref())
+            .await;
+        assert_eq!(reminded, 1);
+        assert_eq!(notifier.users(), vec!["soon", "later", "soon"]);
+    }
 }
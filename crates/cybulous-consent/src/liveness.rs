@@ -0,0 +1,50 @@
+//! Liveness challenges for high-assurance actions
+//!
+//! A consent proof can be replayed for as long as the consent is active.
+//! For sensitive operations the user first answers a short-lived challenge;
+//! the engine then issues a proof bound to that challenge's nonce, which is
+//! accepted only while the liveness check is recent.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// How long a challenge can be answered
+pub const LIVENESS_CHALLENGE_TTL_SECS: i64 = 120;
+
+/// Default time a completed liveness check stays fresh
+pub const DEFAULT_LIVENESS_WINDOW_SECS: i64 = 300;
+
+/// Challenge the user must answer to prove liveness
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LivenessChallenge {
+    /// Challenge identifier
+    pub challenge_id: Uuid,
+    /// User being challenged
+    pub user_id: String,
+    /// Random nonce the response must cover
+    pub nonce: String,
+    /// Issuance time
+    pub issued_at: DateTime<Utc>,
+    /// Time after which the challenge can no longer be answered
+    pub expires_at: DateTime<Utc>,
+}
+
+impl LivenessChallenge {
+    /// Response the user's device is expected to return
+    pub fn expected_response(&self) -> String {
+        cybulous_crypto::hash_data(&format!("{}:{}", self.nonce, self.user_id))
+    }
+
+    /// Audience a liveness-bound proof is issued for
+    pub(crate) fn audience(nonce: &str) -> String {
+        format!("liveness:{}", nonce)
+    }
+}
+
+/// A completed liveness check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LivenessCheck {
+    pub(crate) nonce: String,
+    pub(crate) verified_at: DateTime<Utc>,
+}
@@ -0,0 +1,46 @@
+//! Proof format negotiation
+//!
+//! At session start a client offers the proof formats it can generate, in
+//! preference order, and the engine answers with the first one it also
+//! verifies. Agreeing up front avoids proofs that fail verification only
+//! because client and engine disagree on the format.
+
+use serde::{Deserialize, Serialize};
+
+/// Digest over the consent transaction and minimum age
+pub const TX_DIGEST_SCHEME: &str = "tx-digest";
+
+/// Digest over the consent transaction, minimum age, and audience
+pub const AUDIENCE_TX_DIGEST_SCHEME: &str = "tx-digest-audience";
+
+/// A proof scheme at a specific version
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ProofFormat {
+    /// Scheme name
+    pub scheme: String,
+    /// Scheme version
+    pub version: u32,
+}
+
+impl ProofFormat {
+    /// Format for `scheme` at `version`
+    pub fn new(scheme: impl Into<String>, version: u32) -> Self {
+        Self {
+            scheme: scheme.into(),
+            version,
+        }
+    }
+}
+
+/// Formats this engine verifies
+pub fn supported_formats() -> Vec<ProofFormat> {
+    vec![
+        ProofFormat::new(AUDIENCE_TX_DIGEST_SCHEME, 1),
+        ProofFormat::new(TX_DIGEST_SCHEME, 1),
+    ]
+}
+
+/// The client's most preferred offered format that is also supported
+pub fn negotiate(offered: &[ProofFormat], supported: &[ProofFormat]) -> Option<ProofFormat> {
+    offered.iter().find(|f| supported.contains(f)).cloned()
+}
@@ -0,0 +1,152 @@
+//! Persistent ledger of consumed proof nonces
+//!
+//! Single-use proofs carry a nonce that must never be accepted twice. The
+//! `NonceLedger` remembers consumed nonces until they expire and writes
+//! each one through to a pluggable `NonceStore` before accepting it, so a
+//! restart that reloads the ledger does not reopen the replay window.
+
+use crate::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::RwLock;
+
+/// A nonce that has been used, remembered until it expires
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsumedNonce {
+    /// The nonce value
+    pub nonce: String,
+    /// Time after which the nonce can no longer be presented anyway
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Durable storage backing a nonce ledger
+#[async_trait]
+pub trait NonceStore: Send + Sync {
+    /// Every stored nonce, expired or not
+    async fn load(&self) -> Result<Vec<ConsumedNonce>>;
+
+    /// Durably record a consumed nonce
+    async fn append(&self, nonce: &ConsumedNonce) -> Result<()>;
+
+    /// Drop nonces that expired before `now`
+    async fn prune(&self, now: DateTime<Utc>) -> Result<()>;
+}
+
+/// In-process nonce store, surviving ledger reloads but not the process
+#[derive(Debug, Default)]
+pub struct MemoryNonceStore {
+    nonces: Mutex<Vec<ConsumedNonce>>,
+}
+
+impl MemoryNonceStore {
+    fn nonces(&self) -> std::sync::MutexGuard<'_, Vec<ConsumedNonce>> {
+        self.nonces.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait]
+impl NonceStore for MemoryNonceStore {
+    async fn load(&self) -> Result<Vec<ConsumedNonce>> {
+        Ok(self.nonces().clone())
+    }
+
+    async fn append(&self, nonce: &ConsumedNonce) -> Result<()> {
+        self.nonces().push(nonce.clone());
+        Ok(())
+    }
+
+    async fn prune(&self, now: DateTime<Utc>) -> Result<()> {
+        self.nonces().retain(|n| n.expires_at >= now);
+        Ok(())
+    }
+}
+
+/// Replay-prevention set of consumed nonces
+pub struct NonceLedger {
+    store: Arc<dyn NonceStore>,
+    seen: RwLock<HashMap<String, DateTime<Utc>>>,
+}
+
+impl std::fmt::Debug for NonceLedger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NonceLedger").finish_non_exhaustive()
+    }
+}
+
+impl NonceLedger {
+    /// Load the unexpired nonces recorded in `store`
+    pub async fn load(store: Arc<dyn NonceStore>, now: DateTime<Utc>) -> Result<Self> {
+        let seen = store
+            .load()
+            .await?
+            .into_iter()
+            .filter(|n| n.expires_at >= now)
+            .map(|n| (n.nonce, n.expires_at))
+            .collect();
+        Ok(Self {
+            store,
+            seen: RwLock::new(seen),
+        })
+    }
+
+    /// Consume `nonce`, returning `false` if it was already consumed
+    ///
+    /// The nonce is persisted before it is accepted; a store failure
+    /// rejects it rather than risk a replay after restart.
+    pub async fn consume(
+        &self,
+        nonce: &str,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<bool> {
+        let mut seen = self.seen.write().await;
+        if seen.get(nonce).is_some_and(|expiry| *expiry >= now) {
+            return Ok(false);
+        }
+        self.store
+            .append(&ConsumedNonce {
+                nonce: nonce.to_string(),
+                expires_at,
+            })
+            .await?;
+        seen.insert(nonce.to_string(), expires_at);
+        Ok(true)
+    }
+
+    /// Forget nonces that have expired, in memory and in the store
+    pub async fn prune(&self, now: DateTime<Utc>) -> Result<()> {
+        self.seen.write().await.retain(|_, expiry| *expiry >= now);
+        self.store.prune(now).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn test_consumed_nonce_rejected_after_reload() {
+        let store = Arc::new(MemoryNonceStore::default());
+        let now = Utc::now();
+        let expires_at = now + Duration::minutes(5);
+
+        let ledger = NonceLedger::load(store.clone(), now).await.unwrap();
+        assert!(ledger.consume("n-1", expires_at, now).await.unwrap());
+        assert!(!ledger.consume("n-1", expires_at, now).await.unwrap());
+        drop(ledger);
+
+        // Simulated restart: a fresh ledger over the same store
+        let ledger = NonceLedger::load(store.clone(), now).await.unwrap();
+        assert!(!ledger.consume("n-1", expires_at, now).await.unwrap());
+        assert!(ledger.consume("n-2", expires_at, now).await.unwrap());
+
+        // Once expired, nonces are pruned and no longer block
+        let later = expires_at + Duration::seconds(1);
+        ledger.prune(later).await.unwrap();
+        assert!(store.load().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,39 @@
+//! Obligations attached to consent
+//!
+//! Consent may be granted on conditions the platform must honor, such as
+//! deleting data after a period or notifying a party whenever the user's
+//! data is accessed. Obligations are stored on the consent record and
+//! queried by the systems responsible for enforcing them.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A condition attached to a user's consent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Obligation {
+    /// Delete data associated with the consent once `after` has elapsed
+    /// since it was granted
+    DeleteAfter {
+        /// Time from grant to deletion
+        after: std::time::Duration,
+    },
+    /// Notify `recipient` whenever a tool runs on the user's behalf
+    NotifyOnAccess {
+        /// Party to notify
+        recipient: String,
+    },
+}
+
+impl Obligation {
+    /// When a `DeleteAfter` obligation falls due for consent granted at
+    /// `granted_at`
+    pub fn deletion_due(&self, granted_at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::DeleteAfter { after } => chrono::Duration::from_std(*after)
+                .ok()
+                .and_then(|after| granted_at.checked_add_signed(after)),
+            Self::NotifyOnAccess { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! Consent previews shown before the blockchain write
+//!
+//! A preview runs the same eligibility checks as a real request and shows
+//! the attestation that would be recorded, so a UI can display exactly
+//! what the user is agreeing to. Committing a preview records those terms.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// How long a preview may be committed after it is issued
+pub const PREVIEW_VALIDITY_MINUTES: i64 = 10;
+
+/// Validated, unrecorded consent terms
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsentPreview {
+    /// Identifier used to commit this preview
+    pub preview_id: Uuid,
+    /// User the consent is for
+    pub user_id: String,
+    /// Verified age
+    pub age: u8,
+    /// Minimum age the policy requires
+    pub min_age: u8,
+    /// Discipline eligibility proof
+    pub discipline_proof: String,
+    /// Terms version being consented to
+    pub terms_version: u32,
+    /// Timestamp the attestation will carry
+    pub attested_at: DateTime<Utc>,
+    /// Deadline for committing this preview
+    pub valid_until: DateTime<Utc>,
+}
@@ -0,0 +1,117 @@
+//! Client-side caching of consent proofs
+//!
+//! Generating a proof can require a round trip to the engine. Clients wrap
+//! their generator in a `ConsentProofCache`, which reuses a proof for its
+//! scope until it nears expiry and then transparently regenerates it.
+
+use crate::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Default margin before expiry at which cached proofs are refreshed
+pub const DEFAULT_REFRESH_MARGIN_SECS: i64 = 60;
+
+/// A generated proof and when it stops being accepted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssuedProof {
+    /// Proof presented with calls
+    pub proof: String,
+    /// Expiration time
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Source of fresh consent proofs
+#[async_trait]
+pub trait ProofGenerator: Send + Sync {
+    /// Generate a proof for `scope`
+    async fn generate(&self, scope: &str) -> Result<IssuedProof>;
+}
+
+/// Per-scope cache of generated proofs
+#[derive(Clone)]
+pub struct ConsentProofCache {
+    generator: Arc<dyn ProofGenerator>,
+    refresh_margin: Duration,
+    proofs: Arc<RwLock<HashMap<String, IssuedProof>>>,
+}
+
+impl ConsentProofCache {
+    /// Cache proofs produced by `generator`
+    pub fn new(generator: Arc<dyn ProofGenerator>) -> Self {
+        Self {
+            generator,
+            refresh_margin: Duration::seconds(DEFAULT_REFRESH_MARGIN_SECS),
+            proofs: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Refresh proofs this long before they expire
+    pub fn with_refresh_margin(mut self, margin: Duration) -> Self {
+        self.refresh_margin = margin;
+        self
+    }
+
+    /// Proof for `scope`, regenerating it if missing or near expiry
+    pub async fn proof(&self, scope: &str) -> Result<String> {
+        let fresh_until = Utc::now() + self.refresh_margin;
+        if let Some(cached) = self.proofs.read().await.get(scope) {
+            if cached.expires_at > fresh_until {
+                return Ok(cached.proof.clone());
+            }
+        }
+
+        let issued = self.generator.generate(scope).await?;
+        let proof = issued.proof.clone();
+        self.proofs.write().await.insert(scope.to_string(), issued);
+        Ok(proof)
+    }
+
+    /// Drop the cached proof for `scope`, e.g. after it was rejected
+    pub async fn invalidate(&self, scope: &str) {
+        self.proofs.write().await.remove(scope);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingGenerator {
+        ttl: Duration,
+        generated: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ProofGenerator for CountingGenerator {
+        async fn generate(&self, scope: &str) -> Result<IssuedProof> {
+            let n = self.generated.fetch_add(1, Ordering::SeqCst);
+            Ok(IssuedProof {
+                proof: format!("{}-{}", scope, n),
+                expires_at: Utc::now() + self.ttl,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_proof_reused_until_near_expiry() {
+        let generator = Arc::new(CountingGenerator {
+            ttl: Duration::minutes(10),
+            generated: AtomicUsize::new(0),
+        });
+        let cache = ConsentProofCache::new(generator.clone());
+
+        assert_eq!(cache.proof("core").await.unwrap(), "core-0");
+        assert_eq!(cache.proof("core").await.unwrap(), "core-0");
+        assert_eq!(cache.proof("marketing").await.unwrap(), "marketing-1");
+        assert_eq!(generator.generated.load(Ordering::SeqCst), 2);
+
+        // A margin wider than the TTL treats every cached proof as near expiry
+        let cache = cache.with_refresh_margin(Duration::minutes(15));
+        assert_eq!(cache.proof("core").await.unwrap(), "core-2");
+        assert_eq!(cache.proof("core").await.unwrap(), "core-3");
+    }
+}
@@ -0,0 +1,52 @@
+//! Per-user rate limiting of proof generation
+//!
+//! Caps how many proofs each user can generate within a fixed window to
+//! limit abuse of the proof endpoints.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Fixed-window limiter keyed by user
+#[derive(Debug)]
+pub struct ProofRateLimiter {
+    max_proofs: u32,
+    window: Duration,
+    windows: Mutex<HashMap<String, (DateTime<Utc>, u32)>>,
+}
+
+impl ProofRateLimiter {
+    /// Allow at most `max_proofs` per user per `window`
+    pub fn new(max_proofs: u32, window: Duration) -> Self {
+        Self {
+            max_proofs,
+            window,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count one proof for `user_id` if within the limit at `now`
+    pub fn try_acquire(&self, user_id: &str, now: DateTime<Utc>) -> bool {
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        let (started, count) = windows.entry(user_id.to_string()).or_insert((now, 0));
+
+        if now - *started >= self.window {
+            *started = now;
+            *count = 0;
+        }
+        if *count < self.max_proofs {
+            *count += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Forget `user_id`'s current window
+    pub fn remove(&self, user_id: &str) {
+        self.windows
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(user_id);
+    }
+}
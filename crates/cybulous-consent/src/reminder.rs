@@ -0,0 +1,119 @@
+//! Scheduled consent renewal reminders
+//!
+//! A background scheduler wakes on a fixed cadence, scans for active
+//! consents expiring within the lead time and enqueues a renewal reminder
+//! through a `RenewalNotifier`. Unlike expiry notices, which repeat once
+//! per interval, each consent is reminded exactly once: the ledger is keyed
+//! by consent record, so only a renewed consent earns another.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::expiry::DEFAULT_EXPIRY_LEAD_HOURS;
+
+/// Default time between scheduler scans
+pub const DEFAULT_RENEWAL_CADENCE_SECS: u64 = 3600;
+
+/// Source of the current time for scheduled scans
+pub trait ReminderClock: Send + Sync {
+    /// Current time
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Wall-clock time
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl ReminderClock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Reminder that a user's consent is due for renewal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenewalReminder {
+    /// Consent record due for renewal
+    pub record_id: Uuid,
+    /// User to remind
+    pub user_id: String,
+    /// Transaction that recorded the expiring consent
+    pub consent_tx_hash: String,
+    /// When the consent expires
+    pub expires_at: DateTime<Utc>,
+    /// When the reminder was enqueued
+    pub enqueued_at: DateTime<Utc>,
+}
+
+/// Delivery queue for renewal reminders
+#[async_trait]
+pub trait RenewalNotifier: Send + Sync {
+    /// Enqueue `reminder` for delivery
+    async fn enqueue(&self, reminder: RenewalReminder);
+}
+
+/// How often to scan and how far ahead of expiry to remind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenewalSchedule {
+    /// Time between scans
+    pub cadence: std::time::Duration,
+    /// Remind once consent expires within this window
+    pub lead_time: Duration,
+}
+
+impl Default for RenewalSchedule {
+    fn default() -> Self {
+        Self {
+            cadence: std::time::Duration::from_secs(DEFAULT_RENEWAL_CADENCE_SECS),
+            lead_time: Duration::hours(DEFAULT_EXPIRY_LEAD_HOURS),
+        }
+    }
+}
+
+/// Consent records already reminded
+#[derive(Debug, Default)]
+pub(crate) struct RenewalLedger {
+    reminded: Mutex<HashSet<Uuid>>,
+}
+
+impl RenewalLedger {
+    /// Claim the reminder for consent record `record_id`
+    ///
+    /// Returns `false` if it was already claimed.
+    pub(crate) fn try_claim(&self, record_id: Uuid) -> bool {
+        self.reminded
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(record_id)
+    }
+}
+
+/// Handle to a running renewal reminder scheduler
+///
+/// Dropping the handle also stops the scheduler, after any scan in
+/// progress.
+#[derive(Debug)]
+pub struct RenewalScheduler {
+    stop: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+impl RenewalScheduler {
+    pub(crate) fn new(stop: oneshot::Sender<()>, task: JoinHandle<()>) -> Self {
+        Self { stop, task }
+    }
+
+    /// Stop scheduling and wait for any scan in progress to finish
+    pub async fn stop(self) {
+        let _ = self.stop.send(());
+        if let Err(e) = self.task.await {
+            tracing::warn!("Renewal reminder scheduler ended abnormally: {}", e);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Third-party verifiable revocation receipts
+//!
+//! When consent is revoked the engine can issue a `RevocationAttestation`:
+//! a platform-signed statement that the consent recorded in a given
+//! transaction was revoked at a given time. Anyone holding the platform's
+//! public key can check it without contacting the engine.
+//!
+//! Revocations are also published as `RevocationEvent`s so caches holding
+//! consent-derived state can invalidate it.
+
+use crate::signing;
+use crate::Result;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// Capacity of the engine's revocation event channel
+pub const REVOCATION_CHANNEL_CAPACITY: usize = 256;
+
+/// Published whenever a user's consent is revoked
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevocationEvent {
+    /// User whose consent was revoked
+    pub user_id: String,
+    /// When the consent was revoked
+    pub revoked_at: DateTime<Utc>,
+}
+
+/// Signed proof that a user's consent was revoked
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevocationAttestation {
+    /// User whose consent was revoked
+    pub user_id: String,
+    /// Transaction that recorded the revoked consent
+    pub consent_tx_hash: String,
+    /// When the consent was revoked
+    pub revoked_at: DateTime<Utc>,
+    /// When this receipt was issued
+    pub issued_at: DateTime<Utc>,
+    /// Base64url Ed25519 signature over the other fields
+    pub signature: String,
+}
+
+#[derive(Serialize)]
+struct SignedFields<'a> {
+    user_id: &'a str,
+    consent_tx_hash: &'a str,
+    revoked_at: DateTime<Utc>,
+    issued_at: DateTime<Utc>,
+}
+
+impl RevocationAttestation {
+    /// Issue a receipt signed with the platform key
+    pub fn sign(
+        user_id: &str,
+        consent_tx_hash: &str,
+        revoked_at: DateTime<Utc>,
+        key: &SigningKey,
+    ) -> Result<Self> {
+        let mut receipt = Self {
+            user_id: user_id.to_string(),
+            consent_tx_hash: consent_tx_hash.to_string(),
+            revoked_at,
+            issued_at: Utc::now(),
+            signature: String::new(),
+        };
+        receipt.signature = signing::sign(key, &receipt.signed_bytes()?);
+        Ok(receipt)
+    }
+
+    /// Check the receipt was signed by `public_key` and not altered
+    pub fn verify(&self, public_key: &VerifyingKey) -> Result<()> {
+        signing::verify(
+            public_key,
+            &self.signed_bytes()?,
+            &self.signature,
+            "revocation receipt",
+        )
+    }
+
+    fn signed_bytes(&self) -> Result<Vec<u8>> {
+        signing::signed_bytes(&SignedFields {
+            user_id: &self.user_id,
+            consent_tx_hash: &self.consent_tx_hash,
+            revoked_at: self.revoked_at,
+            issued_at: self.issued_at,
+        })
+    }
+}
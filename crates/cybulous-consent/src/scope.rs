@@ -0,0 +1,69 @@
+//! Scope inheritance hierarchies
+//!
+//! Scopes often nest: `admin` implies `write`, which implies `read`. A
+//! `ScopeHierarchy` records these implications so a grant of a parent
+//! scope satisfies checks for any scope it implies, directly or
+//! transitively.
+
+use std::collections::{HashMap, HashSet};
+
+/// Implications between scopes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScopeHierarchy {
+    /// Child scope to the parents that directly imply it
+    parents: HashMap<String, Vec<String>>,
+}
+
+impl ScopeHierarchy {
+    /// Hierarchy with no implications
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare that granting `parent` also grants `child`
+    pub fn with_implication(mut self, parent: impl Into<String>, child: impl Into<String>) -> Self {
+        self.parents
+            .entry(child.into())
+            .or_default()
+            .push(parent.into());
+        self
+    }
+
+    /// `scope` and every scope that implies it
+    pub fn satisfying_scopes(&self, scope: &str) -> HashSet<String> {
+        let mut satisfying = HashSet::new();
+        let mut pending = vec![scope.to_string()];
+        while let Some(scope) = pending.pop() {
+            if let Some(parents) = self.parents.get(&scope) {
+                pending.extend(parents.iter().filter(|p| !satisfying.contains(*p)).cloned());
+            }
+            satisfying.insert(scope);
+        }
+        satisfying
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_implications_are_transitive_and_cycle_safe() {
+        let hierarchy = ScopeHierarchy::new()
+            .with_implication("admin", "write")
+            .with_implication("write", "read")
+            .with_implication("read", "admin");
+        let mut read = hierarchy
+            .satisfying_scopes("read")
+            .into_iter()
+            .collect::<Vec<_>>();
+        read.sort();
+        assert_eq!(read, vec!["admin", "read", "write"]);
+
+        let flat = ScopeHierarchy::new().with_implication("admin", "write");
+        assert_eq!(
+            flat.satisfying_scopes("read"),
+            HashSet::from(["read".to_string()])
+        );
+    }
+}
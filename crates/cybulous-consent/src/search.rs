@@ -0,0 +1,164 @@
+//! Attribute search over consent records
+//!
+//! Operators look up records by status, scope, jurisdiction, and expiry
+//! window. Status, scope, and jurisdiction are indexed as records are
+//! stored; the expiry window is checked against the indexed candidates.
+//! Results are ordered by user ID so pages are stable.
+
+use crate::{ConsentRecord, ConsentStatus};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Range;
+
+/// Filters and page bounds for [`crate::ConsentEngine::search`]; unset
+/// filters match every record
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsentQuery {
+    /// Only records in this status
+    pub status: Option<ConsentStatus>,
+    /// Only records granting this scope
+    pub scope: Option<String>,
+    /// Only records under this jurisdiction
+    pub jurisdiction: Option<String>,
+    /// Only records expiring within this window
+    pub expires_within: Option<Range<DateTime<Utc>>>,
+    /// Matching records to skip
+    pub offset: usize,
+    /// Maximum records to return; unbounded if `None`
+    pub limit: Option<usize>,
+}
+
+impl ConsentQuery {
+    /// Query matching every record
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only records in `status`
+    pub fn with_status(mut self, status: ConsentStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Only records granting `scope`
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Only records under `jurisdiction`
+    pub fn with_jurisdiction(mut self, jurisdiction: impl Into<String>) -> Self {
+        self.jurisdiction = Some(jurisdiction.into());
+        self
+    }
+
+    /// Only records expiring within `window`
+    pub fn expiring_within(mut self, window: Range<DateTime<Utc>>) -> Self {
+        self.expires_within = Some(window);
+        self
+    }
+
+    /// Skip `offset` matches and return at most `limit`
+    pub fn page(mut self, offset: usize, limit: usize) -> Self {
+        self.offset = offset;
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Whether `record` passes every filter
+    pub fn matches(&self, record: &ConsentRecord) -> bool {
+        if self.status.is_some_and(|s| s != record.status) {
+            return false;
+        }
+        if self
+            .scope
+            .as_ref()
+            .is_some_and(|scope| !record.scopes.contains(scope))
+        {
+            return false;
+        }
+        if self.jurisdiction.is_some() && self.jurisdiction != record.jurisdiction {
+            return false;
+        }
+        match &self.expires_within {
+            Some(window) => record
+                .expires_at
+                .is_some_and(|expires_at| window.contains(&expires_at)),
+            None => true,
+        }
+    }
+}
+
+/// Users with live records, by indexed attribute
+#[derive(Debug, Default)]
+pub(crate) struct AttributeIndex {
+    by_status: HashMap<ConsentStatus, BTreeSet<String>>,
+    by_scope: HashMap<String, BTreeSet<String>>,
+    by_jurisdiction: HashMap<String, BTreeSet<String>>,
+}
+
+impl AttributeIndex {
+    /// Index `record`
+    pub(crate) fn insert(&mut self, record: &ConsentRecord) {
+        let user = &record.user_id;
+        self.by_status
+            .entry(record.status)
+            .or_default()
+            .insert(user.clone());
+        for scope in &record.scopes {
+            self.by_scope
+                .entry(scope.clone())
+                .or_default()
+                .insert(user.clone());
+        }
+        if let Some(jurisdiction) = &record.jurisdiction {
+            self.by_jurisdiction
+                .entry(jurisdiction.clone())
+                .or_default()
+                .insert(user.clone());
+        }
+    }
+
+    /// Drop `record` from the index
+    pub(crate) fn remove(&mut self, record: &ConsentRecord) {
+        fn unlink<K: std::hash::Hash + Eq>(
+            index: &mut HashMap<K, BTreeSet<String>>,
+            key: &K,
+            user: &str,
+        ) {
+            if let Some(users) = index.get_mut(key) {
+                users.remove(user);
+                if users.is_empty() {
+                    index.remove(key);
+                }
+            }
+        }
+
+        let user = &record.user_id;
+        unlink(&mut self.by_status, &record.status, user);
+        for scope in &record.scopes {
+            unlink(&mut self.by_scope, scope, user);
+        }
+        if let Some(jurisdiction) = &record.jurisdiction {
+            unlink(&mut self.by_jurisdiction, jurisdiction, user);
+        }
+    }
+
+    /// Users satisfying every indexed filter of `query`, in order; `None`
+    /// if the query sets no indexed filter
+    pub(crate) fn candidates(&self, query: &ConsentQuery) -> Option<BTreeSet<String>> {
+        let lookups = [
+            query.status.map(|s| self.by_status.get(&s)),
+            query.scope.as_ref().map(|s| self.by_scope.get(s)),
+            query
+                .jurisdiction
+                .as_ref()
+                .map(|j| self.by_jurisdiction.get(j)),
+        ];
+        lookups
+            .into_iter()
+            .flatten()
+            .map(|users| users.cloned().unwrap_or_default())
+            .reduce(|acc, users| acc.intersection(&users).cloned().collect())
+    }
+}
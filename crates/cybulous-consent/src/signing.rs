@@ -0,0 +1,42 @@
+//! Platform signatures over issued documents
+//!
+//! Receipts, summaries and exports the engine issues carry a base64url
+//! Ed25519 signature over their canonical JSON bytes, so anyone holding the
+//! platform's public key can check them.
+
+use crate::{ConsentError, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::Serialize;
+
+/// Canonical bytes of `fields`, the part of a document its signature covers
+pub(crate) fn signed_bytes<T: Serialize>(fields: &T) -> Result<Vec<u8>> {
+    serde_json::to_vec(fields).map_err(|e| ConsentError::AttestationInvalid(e.to_string()))
+}
+
+/// Base64url signature over `bytes` with the platform key
+pub(crate) fn sign(key: &SigningKey, bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(key.sign(bytes).to_bytes())
+}
+
+/// Check `signature` is `public_key`'s signature over `bytes`
+///
+/// `document` names what was signed in the error.
+pub(crate) fn verify(
+    public_key: &VerifyingKey,
+    bytes: &[u8],
+    signature: &str,
+    document: &str,
+) -> Result<()> {
+    let invalid =
+        |reason: &str| ConsentError::AttestationInvalid(format!("{} {}", document, reason));
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .ok()
+        .and_then(|bytes| Signature::from_slice(&bytes).ok())
+        .ok_or_else(|| invalid("signature malformed"))?;
+    public_key
+        .verify(bytes, &signature)
+        .map_err(|_| invalid("signature mismatch"))
+}
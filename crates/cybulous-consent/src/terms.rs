@@ -0,0 +1,27 @@
+//! Versioned consent terms
+//!
+//! Each record carries the terms version it was granted under. When the
+//! engine's active terms advance, earlier consents become stale and fail
+//! verification until the user consents again.
+
+use serde::{Deserialize, Serialize};
+
+/// Terms version assumed for records granted before versioning existed
+pub const INITIAL_TERMS_VERSION: u32 = 1;
+
+pub(crate) fn initial_terms_version() -> u32 {
+    INITIAL_TERMS_VERSION
+}
+
+/// Detailed outcome of a consent verification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsentVerification {
+    /// Whether consent is valid for the presented proof
+    pub valid: bool,
+    /// Whether the consent was granted under superseded terms
+    pub consent_stale: bool,
+    /// Terms version the consent was granted under
+    pub terms_version: u32,
+    /// Terms version currently in force
+    pub active_terms_version: u32,
+}
@@ -0,0 +1,94 @@
+//! Platform-signed consent tokens
+//!
+//! A `ConsentToken` bundles a user's consent decision into a short-lived,
+//! Ed25519-signed statement that downstream services verify offline with
+//! the platform's public key, without calling back into the engine.
+
+use crate::{ConsentError, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// Claims embedded in a consent token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenClaims {
+    /// Subject user ID
+    pub sub: String,
+    /// Scopes granted at issuance
+    pub scopes: Vec<String>,
+    /// Assurance level of the underlying consent (0 = baseline)
+    pub level: u8,
+    /// Issuance time
+    pub iat: DateTime<Utc>,
+    /// Expiration time
+    pub exp: DateTime<Utc>,
+}
+
+/// Signed consent token in compact `claims.signature` form
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsentToken(String);
+
+impl ConsentToken {
+    /// Sign claims with the platform key
+    pub fn sign(claims: &TokenClaims, key: &SigningKey) -> Result<Self> {
+        let payload =
+            serde_json::to_vec(claims).map_err(|e| ConsentError::TokenInvalid(e.to_string()))?;
+        let encoded = URL_SAFE_NO_PAD.encode(payload);
+        let signature = key.sign(encoded.as_bytes());
+
+        Ok(Self(format!(
+            "{}.{}",
+            encoded,
+            URL_SAFE_NO_PAD.encode(signature.to_bytes())
+        )))
+    }
+
+    /// Parse a token from its compact string form
+    pub fn from_compact(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    /// Compact string form suitable for transport
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Verify signature and expiry, returning the embedded claims
+    pub fn verify(&self, public_key: &VerifyingKey) -> Result<TokenClaims> {
+        self.verify_at(public_key, Utc::now())
+    }
+
+    /// Verify signature and expiry as of `now`
+    pub fn verify_at(&self, public_key: &VerifyingKey, now: DateTime<Utc>) -> Result<TokenClaims> {
+        let (encoded, signature) = self
+            .0
+            .split_once('.')
+            .ok_or_else(|| ConsentError::TokenInvalid("malformed token".to_string()))?;
+
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or_else(|| ConsentError::TokenInvalid("malformed signature".to_string()))?;
+        public_key
+            .verify(encoded.as_bytes(), &signature)
+            .map_err(|_| ConsentError::TokenInvalid("signature mismatch".to_string()))?;
+
+        let payload = URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|e| ConsentError::TokenInvalid(e.to_string()))?;
+        let claims: TokenClaims = serde_json::from_slice(&payload)
+            .map_err(|e| ConsentError::TokenInvalid(e.to_string()))?;
+
+        if now >= claims.exp {
+            return Err(ConsentError::TokenInvalid(format!(
+                "token expired at {}",
+                claims.exp
+            )));
+        }
+
+        Ok(claims)
+    }
+}
@@ -0,0 +1,112 @@
+//! Error classification and alerting thresholds
+//!
+//! Executor errors are classified so alerting can distinguish a burst of
+//! transient network blips from a spike of permanent or authorization
+//! failures. The orchestrator counts classified errors per tool over a
+//! rolling window and emits an `ErrorAlert` when a class crosses its
+//! threshold. Classes without a threshold never alert.
+
+use crate::CybulousError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Default window over which errors are counted
+pub const DEFAULT_ALERT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Broad class of an executor error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ErrorClass {
+    /// Likely to succeed on retry, e.g. timeouts or dropped connections
+    Transient,
+    /// Will fail again without a change, e.g. bad input or a bug
+    Permanent,
+    /// Credentials, consent, or permissions were rejected
+    Auth,
+    /// A quota, budget, or capacity limit was hit
+    Resource,
+}
+
+/// Default classification of an error by its variant
+pub fn classify(error: &CybulousError) -> ErrorClass {
+    match error {
+        CybulousError::NetworkError(_) => ErrorClass::Transient,
+        CybulousError::AccessDenied(_) | CybulousError::ConsentError(_) => ErrorClass::Auth,
+        CybulousError::RateLimited(_)
+        | CybulousError::CircuitOpen(_)
+        | CybulousError::BudgetExceeded(_)
+        | CybulousError::Draining(_) => ErrorClass::Resource,
+        _ => ErrorClass::Permanent,
+    }
+}
+
+/// Emitted when a class of errors for a tool crosses its threshold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorAlert {
+    /// Tool producing the errors
+    pub tool_name: String,
+    /// Class that crossed its threshold
+    pub class: ErrorClass,
+    /// Errors of the class within the window
+    pub count: usize,
+    /// When the threshold was crossed
+    pub raised_at: DateTime<Utc>,
+}
+
+/// Per-class thresholds over a rolling window
+#[derive(Debug)]
+pub struct ErrorAlerter {
+    window: Duration,
+    thresholds: HashMap<ErrorClass, usize>,
+    errors: Mutex<HashMap<(String, ErrorClass), VecDeque<Instant>>>,
+}
+
+impl Default for ErrorAlerter {
+    /// Alert on permanent and auth spikes; never on transient errors
+    fn default() -> Self {
+        Self::new(DEFAULT_ALERT_WINDOW)
+            .with_threshold(ErrorClass::Permanent, 5)
+            .with_threshold(ErrorClass::Auth, 3)
+            .with_threshold(ErrorClass::Resource, 20)
+    }
+}
+
+impl ErrorAlerter {
+    /// Count errors over `window`, with no thresholds configured
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            thresholds: HashMap::new(),
+            errors: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Alert when `count` errors of `class` occur within the window
+    pub fn with_threshold(mut self, class: ErrorClass, count: usize) -> Self {
+        self.thresholds.insert(class, count);
+        self
+    }
+
+    /// Record an error, returning an alert if it crossed the threshold
+    pub fn record(&self, tool_name: &str, class: ErrorClass, now: Instant) -> Option<ErrorAlert> {
+        let threshold = *self.thresholds.get(&class)?;
+        let mut errors = self.errors.lock().unwrap_or_else(|e| e.into_inner());
+        let recent = errors.entry((tool_name.to_string(), class)).or_default();
+        while recent
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= self.window)
+        {
+            recent.pop_front();
+        }
+        recent.push_back(now);
+
+        (recent.len() == threshold).then(|| ErrorAlert {
+            tool_name: tool_name.to_string(),
+            class,
+            count: recent.len(),
+            raised_at: Utc::now(),
+        })
+    }
+}
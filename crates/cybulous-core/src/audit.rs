@@ -0,0 +1,170 @@
+//! Audit log of orchestrated tool calls
+//!
+//! Keeps a bounded, in-memory history of executed calls with their
+//! causality links so call lineage can be reconstructed after the fact.
+
+use crate::circuit::CircuitState;
+use crate::orchestration::{ExecutionStatus, ToolCall};
+use crate::sampling;
+use crate::usage;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Default number of records retained by the audit log
+pub const DEFAULT_AUDIT_CAPACITY: usize = 10_000;
+
+/// Audit record for a single tool call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Call identifier
+    pub call_id: Uuid,
+    /// Tool that was invoked
+    pub tool_name: String,
+    /// User on whose behalf the call ran
+    pub user_id: String,
+    /// Root of the causality chain, if derived
+    pub root_call_id: Option<Uuid>,
+    /// Immediate parent call, if derived
+    pub parent_call_id: Option<Uuid>,
+    /// Final execution status
+    pub status: ExecutionStatus,
+    /// When the call completed
+    pub timestamp: DateTime<Utc>,
+    /// Whether a detailed trace was recorded for the call
+    #[serde(default)]
+    pub sampled: bool,
+    /// Cost center billed for the call
+    #[serde(default)]
+    pub cost_center: Option<String>,
+    /// Attribution tags carried by the call
+    #[serde(default)]
+    pub attribution: HashMap<String, String>,
+}
+
+/// Manual change an operator made to a circuit breaker
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitAuditEvent {
+    /// Tool the change was requested for
+    pub tool_name: String,
+    /// Group whose shared breaker changed, or `None` if the tool's own
+    /// breaker changed
+    #[serde(default)]
+    pub group: Option<String>,
+    /// State the circuit was forced into
+    pub state: CircuitState,
+    /// When the change was made
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Bounded in-memory audit log
+#[derive(Debug)]
+pub struct AuditLog {
+    records: RwLock<VecDeque<AuditRecord>>,
+    circuit_events: RwLock<VecDeque<CircuitAuditEvent>>,
+    capacity: usize,
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_AUDIT_CAPACITY)
+    }
+}
+
+impl AuditLog {
+    /// Create a log retaining at most `capacity` records
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: RwLock::new(VecDeque::new()),
+            circuit_events: RwLock::new(VecDeque::new()),
+            capacity,
+        }
+    }
+
+    /// Record the outcome of a call, evicting the oldest record when full
+    pub async fn record(&self, call: &ToolCall, status: ExecutionStatus) {
+        let mut records = self.records.write().await;
+        if records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(AuditRecord {
+            call_id: call.id,
+            tool_name: call.tool_name.clone(),
+            user_id: call.user_id.clone(),
+            root_call_id: call.context.root_call_id,
+            parent_call_id: call.context.parent_call_id,
+            status,
+            timestamp: Utc::now(),
+            sampled: sampling::sampling_decision(call).unwrap_or(false),
+            cost_center: usage::cost_center(call).map(str::to_string),
+            attribution: usage::attribution(call),
+        });
+    }
+
+    /// Record a manual circuit change, evicting the oldest event when full
+    pub async fn record_circuit_change(
+        &self,
+        tool_name: &str,
+        group: Option<&str>,
+        state: CircuitState,
+    ) {
+        let mut events = self.circuit_events.write().await;
+        if events.len() >= self.capacity {
+            events.pop_front();
+        }
+        events.push_back(CircuitAuditEvent {
+            tool_name: tool_name.to_string(),
+            group: group.map(str::to_string),
+            state,
+            timestamp: Utc::now(),
+        });
+    }
+
+    /// All retained manual circuit changes, oldest first
+    pub async fn circuit_events(&self) -> Vec<CircuitAuditEvent> {
+        self.circuit_events.read().await.iter().cloned().collect()
+    }
+
+    /// Look up the record for a call
+    pub async fn get(&self, call_id: Uuid) -> Option<AuditRecord> {
+        self.records
+            .read()
+            .await
+            .iter()
+            .rev()
+            .find(|r| r.call_id == call_id)
+            .cloned()
+    }
+
+    /// All retained records, oldest first
+    pub async fn records(&self) -> Vec<AuditRecord> {
+        self.records.read().await.iter().cloned().collect()
+    }
+
+    /// Reconstruct the causality chain ending at `call_id`, root first
+    ///
+    /// Stops early if an ancestor has been evicted from the log.
+    pub async fn causality_chain(&self, call_id: Uuid) -> Vec<AuditRecord> {
+        let mut chain = Vec::new();
+        let mut next = Some(call_id);
+
+        while let Some(id) = next {
+            // Guard against malformed, self-referencing parents
+            if chain.iter().any(|r: &AuditRecord| r.call_id == id) {
+                break;
+            }
+            match self.get(id).await {
+                Some(record) => {
+                    next = record.parent_call_id;
+                    chain.push(record);
+                }
+                None => break,
+            }
+        }
+
+        chain.reverse();
+        chain
+    }
+}
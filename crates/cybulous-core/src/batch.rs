@@ -0,0 +1,62 @@
+//! Many independent calls submitted together
+//!
+//! A batch runs its calls concurrently and reports each one's outcome
+//! separately, so one failing call does not hide the results of the rest.
+//! Calls rejected before producing a response are reported as responses
+//! carrying the rejection as their error.
+
+use crate::orchestration::{ExecutionStatus, ToolResponse};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Outcome of every call in a batch
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchResponse {
+    /// Response to each call, keyed by call id
+    pub responses: HashMap<Uuid, ToolResponse>,
+}
+
+impl BatchResponse {
+    /// Response to call `call_id`, if it was part of the batch
+    pub fn get(&self, call_id: Uuid) -> Option<&ToolResponse> {
+        self.responses.get(&call_id)
+    }
+
+    /// Status of call `call_id`, if it was part of the batch
+    pub fn status(&self, call_id: Uuid) -> Option<ExecutionStatus> {
+        self.get(call_id).map(|r| r.status)
+    }
+
+    /// Number of calls in the batch
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    /// Whether the batch had no calls
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+
+    /// Number of calls that succeeded
+    pub fn succeeded(&self) -> usize {
+        self.responses
+            .values()
+            .filter(|r| r.status == ExecutionStatus::Success)
+            .count()
+    }
+
+    /// Ids of the calls that did not succeed
+    pub fn failed(&self) -> Vec<Uuid> {
+        self.responses
+            .values()
+            .filter(|r| r.status != ExecutionStatus::Success)
+            .map(|r| r.call_id)
+            .collect()
+    }
+
+    /// Whether every call succeeded
+    pub fn is_success(&self) -> bool {
+        self.succeeded() == self.len()
+    }
+}
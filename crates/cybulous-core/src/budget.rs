@@ -0,0 +1,49 @@
+//! Per-user execution budgets
+//!
+//! Executors declare a cost per call. The orchestrator debits the caller's
+//! budget before dispatch and refunds it if the call is rejected before
+//! the executor runs, so only genuine executions consume budget.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Remaining cost units per user; users without a budget are unmetered
+#[derive(Debug, Default)]
+pub struct BudgetLedger {
+    remaining: Mutex<HashMap<String, u64>>,
+}
+
+impl BudgetLedger {
+    /// Set a user's remaining budget
+    pub fn set(&self, user_id: &str, units: u64) {
+        self.lock().insert(user_id.to_string(), units);
+    }
+
+    /// Remaining budget for a user, if metered
+    pub fn remaining(&self, user_id: &str) -> Option<u64> {
+        self.lock().get(user_id).copied()
+    }
+
+    /// Debit `cost` if the user can afford it
+    pub fn debit(&self, user_id: &str, cost: u64) -> bool {
+        match self.lock().get_mut(user_id) {
+            Some(remaining) if *remaining < cost => false,
+            Some(remaining) => {
+                *remaining -= cost;
+                true
+            }
+            None => true,
+        }
+    }
+
+    /// Return a previously debited `cost`
+    pub fn refund(&self, user_id: &str, cost: u64) {
+        if let Some(remaining) = self.lock().get_mut(user_id) {
+            *remaining = remaining.saturating_add(cost);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, u64>> {
+        self.remaining.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
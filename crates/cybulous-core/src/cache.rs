@@ -0,0 +1,282 @@
+//! Per-user caching of tool results
+//!
+//! Executors opt in by declaring a cache TTL. Successful responses are
+//! cached per tool, user, and parameters, and may carry tags describing
+//! the upstream state they depend on. When that state changes, callers
+//! purge affected entries with a `CachePredicate`.
+//!
+//! A cache may be backed by a `CacheStore` so warm entries survive a
+//! restart: every insert and removal is written through to the store, and
+//! unexpired entries are reloaded when the cache is created.
+
+use crate::orchestration::{ToolCall, ToolResponse};
+use crate::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Selects cache entries to invalidate; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CachePredicate {
+    /// Only entries for this tool
+    pub tool_name: Option<String>,
+    /// Only entries whose parameters contain these top-level fields
+    pub parameters: Option<serde_json::Value>,
+    /// Only entries carrying this tag
+    pub tag: Option<String>,
+}
+
+impl CachePredicate {
+    /// Match entries for `tool_name`
+    pub fn tool(tool_name: impl Into<String>) -> Self {
+        Self {
+            tool_name: Some(tool_name.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Match entries carrying `tag`
+    pub fn tag(tag: impl Into<String>) -> Self {
+        Self {
+            tag: Some(tag.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Additionally require parameters containing every field of `parameters`
+    pub fn with_parameters(mut self, parameters: serde_json::Value) -> Self {
+        self.parameters = Some(parameters);
+        self
+    }
+
+    fn matches(&self, entry: &CacheEntry) -> bool {
+        if self
+            .tool_name
+            .as_ref()
+            .is_some_and(|t| *t != entry.tool_name)
+        {
+            return false;
+        }
+        if self.tag.as_ref().is_some_and(|t| !entry.tags.contains(t)) {
+            return false;
+        }
+        match self.parameters.as_ref().and_then(|p| p.as_object()) {
+            Some(fields) => fields
+                .iter()
+                .all(|(key, value)| entry.parameters.get(key) == Some(value)),
+            None => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    tool_name: String,
+    parameters: serde_json::Value,
+    tags: Vec<String>,
+    response: ToolResponse,
+    expires_at: Instant,
+}
+
+/// Cache entry as written to a `CacheStore`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedEntry {
+    /// Stable hash of the tool, user, and parameters
+    pub key: String,
+    /// Tool that produced the response
+    pub tool_name: String,
+    /// Parameters of the cached call
+    pub parameters: serde_json::Value,
+    /// Tags naming upstream state the response depends on
+    pub tags: Vec<String>,
+    /// Cached response
+    pub response: ToolResponse,
+    /// Wall-clock time after which the entry is stale
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Durable backing for a `ResultCache`
+pub trait CacheStore: Send + Sync {
+    /// All persisted entries, including any that have since expired
+    fn load(&self) -> Result<Vec<PersistedEntry>>;
+
+    /// Write `entry`, replacing any entry with the same key
+    fn put(&self, entry: &PersistedEntry) -> Result<()>;
+
+    /// Delete the entry stored under `key`, if any
+    fn remove(&self, key: &str) -> Result<()>;
+}
+
+/// `CacheStore` that outlives the caches using it but not the process
+#[derive(Debug, Default)]
+pub struct MemoryCacheStore {
+    entries: Mutex<HashMap<String, PersistedEntry>>,
+}
+
+impl MemoryCacheStore {
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, PersistedEntry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl CacheStore for MemoryCacheStore {
+    fn load(&self) -> Result<Vec<PersistedEntry>> {
+        Ok(self.lock().values().cloned().collect())
+    }
+
+    fn put(&self, entry: &PersistedEntry) -> Result<()> {
+        self.lock().insert(entry.key.clone(), entry.clone());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        self.lock().remove(key);
+        Ok(())
+    }
+}
+
+/// In-memory result cache keyed by tool, user, and parameters
+#[derive(Default)]
+pub struct ResultCache {
+    entries: Mutex<HashMap<String, CacheEntry>>,
+    store: Option<Arc<dyn CacheStore>>,
+}
+
+impl fmt::Debug for ResultCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResultCache")
+            .field("persistent", &self.store.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ResultCache {
+    /// Cache backed by `store`, warmed with its unexpired entries
+    ///
+    /// A store that cannot be read leaves the cache cold rather than
+    /// failing startup.
+    pub fn with_store(store: Arc<dyn CacheStore>) -> Self {
+        let mut entries = HashMap::new();
+        match store.load() {
+            Ok(persisted) => {
+                let now = Utc::now();
+                for entry in persisted {
+                    let Ok(remaining) = (entry.expires_at - now).to_std() else {
+                        Self::forget(store.as_ref(), &entry.key);
+                        continue;
+                    };
+                    entries.insert(
+                        entry.key,
+                        CacheEntry {
+                            tool_name: entry.tool_name,
+                            parameters: entry.parameters,
+                            tags: entry.tags,
+                            response: entry.response,
+                            expires_at: Instant::now() + remaining,
+                        },
+                    );
+                }
+            }
+            Err(e) => warn!("Failed to load persisted result cache: {}", e),
+        }
+        Self {
+            entries: Mutex::new(entries),
+            store: Some(store),
+        }
+    }
+
+    /// Stable across processes, so persisted entries stay addressable
+    pub(crate) fn key(call: &ToolCall) -> String {
+        let digest = Sha256::new()
+            .chain_update(call.tool_name.as_bytes())
+            .chain_update([0])
+            .chain_update(call.user_id.as_bytes())
+            .chain_update([0])
+            .chain_update(call.parameters.to_string().as_bytes())
+            .finalize();
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn forget(store: &dyn CacheStore, key: &str) {
+        if let Err(e) = store.remove(key) {
+            warn!("Failed to remove persisted cache entry: {}", e);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, CacheEntry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Cached response for an equivalent call, if unexpired
+    pub fn get(&self, call: &ToolCall) -> Option<ToolResponse> {
+        let key = Self::key(call);
+        let mut entries = self.lock();
+        match entries.get(&key) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.response.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                if let Some(store) = &self.store {
+                    Self::forget(store.as_ref(), &key);
+                }
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Cache a response to `call` for `ttl`
+    pub fn insert(
+        &self,
+        call: &ToolCall,
+        response: ToolResponse,
+        tags: Vec<String>,
+        ttl: Duration,
+    ) {
+        let key = Self::key(call);
+        if let Some(store) = &self.store {
+            let persisted = PersistedEntry {
+                key: key.clone(),
+                tool_name: call.tool_name.clone(),
+                parameters: call.parameters.clone(),
+                tags: tags.clone(),
+                response: response.clone(),
+                expires_at: Utc::now()
+                    + chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::MAX),
+            };
+            if let Err(e) = store.put(&persisted) {
+                warn!("Failed to persist cache entry: {}", e);
+            }
+        }
+        self.lock().insert(
+            key,
+            CacheEntry {
+                tool_name: call.tool_name.clone(),
+                parameters: call.parameters.clone(),
+                tags,
+                response,
+                expires_at: Instant::now() + ttl,
+            },
+        );
+    }
+
+    /// Remove entries matching `predicate`, returning how many were removed
+    pub fn invalidate(&self, predicate: &CachePredicate) -> usize {
+        let mut entries = self.lock();
+        let before = entries.len();
+        entries.retain(|key, entry| {
+            let matched = predicate.matches(entry);
+            if matched {
+                if let Some(store) = &self.store {
+                    Self::forget(store.as_ref(), key);
+                }
+            }
+            !matched
+        });
+        before - entries.len()
+    }
+}
@@ -0,0 +1,51 @@
+//! Cooperative cancellation for executors
+//!
+//! When a call is cancelled the orchestrator drops its execution future,
+//! which stops in-process work. Work an executor hands to background tasks
+//! outlives that future, so executors receive a `CancellationToken` they
+//! can pass along and watch.
+
+use crate::orchestration::CancellationReason;
+use tokio::sync::watch;
+
+/// Signals that a call was cancelled, and why
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    reason: watch::Receiver<Option<CancellationReason>>,
+}
+
+impl CancellationToken {
+    pub(crate) fn new(reason: watch::Receiver<Option<CancellationReason>>) -> Self {
+        Self { reason }
+    }
+
+    /// A token that is never cancelled
+    pub fn never() -> Self {
+        Self::new(watch::channel(None).1)
+    }
+
+    /// Why the call was cancelled, if it has been
+    pub fn reason(&self) -> Option<CancellationReason> {
+        *self.reason.borrow()
+    }
+
+    /// Whether the call has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.reason().is_some()
+    }
+
+    /// Wait until the call is cancelled
+    ///
+    /// Never resolves if the call ends without being cancelled.
+    pub async fn cancelled(&self) -> CancellationReason {
+        let mut receiver = self.reason.clone();
+        let reason = match receiver.wait_for(Option::is_some).await {
+            Ok(reason) => *reason,
+            Err(_) => None,
+        };
+        match reason {
+            Some(reason) => reason,
+            None => std::future::pending().await,
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Capability-based access tokens
+//!
+//! A `CapabilityToken` is an Ed25519-signed grant listing the tools and
+//! capabilities a client may invoke. When the orchestrator is configured
+//! with an issuer key, it checks the token before consent and rejects
+//! out-of-scope calls.
+
+use crate::{CybulousError, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// Context metadata key carrying the caller's capability token
+pub const CAPABILITY_TOKEN_KEY: &str = "capability_token";
+
+/// Grants embedded in a capability token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityClaims {
+    /// Client the token was issued to
+    pub subject: String,
+    /// Tool names the client may invoke
+    pub tools: Vec<String>,
+    /// Capabilities the client may invoke on any tool advertising them
+    pub capabilities: Vec<String>,
+    /// Expiration time
+    pub expires_at: DateTime<Utc>,
+}
+
+impl CapabilityClaims {
+    /// Whether the claims permit calling `tool_name`
+    ///
+    /// `supports` reports whether the tool advertises a given capability.
+    pub fn permits(&self, tool_name: &str, supports: impl Fn(&str) -> bool) -> bool {
+        self.tools.iter().any(|t| t == tool_name) || self.capabilities.iter().any(|c| supports(c))
+    }
+}
+
+/// Signed capability token in compact `claims.signature` form
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityToken(String);
+
+impl CapabilityToken {
+    /// Sign claims with the issuer key
+    pub fn sign(claims: &CapabilityClaims, key: &SigningKey) -> Result<Self> {
+        let encoded = URL_SAFE_NO_PAD.encode(serde_json::to_vec(claims)?);
+        let signature = key.sign(encoded.as_bytes());
+        Ok(Self(format!(
+            "{}.{}",
+            encoded,
+            URL_SAFE_NO_PAD.encode(signature.to_bytes())
+        )))
+    }
+
+    /// Wrap a token received in compact form
+    pub fn from_compact(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    /// Compact string form
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Verify signature and expiry, returning the claims
+    pub fn verify(&self, issuer: &VerifyingKey) -> Result<CapabilityClaims> {
+        let invalid =
+            |reason: &str| CybulousError::AccessDenied(format!("capability token {}", reason));
+
+        let (encoded, signature) = self.0.split_once('.').ok_or_else(|| invalid("malformed"))?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or_else(|| invalid("signature malformed"))?;
+        issuer
+            .verify(encoded.as_bytes(), &signature)
+            .map_err(|_| invalid("signature mismatch"))?;
+
+        let payload = URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|_| invalid("payload malformed"))?;
+        let claims: CapabilityClaims = serde_json::from_slice(&payload)?;
+
+        if Utc::now() >= claims.expires_at {
+            return Err(invalid("expired"));
+        }
+        Ok(claims)
+    }
+}
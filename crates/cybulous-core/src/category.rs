@@ -0,0 +1,41 @@
+//! Tool category taxonomy
+//!
+//! Executors declare a `ToolCategory` describing the kind of effect they
+//! have. Categories drive UI grouping and coarse policy, such as denying
+//! every `External` tool during an incident or draining to read-only
+//! tools during maintenance.
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of effect a tool has
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ToolCategory {
+    /// Reads data without side effects
+    Read,
+    /// Modifies platform data
+    Write,
+    /// Administrative operations
+    Admin,
+    /// Calls out to third-party services
+    External,
+}
+
+/// Maintenance posture restricting which categories may run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DrainMode {
+    /// All categories run normally
+    #[default]
+    Off,
+    /// Only `Read` tools run; everything else is rejected
+    ReadOnly,
+}
+
+impl DrainMode {
+    /// Whether tools in `category` may run under this mode
+    pub fn permits(self, category: ToolCategory) -> bool {
+        match self {
+            DrainMode::Off => true,
+            DrainMode::ReadOnly => category == ToolCategory::Read,
+        }
+    }
+}
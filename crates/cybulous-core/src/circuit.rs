@@ -0,0 +1,306 @@
+//! Circuit breaker for failing executors
+//!
+//! Opens after a run of consecutive failures, rejects calls until the reset
+//! timeout elapses, then admits a single half-open probe whose outcome
+//! decides whether the circuit closes again.
+//!
+//! Operators can also force a circuit open or closed. A forced-open circuit
+//! stays open, ignoring call outcomes and the reset timeout, until it is
+//! forced closed.
+//!
+//! Each breaker counts its state transitions for metrics.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Circuit breaker configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a probe is allowed
+    pub reset_timeout: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            reset_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Circuit state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CircuitState {
+    /// Calls flow normally
+    Closed,
+    /// Calls are rejected
+    Open,
+    /// A single probe call is allowed through
+    HalfOpen,
+}
+
+/// Count of transitions into each circuit state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitMetrics {
+    /// Times the circuit opened
+    pub opened: u64,
+    /// Times the circuit moved to half-open
+    pub half_opened: u64,
+    /// Times the circuit closed again
+    pub closed: u64,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: CircuitState,
+    metrics: CircuitMetrics,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    probe_in_flight: bool,
+    forced_open: bool,
+}
+
+/// Consecutive-failure circuit breaker
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    inner: Mutex<Inner>,
+}
+
+impl CircuitBreaker {
+    /// Create a closed breaker
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(Inner {
+                state: CircuitState::Closed,
+                metrics: CircuitMetrics::default(),
+                consecutive_failures: 0,
+                opened_at: None,
+                probe_in_flight: false,
+                forced_open: false,
+            }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Current state, promoting an expired open circuit to half-open
+    pub fn state(&self) -> CircuitState {
+        let mut inner = self.lock();
+        self.refresh(&mut inner);
+        inner.state
+    }
+
+    /// Transitions recorded so far
+    pub fn metrics(&self) -> CircuitMetrics {
+        let mut inner = self.lock();
+        self.refresh(&mut inner);
+        inner.metrics
+    }
+
+    fn refresh(&self, inner: &mut Inner) {
+        if inner.state == CircuitState::Open
+            && !inner.forced_open
+            && inner
+                .opened_at
+                .is_some_and(|t| t.elapsed() >= self.config.reset_timeout)
+        {
+            inner.enter(CircuitState::HalfOpen);
+            inner.probe_in_flight = false;
+        }
+    }
+
+    /// Whether a call may proceed; claims the probe slot when half-open
+    pub fn allow(&self) -> bool {
+        let mut inner = self.lock();
+        self.refresh(&mut inner);
+
+        match inner.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => false,
+            CircuitState::HalfOpen if inner.probe_in_flight => false,
+            CircuitState::HalfOpen => {
+                inner.probe_in_flight = true;
+                true
+            }
+        }
+    }
+
+    /// Admit a call, returning a permit to record its outcome with
+    ///
+    /// Like [`CircuitBreaker::allow`], but a permit dropped without an
+    /// outcome gives back the half-open probe slot it claimed, so a call
+    /// that ends without executing cannot wedge the circuit half-open.
+    pub fn try_admit(self: &Arc<Self>) -> Option<CircuitPermit> {
+        let mut inner = self.lock();
+        self.refresh(&mut inner);
+
+        let probe = match inner.state {
+            CircuitState::Closed => false,
+            CircuitState::Open => return None,
+            CircuitState::HalfOpen if inner.probe_in_flight => return None,
+            CircuitState::HalfOpen => {
+                inner.probe_in_flight = true;
+                true
+            }
+        };
+        Some(CircuitPermit {
+            breaker: self.clone(),
+            probe,
+        })
+    }
+
+    /// Record a successful call, closing the circuit
+    pub fn record_success(&self) {
+        let mut inner = self.lock();
+        if inner.forced_open {
+            return;
+        }
+        inner.enter(CircuitState::Closed);
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.probe_in_flight = false;
+    }
+
+    /// Record a failed call, opening the circuit once the threshold is hit
+    pub fn record_failure(&self) {
+        let mut inner = self.lock();
+        if inner.forced_open {
+            return;
+        }
+        inner.consecutive_failures += 1;
+
+        if inner.state == CircuitState::HalfOpen
+            || inner.consecutive_failures >= self.config.failure_threshold
+        {
+            inner.enter(CircuitState::Open);
+            inner.opened_at = Some(Instant::now());
+            inner.probe_in_flight = false;
+        }
+    }
+
+    /// Open the circuit until [`CircuitBreaker::force_close`] is called
+    pub fn force_open(&self) {
+        let mut inner = self.lock();
+        inner.enter(CircuitState::Open);
+        inner.opened_at = Some(Instant::now());
+        inner.probe_in_flight = false;
+        inner.forced_open = true;
+    }
+
+    /// Close the circuit and reset its failure count
+    pub fn force_close(&self) {
+        let mut inner = self.lock();
+        inner.forced_open = false;
+        inner.enter(CircuitState::Closed);
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.probe_in_flight = false;
+    }
+}
+
+/// A call admitted through a [`CircuitBreaker`]
+///
+/// Dropping the permit without recording an outcome releases the
+/// half-open probe slot, if the call claimed it.
+#[derive(Debug)]
+pub struct CircuitPermit {
+    breaker: Arc<CircuitBreaker>,
+    probe: bool,
+}
+
+impl CircuitPermit {
+    /// Record that the call succeeded
+    pub fn record_success(mut self) {
+        self.probe = false;
+        self.breaker.record_success();
+    }
+
+    /// Record that the call failed
+    pub fn record_failure(mut self) {
+        self.probe = false;
+        self.breaker.record_failure();
+    }
+}
+
+impl Drop for CircuitPermit {
+    fn drop(&mut self) {
+        if self.probe {
+            let mut inner = self.breaker.lock();
+            if inner.state == CircuitState::HalfOpen {
+                inner.probe_in_flight = false;
+            }
+        }
+    }
+}
+
+impl Inner {
+    fn enter(&mut self, state: CircuitState) {
+        if self.state == state {
+            return;
+        }
+        self.state = state;
+        match state {
+            CircuitState::Open => self.metrics.opened += 1,
+            CircuitState::HalfOpen => self.metrics.half_opened += 1,
+            CircuitState::Closed => self.metrics.closed += 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breaker_opens_and_recovers() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            reset_timeout: Duration::from_millis(10),
+        });
+
+        breaker.record_failure();
+        assert!(breaker.allow());
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allow());
+
+        std::thread::sleep(Duration::from_millis(15));
+        assert!(breaker.allow());
+        assert!(!breaker.allow(), "only one half-open probe");
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(
+            breaker.metrics(),
+            CircuitMetrics {
+                opened: 1,
+                half_opened: 1,
+                closed: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_dropped_permit_releases_probe() {
+        let breaker = Arc::new(CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            reset_timeout: Duration::from_millis(10),
+        }));
+        breaker.record_failure();
+        std::thread::sleep(Duration::from_millis(15));
+
+        let probe = breaker.try_admit().expect("half-open admits a probe");
+        assert!(breaker.try_admit().is_none(), "only one half-open probe");
+        drop(probe);
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        breaker.try_admit().unwrap().record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
@@ -0,0 +1,155 @@
+//! Pluggable wire encodings for execution context
+//!
+//! Transports encode `ExecutionContext` through a `ContextCodec` so the
+//! in-memory type stays stable while deployments choose their encoding.
+
+use crate::orchestration::ExecutionContext;
+use crate::{CybulousError, Result};
+use prost::Message;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Encoder/decoder for execution context on the wire
+pub trait ContextCodec: Send + Sync {
+    /// MIME content type produced by this codec
+    fn content_type(&self) -> &'static str;
+
+    /// Encode a context into bytes
+    fn encode(&self, context: &ExecutionContext) -> Result<Vec<u8>>;
+
+    /// Decode a context from bytes
+    fn decode(&self, bytes: &[u8]) -> Result<ExecutionContext>;
+}
+
+/// JSON context codec (default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+impl ContextCodec for JsonCodec {
+    fn content_type(&self) -> &'static str {
+        "application/json"
+    }
+
+    fn encode(&self, context: &ExecutionContext) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(context)?)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<ExecutionContext> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+/// Protocol Buffers context codec
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProtobufCodec;
+
+/// Protobuf wire representation of `ExecutionContext`
+#[derive(Clone, PartialEq, Message)]
+struct ExecutionContextProto {
+    #[prost(bytes = "vec", tag = "1")]
+    session_id: Vec<u8>,
+    #[prost(string, tag = "2")]
+    consent_proof: String,
+    #[prost(string, optional, tag = "3")]
+    biophysical_hash: Option<String>,
+    #[prost(map = "string, string", tag = "4")]
+    metadata: HashMap<String, String>,
+    #[prost(bytes = "vec", optional, tag = "5")]
+    root_call_id: Option<Vec<u8>>,
+    #[prost(bytes = "vec", optional, tag = "6")]
+    parent_call_id: Option<Vec<u8>>,
+    #[prost(string, optional, tag = "7")]
+    region: Option<String>,
+}
+
+fn decode_uuid(field: &str, bytes: &[u8]) -> Result<Uuid> {
+    Uuid::from_slice(bytes)
+        .map_err(|e| CybulousError::CodecError(format!("invalid {}: {}", field, e)))
+}
+
+impl ContextCodec for ProtobufCodec {
+    fn content_type(&self) -> &'static str {
+        "application/x-protobuf"
+    }
+
+    fn encode(&self, context: &ExecutionContext) -> Result<Vec<u8>> {
+        let proto = ExecutionContextProto {
+            session_id: context.session_id.as_bytes().to_vec(),
+            consent_proof: context.consent_proof.clone(),
+            biophysical_hash: context.biophysical_hash.clone(),
+            metadata: context.metadata.clone(),
+            root_call_id: context.root_call_id.map(|id| id.as_bytes().to_vec()),
+            parent_call_id: context.parent_call_id.map(|id| id.as_bytes().to_vec()),
+            region: context.region.clone(),
+        };
+        Ok(proto.encode_to_vec())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<ExecutionContext> {
+        let proto = ExecutionContextProto::decode(bytes)
+            .map_err(|e| CybulousError::CodecError(e.to_string()))?;
+        let root_call_id = proto
+            .root_call_id
+            .as_deref()
+            .map(|b| decode_uuid("root_call_id", b))
+            .transpose()?;
+        let parent_call_id = proto
+            .parent_call_id
+            .as_deref()
+            .map(|b| decode_uuid("parent_call_id", b))
+            .transpose()?;
+
+        Ok(ExecutionContext {
+            session_id: decode_uuid("session_id", &proto.session_id)?,
+            consent_proof: proto.consent_proof,
+            biophysical_hash: proto.biophysical_hash,
+            metadata: proto.metadata,
+            root_call_id,
+            parent_call_id,
+            region: proto.region,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_context() -> ExecutionContext {
+        let mut metadata = HashMap::new();
+        metadata.insert("region".to_string(), "eu".to_string());
+        metadata.insert("client".to_string(), "cli".to_string());
+
+        let mut context = ExecutionContext::new(Uuid::new_v4(), "proof-abc")
+            .with_region("EU")
+            .derive(Uuid::new_v4());
+        context.biophysical_hash = Some("bio-123".to_string());
+        context.metadata = metadata;
+        context
+    }
+
+    fn assert_round_trip(codec: &dyn ContextCodec) {
+        let context = sample_context();
+        let bytes = codec.encode(&context).unwrap();
+        let decoded = codec.decode(&bytes).unwrap();
+
+        assert_eq!(decoded.session_id, context.session_id);
+        assert_eq!(decoded.consent_proof, context.consent_proof);
+        assert_eq!(decoded.biophysical_hash, context.biophysical_hash);
+        assert_eq!(decoded.metadata, context.metadata);
+        assert_eq!(decoded.root_call_id, context.root_call_id);
+        assert_eq!(decoded.parent_call_id, context.parent_call_id);
+        assert_eq!(decoded.region, context.region);
+    }
+
+    #[test]
+    fn test_json_codec_round_trip() {
+        assert_round_trip(&JsonCodec);
+    }
+
+    #[test]
+    fn test_protobuf_codec_round_trip() {
+        assert_round_trip(&ProtobufCodec);
+        assert!(ProtobufCodec.decode(&[0xff, 0xff]).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Degraded-mode responses
+//!
+//! When a tool's dependencies are unhealthy, serving a recent result is
+//! often better than failing. Executors opt in with a `DegradedPolicy`;
+//! the orchestrator then remembers each call's last successful response
+//! and, if the executor later fails, returns that response marked stale
+//! provided it is no older than the policy allows.
+
+use crate::cache::ResultCache;
+use crate::orchestration::{ToolCall, ToolResponse};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How a tool may degrade when its executor fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DegradedPolicy {
+    /// Oldest last-good result that may be served in place of a failure
+    pub max_staleness: Duration,
+}
+
+impl DegradedPolicy {
+    /// Serve last-good results up to `max_staleness` old
+    pub fn new(max_staleness: Duration) -> Self {
+        Self { max_staleness }
+    }
+}
+
+#[derive(Debug)]
+struct LastGood {
+    response: ToolResponse,
+    recorded_at: Instant,
+    usable_until: Instant,
+}
+
+/// Last successful response per call, for tools with a degraded policy
+#[derive(Debug, Default)]
+pub(crate) struct LastGoodResults {
+    entries: Mutex<HashMap<String, LastGood>>,
+}
+
+impl LastGoodResults {
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, LastGood>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Remember `response` as the last good result of `call`
+    pub(crate) fn record(
+        &self,
+        call: &ToolCall,
+        response: &ToolResponse,
+        policy: DegradedPolicy,
+        now: Instant,
+    ) {
+        let mut entries = self.lock();
+        entries.retain(|_, entry| entry.usable_until > now);
+        entries.insert(
+            ResultCache::key(call),
+            LastGood {
+                response: response.clone(),
+                recorded_at: now,
+                usable_until: now + policy.max_staleness,
+            },
+        );
+    }
+
+    /// Last good result of `call`, marked stale, if within the policy's
+    /// staleness bound
+    pub(crate) fn stale(
+        &self,
+        call: &ToolCall,
+        policy: DegradedPolicy,
+        now: Instant,
+    ) -> Option<ToolResponse> {
+        let entries = self.lock();
+        let entry = entries.get(&ResultCache::key(call))?;
+        (now.duration_since(entry.recorded_at) < policy.max_staleness).then(|| ToolResponse {
+            call_id: call.id,
+            stale: true,
+            ..entry.response.clone()
+        })
+    }
+}
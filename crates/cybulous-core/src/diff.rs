@@ -0,0 +1,107 @@
+//! Structural diffs between tool results
+//!
+//! Objects are compared field by field, recursively; any other value,
+//! including arrays, is compared as a whole. Fields are addressed by JSON
+//! pointer (RFC 6901), with the empty pointer naming the whole result.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// A field whose value differs between two results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// Value in the previous result
+    pub before: Value,
+    /// Value in the new result
+    pub after: Value,
+}
+
+/// Differences from a previous result to a new one, keyed by JSON pointer
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResultDiff {
+    /// Fields present only in the new result
+    pub added: BTreeMap<String, Value>,
+    /// Fields present only in the previous result
+    pub removed: BTreeMap<String, Value>,
+    /// Fields present in both with different values
+    pub changed: BTreeMap<String, FieldChange>,
+}
+
+impl ResultDiff {
+    /// Diff two results; a missing result is treated as `null`
+    pub fn between(previous: Option<&Value>, current: Option<&Value>) -> Self {
+        let mut diff = Self::default();
+        diff.walk(
+            String::new(),
+            previous.unwrap_or(&Value::Null),
+            current.unwrap_or(&Value::Null),
+        );
+        diff
+    }
+
+    /// Whether the results are identical
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    fn walk(&mut self, path: String, before: &Value, after: &Value) {
+        match (before, after) {
+            (Value::Object(before), Value::Object(after)) => {
+                for (key, old) in before {
+                    let field = format!("{}/{}", path, escape(key));
+                    match after.get(key) {
+                        Some(new) => self.walk(field, old, new),
+                        None => {
+                            self.removed.insert(field, old.clone());
+                        }
+                    }
+                }
+                for (key, new) in after {
+                    if !before.contains_key(key) {
+                        self.added
+                            .insert(format!("{}/{}", path, escape(key)), new.clone());
+                    }
+                }
+            }
+            _ if before != after => {
+                self.changed.insert(
+                    path,
+                    FieldChange {
+                        before: before.clone(),
+                        after: after.clone(),
+                    },
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_nested_fields_and_pointer_escaping() {
+        let previous = json!({"user": {"name": "ada", "tags": ["a"]}, "a/b": 1});
+        let current = json!({"user": {"name": "ada", "tags": ["a", "b"]}, "a/b": 2});
+        let diff = ResultDiff::between(Some(&previous), Some(&current));
+
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(
+            diff.changed.keys().collect::<Vec<_>>(),
+            vec!["/a~1b", "/user/tags"]
+        );
+        assert!(ResultDiff::between(Some(&current), Some(&current)).is_empty());
+        assert_eq!(
+            ResultDiff::between(None, Some(&json!(3))).changed[""].after,
+            json!(3)
+        );
+    }
+}
@@ -0,0 +1,495 @@
+//! Discovery of remote tools
+//!
+//! A tool registry lists tools served over HTTP by remote executors.
+//! `ToolDiscovery` keeps an orchestrator in step with a registry: each sync
+//! registers a [`RemoteExecutor`] for every newly listed tool, swaps in new
+//! executors for tools whose listing changed and deregisters the ones the
+//! registry no longer lists. Tools registered by other means are never
+//! touched; a listing that collides with one is skipped.
+//!
+//! Syncs run on an interval with [`ToolDiscovery::start`], or on demand
+//! with [`ToolDiscovery::sync`], e.g. when a registry pushes a change
+//! notification.
+
+use crate::orchestration::{Orchestrator, ToolCall, ToolExecutor, ToolResponse};
+use crate::Result;
+use async_trait::async_trait;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{oneshot, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// Tool listed by a registry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteTool {
+    /// Name the tool registers under
+    pub name: String,
+    /// URL calls are POSTed to
+    pub endpoint: String,
+    /// Version of the tool served at `endpoint`
+    #[serde(default)]
+    pub version: Option<Version>,
+    /// Capabilities the tool supports
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    /// JSON Schema call parameters must satisfy
+    #[serde(default)]
+    pub input_schema: Option<serde_json::Value>,
+}
+
+/// Source of the tools currently available remotely
+#[async_trait]
+pub trait ToolRegistry: Send + Sync {
+    /// Tools currently listed
+    async fn list_tools(&self) -> Result<Vec<RemoteTool>>;
+}
+
+/// Registry serving its listing as a JSON array of [`RemoteTool`]s
+#[derive(Debug, Clone)]
+pub struct HttpToolRegistry {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl HttpToolRegistry {
+    /// Registry listing its tools at `url`
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Fetch the listing with `client`
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+#[async_trait]
+impl ToolRegistry for HttpToolRegistry {
+    async fn list_tools(&self) -> Result<Vec<RemoteTool>> {
+        let response = self.client.get(&self.url).send().await?;
+        Ok(response.error_for_status()?.json().await?)
+    }
+}
+
+/// Executor forwarding calls to a remote tool
+///
+/// The call is POSTed to the tool's endpoint as JSON and the endpoint
+/// answers with a JSON [`ToolResponse`].
+#[derive(Debug, Clone)]
+pub struct RemoteExecutor {
+    tool: RemoteTool,
+    client: reqwest::Client,
+}
+
+impl RemoteExecutor {
+    /// Executor for `tool`
+    pub fn new(tool: RemoteTool) -> Self {
+        Self {
+            tool,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Send calls with `client`
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Listing this executor serves
+    pub fn tool(&self) -> &RemoteTool {
+        &self.tool
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for RemoteExecutor {
+    async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+        let response = self
+            .client
+            .post(&self.tool.endpoint)
+            .json(call)
+            .send()
+            .await?;
+        let mut response: ToolResponse = response.error_for_status()?.json().await?;
+        response.call_id = call.id;
+        Ok(response)
+    }
+
+    fn name(&self) -> &str {
+        &self.tool.name
+    }
+
+    fn supports_capability(&self, capability: &str) -> bool {
+        self.tool.capabilities.iter().any(|c| c == capability)
+    }
+
+    fn input_schema(&self) -> Option<serde_json::Value> {
+        self.tool.input_schema.clone()
+    }
+
+    fn version(&self) -> Option<Version> {
+        self.tool.version.clone()
+    }
+}
+
+/// Tools a sync registered, re-registered and deregistered
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscoverySync {
+    /// Newly listed tools
+    pub added: Vec<String>,
+    /// Tools whose listing changed
+    pub updated: Vec<String>,
+    /// Tools no longer listed
+    pub removed: Vec<String>,
+}
+
+impl DiscoverySync {
+    /// Whether the sync changed nothing
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Keeps an orchestrator's remote executors in step with a registry
+pub struct ToolDiscovery {
+    orchestrator: Orchestrator,
+    registry: Arc<dyn ToolRegistry>,
+    client: reqwest::Client,
+    /// Listings registered by the last sync, by tool name
+    discovered: Mutex<HashMap<String, Vec<RemoteTool>>>,
+    /// Serializes syncs, which may wait for displaced executors to drain
+    syncing: Mutex<()>,
+}
+
+impl std::fmt::Debug for ToolDiscovery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolDiscovery").finish_non_exhaustive()
+    }
+}
+
+impl ToolDiscovery {
+    /// Sync `orchestrator` with `registry`
+    pub fn new(orchestrator: Orchestrator, registry: Arc<dyn ToolRegistry>) -> Self {
+        Self {
+            orchestrator,
+            registry,
+            client: reqwest::Client::new(),
+            discovered: Mutex::new(HashMap::new()),
+            syncing: Mutex::new(()),
+        }
+    }
+
+    /// Send calls to discovered tools with `client`
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Names of the tools currently registered through discovery
+    pub async fn discovered_tools(&self) -> Vec<String> {
+        let mut names: Vec<_> = self.discovered.lock().await.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Fetch the registry's listing and apply it to the orchestrator
+    ///
+    /// A changed listing is applied version by version: listed versions
+    /// are swapped in as by [`Orchestrator::replace_executor`], so the tool
+    /// stays callable throughout, and only versions no longer listed are
+    /// unregistered. Fails only if the listing cannot be fetched; a tool
+    /// that fails to register is logged and retried on the next sync.
+    pub async fn sync(&self) -> Result<DiscoverySync> {
+        let mut listing: BTreeMap<String, Vec<RemoteTool>> = BTreeMap::new();
+        for tool in self.registry.list_tools().await? {
+            listing.entry(tool.name.clone()).or_default().push(tool);
+        }
+
+        let _syncing = self.syncing.lock().await;
+        let mut discovered = self.discovered.lock().await.clone();
+        let mut changes = DiscoverySync::default();
+        let local = self.orchestrator.list_tools().await;
+
+        let vanished: Vec<_> = discovered
+            .keys()
+            .filter(|name| !listing.contains_key(*name))
+            .cloned()
+            .collect();
+        for name in vanished {
+            discovered.remove(&name);
+            self.orchestrator.unregister_executor(&name).await;
+            info!("Deregistered remote tool {}: no longer listed", name);
+            changes.removed.push(name);
+        }
+
+        for (name, tools) in listing {
+            let previous = match discovered.remove(&name) {
+                Some(previous) if previous == tools => {
+                    discovered.insert(name, previous);
+                    continue;
+                }
+                Some(previous) => {
+                    changes.updated.push(name.clone());
+                    previous
+                }
+                None if local.contains(&name) => {
+                    warn!(
+                        "Registry lists {}, which is already registered; skipping it",
+                        name
+                    );
+                    continue;
+                }
+                None => {
+                    changes.added.push(name.clone());
+                    Vec::new()
+                }
+            };
+
+            let stale: Vec<_> = previous
+                .iter()
+                .filter_map(|p| p.version.clone())
+                .filter(|v| tools.iter().all(|t| t.version.as_ref() != Some(v)))
+                .collect();
+            // Registering an unversioned executor drops every version, so
+            // stale versions must leave first; otherwise they leave last,
+            // keeping the tool callable throughout
+            let unversioned = tools.iter().any(|t| t.version.is_none());
+            if unversioned {
+                self.unregister_versions(&name, &stale).await;
+            }
+
+            let mut registered = Vec::new();
+            for tool in &tools {
+                let current = previous.iter().find(|p| p.version == tool.version);
+                if current == Some(tool) {
+                    registered.push(tool.clone());
+                    continue;
+                }
+                let executor =
+                    Arc::new(RemoteExecutor::new(tool.clone()).with_client(self.client.clone()));
+                let applied = match current {
+                    Some(_) => self.orchestrator.replace_executor(executor).await,
+                    None => self.orchestrator.register_executor(executor).await,
+                };
+                match applied {
+                    Ok(()) => registered.push(tool.clone()),
+                    Err(e) => {
+                        warn!("Failed to register remote tool {}: {}", name, e);
+                        // The version it would have replaced still serves
+                        registered.extend(current.cloned());
+                    }
+                }
+            }
+            if !unversioned {
+                self.unregister_versions(&name, &stale).await;
+            }
+            if !registered.is_empty() {
+                info!(
+                    "Registered remote tool {} at {}",
+                    name, registered[0].endpoint
+                );
+                discovered.insert(name, registered);
+            }
+        }
+
+        *self.discovered.lock().await = discovered;
+        Ok(changes)
+    }
+
+    /// Unregister versions of `name` the listing no longer has
+    async fn unregister_versions(&self, name: &str, versions: &[Version]) {
+        for version in versions {
+            self.orchestrator.unregister_version(name, version).await;
+            info!(
+                "Deregistered remote tool {} {}: no longer listed",
+                name, version
+            );
+        }
+    }
+
+    /// Start a background task syncing every `interval`
+    ///
+    /// Failed syncs are logged and retried on the next tick. Must be called
+    /// from within a Tokio runtime; stop the task with
+    /// [`DiscoveryPoller::stop`].
+    pub fn start(self: &Arc<Self>, interval: Duration) -> DiscoveryPoller {
+        let discovery = self.clone();
+        let (stop, mut stop_rx) = oneshot::channel();
+        let task = tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = &mut stop_rx => break,
+                    _ = ticks.tick() => {
+                        if let Err(e) = discovery.sync().await {
+                            warn!("Tool discovery sync failed: {}", e);
+                        }
+                    }
+                }
+            }
+        });
+        DiscoveryPoller { stop, task }
+    }
+}
+
+/// Handle to a running discovery task
+///
+/// Dropping the handle also stops the task, after any sync in progress.
+/// Discovered tools stay registered either way.
+#[derive(Debug)]
+pub struct DiscoveryPoller {
+    stop: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+impl DiscoveryPoller {
+    /// Stop syncing and wait for any sync in progress to finish
+    pub async fn stop(self) {
+        let _ = self.stop.send(());
+        if let Err(e) = self.task.await {
+            warn!("Tool discovery task ended abnormally: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Registry whose listing tests edit directly
+    #[derive(Default)]
+    struct StaticRegistry {
+        tools: std::sync::Mutex<Vec<RemoteTool>>,
+    }
+
+    impl StaticRegistry {
+        fn set(&self, tools: Vec<RemoteTool>) {
+            *self.tools.lock().unwrap() = tools;
+        }
+    }
+
+    #[async_trait]
+    impl ToolRegistry for StaticRegistry {
+        async fn list_tools(&self) -> Result<Vec<RemoteTool>> {
+            Ok(self.tools.lock().unwrap().clone())
+        }
+    }
+
+    fn tool(name: &str, endpoint: &str) -> RemoteTool {
+        RemoteTool {
+            name: name.to_string(),
+            endpoint: endpoint.to_string(),
+            version: None,
+            capabilities: vec!["remote".to_string()],
+            input_schema: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_follows_the_listing() {
+        let orchestrator = Orchestrator::new(Arc::new(cybulous_consent::ConsentEngine::mock()), 10);
+        let local = RemoteExecutor::new(tool("local", "http://localhost:1/local"));
+        orchestrator
+            .register_executor(Arc::new(local))
+            .await
+            .unwrap();
+
+        let registry = Arc::new(StaticRegistry::default());
+        let discovery = ToolDiscovery::new(orchestrator.clone(), registry.clone());
+        let mut versioned = tool("geocode", "http://localhost:1/geocode/v2");
+        versioned.version = Some(Version::new(2, 0, 0));
+        registry.set(vec![
+            tool("weather", "http://localhost:1/weather"),
+            versioned,
+            tool("local", "http://localhost:1/impostor"),
+        ]);
+
+        let changes = discovery.sync().await.unwrap();
+        assert_eq!(changes.added, ["geocode", "weather"]);
+        assert!(orchestrator.is_ready("weather").await);
+        assert_eq!(
+            orchestrator.tool_versions("geocode").await,
+            [Version::new(2, 0, 0)]
+        );
+        assert_eq!(discovery.discovered_tools().await, ["geocode", "weather"]);
+        assert!(discovery.sync().await.unwrap().is_empty());
+
+        registry.set(vec![tool("weather", "http://localhost:2/weather")]);
+        let changes = discovery.sync().await.unwrap();
+        assert_eq!(changes.updated, ["weather"]);
+        assert_eq!(changes.removed, ["geocode"]);
+        assert!(!orchestrator.is_ready("geocode").await);
+        assert!(orchestrator.is_ready("local").await);
+
+        registry.set(Vec::new());
+        discovery.sync().await.unwrap();
+        let mut tools = orchestrator.list_tools().await;
+        tools.sort();
+        assert_eq!(tools, ["local"]);
+    }
+
+    #[tokio::test]
+    async fn test_changed_listing_updates_versions_in_place() {
+        let orchestrator = Orchestrator::new(Arc::new(cybulous_consent::ConsentEngine::mock()), 10);
+        let registry = Arc::new(StaticRegistry::default());
+        let discovery = ToolDiscovery::new(orchestrator.clone(), registry.clone());
+        let geocode = |major: u64, endpoint: &str| RemoteTool {
+            version: Some(Version::new(major, 0, 0)),
+            ..tool("geocode", endpoint)
+        };
+
+        registry.set(vec![
+            geocode(1, "http://localhost:1/geocode/v1"),
+            geocode(2, "http://localhost:1/geocode/v2"),
+        ]);
+        discovery.sync().await.unwrap();
+
+        registry.set(vec![
+            geocode(2, "http://localhost:2/geocode/v2"),
+            geocode(3, "http://localhost:2/geocode/v3"),
+        ]);
+        let changes = discovery.sync().await.unwrap();
+        assert_eq!(changes.updated, ["geocode"]);
+        assert!(changes.removed.is_empty());
+        assert_eq!(
+            orchestrator.tool_versions("geocode").await,
+            [Version::new(2, 0, 0), Version::new(3, 0, 0)]
+        );
+        assert!(orchestrator.is_ready("geocode").await);
+
+        // Dropping versions for an unversioned listing
+        registry.set(vec![tool("geocode", "http://localhost:3/geocode")]);
+        discovery.sync().await.unwrap();
+        assert!(orchestrator.tool_versions("geocode").await.is_empty());
+        assert!(orchestrator.is_ready("geocode").await);
+        assert!(discovery.sync().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_poller_registers_new_tools() {
+        let orchestrator = Orchestrator::new(Arc::new(cybulous_consent::ConsentEngine::mock()), 10);
+        let registry = Arc::new(StaticRegistry::default());
+        let discovery = Arc::new(ToolDiscovery::new(orchestrator.clone(), registry.clone()));
+        let poller = discovery.start(Duration::from_millis(10));
+
+        registry.set(vec![tool("weather", "http://localhost:1/weather")]);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !orchestrator.is_ready("weather").await {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("poller never registered the new tool");
+        poller.stop().await;
+    }
+}
@@ -0,0 +1,147 @@
+//! Per-call output encryption
+//!
+//! A caller can ask for a tool's result to be encrypted to its X25519
+//! public key. The orchestrator seals the result with a fresh AES-256-GCM
+//! data key and wraps that key under a secret agreed between an ephemeral
+//! key pair and the recipient, so only the holder of the recipient's
+//! secret can read the result.
+
+use crate::{CybulousError, Result};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use hkdf::Hkdf;
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use x25519_dalek::{EphemeralSecret, PublicKey, StaticSecret};
+
+/// HKDF info label for deriving the key-wrapping key
+const KEY_WRAP_CONTEXT: &[u8] = b"cybulous-result-encryption-v1";
+
+/// A tool result sealed to a recipient's public key
+///
+/// All binary fields are base64url without padding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedResult {
+    /// Sender's ephemeral X25519 public key
+    pub ephemeral_public_key: String,
+    /// Data key wrapped under the agreed secret
+    pub wrapped_key: String,
+    /// Nonce used to wrap the data key
+    pub key_nonce: String,
+    /// Nonce used to seal the result
+    pub nonce: String,
+    /// Result JSON sealed under the data key
+    pub ciphertext: String,
+}
+
+impl EncryptedResult {
+    /// Seal `result` so only the holder of `recipient`'s secret can read it
+    pub fn seal(result: &serde_json::Value, recipient: &PublicKey) -> Result<Self> {
+        let data_key = Aes256Gcm::generate_key(OsRng);
+        let nonce = Aes256Gcm::generate_nonce(OsRng);
+        let ciphertext = Aes256Gcm::new(&data_key)
+            .encrypt(&nonce, serde_json::to_vec(result)?.as_slice())
+            .map_err(|_| failed("sealing result"))?;
+
+        let ephemeral = EphemeralSecret::random_from_rng(OsRng);
+        let ephemeral_public = PublicKey::from(&ephemeral);
+        let shared = ephemeral.diffie_hellman(recipient);
+        let wrapping_key = wrapping_key(shared.as_bytes(), &ephemeral_public, recipient);
+        let key_nonce = Aes256Gcm::generate_nonce(OsRng);
+        let wrapped_key = Aes256Gcm::new(&wrapping_key)
+            .encrypt(&key_nonce, data_key.as_slice())
+            .map_err(|_| failed("wrapping data key"))?;
+
+        Ok(Self {
+            ephemeral_public_key: URL_SAFE_NO_PAD.encode(ephemeral_public.as_bytes()),
+            wrapped_key: URL_SAFE_NO_PAD.encode(wrapped_key),
+            key_nonce: URL_SAFE_NO_PAD.encode(key_nonce),
+            nonce: URL_SAFE_NO_PAD.encode(nonce),
+            ciphertext: URL_SAFE_NO_PAD.encode(ciphertext),
+        })
+    }
+
+    /// Recover the result with the recipient's secret key
+    pub fn open(&self, secret: &StaticSecret) -> Result<serde_json::Value> {
+        let ephemeral_public: [u8; 32] = decode(&self.ephemeral_public_key)?
+            .try_into()
+            .map_err(|_| failed("ephemeral key malformed"))?;
+        let ephemeral_public = PublicKey::from(ephemeral_public);
+        let shared = secret.diffie_hellman(&ephemeral_public);
+        let wrapping_key = wrapping_key(
+            shared.as_bytes(),
+            &ephemeral_public,
+            &PublicKey::from(secret),
+        );
+
+        let data_key = Aes256Gcm::new(&wrapping_key)
+            .decrypt(
+                nonce(&decode(&self.key_nonce)?)?,
+                decode(&self.wrapped_key)?.as_slice(),
+            )
+            .map_err(|_| failed("unwrapping data key"))?;
+        if data_key.len() != 32 {
+            return Err(failed("data key malformed"));
+        }
+        let plaintext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&data_key))
+            .decrypt(
+                nonce(&decode(&self.nonce)?)?,
+                decode(&self.ciphertext)?.as_slice(),
+            )
+            .map_err(|_| failed("opening result"))?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+}
+
+/// HKDF-SHA256 of the agreed secret, salted with both public keys
+fn wrapping_key(shared: &[u8], ephemeral: &PublicKey, recipient: &PublicKey) -> Key<Aes256Gcm> {
+    let mut salt = [0u8; 64];
+    salt[..32].copy_from_slice(ephemeral.as_bytes());
+    salt[32..].copy_from_slice(recipient.as_bytes());
+    let mut key = Key::<Aes256Gcm>::default();
+    Hkdf::<Sha256>::new(Some(&salt), shared)
+        .expand(KEY_WRAP_CONTEXT, &mut key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    key
+}
+
+fn decode(field: &str) -> Result<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(field)
+        .map_err(|_| failed("field is not base64url"))
+}
+
+fn nonce(bytes: &[u8]) -> Result<&Nonce<<Aes256Gcm as AeadCore>::NonceSize>> {
+    if bytes.len() != 12 {
+        return Err(failed("nonce malformed"));
+    }
+    Ok(Nonce::from_slice(bytes))
+}
+
+fn failed(reason: &str) -> CybulousError {
+    CybulousError::EncryptionError(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sealed_result_opens_only_for_recipient() {
+        let secret = StaticSecret::random_from_rng(OsRng);
+        let result = serde_json::json!({"balance": 42, "account": "acct-1"});
+
+        let sealed = EncryptedResult::seal(&result, &PublicKey::from(&secret)).unwrap();
+        assert!(!sealed.ciphertext.contains("acct-1"));
+        assert_eq!(sealed.open(&secret).unwrap(), result);
+
+        let stranger = StaticSecret::random_from_rng(OsRng);
+        assert!(matches!(
+            sealed.open(&stranger),
+            Err(CybulousError::EncryptionError(_))
+        ));
+    }
+}
@@ -0,0 +1,28 @@
+//! Merged streaming from fanned-out calls
+//!
+//! A fan-out runs several paged calls concurrently and merges their
+//! responses into one stream. Each response is tagged with the index of
+//! the call (source) it came from, and every source's pages arrive in
+//! order. `MergeMode` decides how sources interleave.
+
+use crate::orchestration::ToolResponse;
+use serde::{Deserialize, Serialize};
+
+/// How responses from fanned-out sources are merged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MergeMode {
+    /// All of the first source's responses, then the second's, and so on;
+    /// later sources run ahead into a bounded buffer meanwhile
+    Ordered,
+    /// Responses from any source as soon as they are ready
+    Unordered,
+}
+
+/// Response from one source of a fan-out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanOutItem {
+    /// Index of the source call in the fan-out
+    pub source: usize,
+    /// Response from that source
+    pub response: ToolResponse,
+}
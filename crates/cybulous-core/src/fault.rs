@@ -0,0 +1,123 @@
+//! Seeded fault injection for resilience testing
+//!
+//! A `FaultInjector` probabilistically adds latency, errors, or timeouts to
+//! calls for configured tools. Decisions come from a seeded RNG so chaos
+//! tests of retry and circuit-breaker logic are reproducible.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+/// Per-tool fault probabilities
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FaultConfig {
+    /// Probability a call fails with an injected error
+    pub error_rate: f64,
+    /// Probability a call times out without reaching the executor
+    pub timeout_rate: f64,
+    /// Probability extra latency is added before dispatch
+    pub latency_rate: f64,
+    /// Latency added when injected
+    pub latency: Duration,
+}
+
+/// Terminal fault replacing the executor's result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultOutcome {
+    /// Fail the call
+    Error,
+    /// Time the call out
+    Timeout,
+}
+
+/// Faults chosen for a single call
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FaultDecision {
+    /// Latency to add before dispatch
+    pub latency: Option<Duration>,
+    /// Terminal fault, if any
+    pub outcome: Option<FaultOutcome>,
+}
+
+/// Seeded, per-tool fault injector
+#[derive(Debug)]
+pub struct FaultInjector {
+    rng: Mutex<StdRng>,
+    configs: RwLock<HashMap<String, FaultConfig>>,
+}
+
+impl FaultInjector {
+    /// Create an injector whose decisions are determined by `seed`
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+            configs: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Configure faults for a tool
+    pub fn configure(&self, tool_name: &str, config: FaultConfig) {
+        self.configs
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(tool_name.to_string(), config);
+    }
+
+    /// Stop injecting faults for a tool
+    pub fn clear(&self, tool_name: &str) {
+        self.configs
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(tool_name);
+    }
+
+    /// Decide which faults, if any, apply to the next call for `tool_name`
+    pub fn decide(&self, tool_name: &str) -> FaultDecision {
+        let configs = self.configs.read().unwrap_or_else(|e| e.into_inner());
+        let Some(config) = configs.get(tool_name) else {
+            return FaultDecision::default();
+        };
+
+        let mut rng = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+        let latency = (rng.gen::<f64>() < config.latency_rate).then_some(config.latency);
+        let roll = rng.gen::<f64>();
+        let outcome = if roll < config.error_rate {
+            Some(FaultOutcome::Error)
+        } else if roll < config.error_rate + config.timeout_rate {
+            Some(FaultOutcome::Timeout)
+        } else {
+            None
+        };
+
+        FaultDecision { latency, outcome }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_decisions_are_reproducible() {
+        let config = FaultConfig {
+            error_rate: 0.3,
+            timeout_rate: 0.2,
+            latency_rate: 0.5,
+            latency: Duration::from_millis(5),
+        };
+        let a = FaultInjector::new(42);
+        let b = FaultInjector::new(42);
+        a.configure("tool", config.clone());
+        b.configure("tool", config);
+
+        let run_a: Vec<_> = (0..50).map(|_| a.decide("tool")).collect();
+        let run_b: Vec<_> = (0..50).map(|_| b.decide("tool")).collect();
+        assert_eq!(run_a, run_b);
+        assert!(run_a.iter().any(|d| d.outcome.is_some()));
+        assert!(run_a.iter().any(|d| d.outcome.is_none()));
+        assert_eq!(a.decide("other"), FaultDecision::default());
+    }
+}
@@ -0,0 +1,62 @@
+//! Executor groups with shared, group-level policies
+//!
+//! Related tools can be placed in a group whose rate limit and circuit
+//! breaker are shared by every member, so the group is throttled and
+//! tripped as a whole.
+
+use crate::circuit::{CircuitBreaker, CircuitBreakerConfig};
+use crate::rate_limit::{RateLimit, RateLimiter};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Policies applied to all members of a group
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GroupPolicy {
+    /// Combined rate limit across all member tools
+    pub rate_limit: Option<RateLimit>,
+    /// Breaker tripped by failures of any member tool
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+}
+
+/// Named group of executors sharing a policy
+#[derive(Debug)]
+pub struct ExecutorGroup {
+    name: String,
+    policy: GroupPolicy,
+    limiter: Option<RateLimiter>,
+    breaker: Option<Arc<CircuitBreaker>>,
+}
+
+impl ExecutorGroup {
+    /// Create a group enforcing `policy`
+    pub fn new(name: impl Into<String>, policy: GroupPolicy) -> Self {
+        Self {
+            name: name.into(),
+            limiter: policy.rate_limit.map(RateLimiter::new),
+            breaker: policy
+                .circuit_breaker
+                .map(|config| Arc::new(CircuitBreaker::new(config))),
+            policy,
+        }
+    }
+
+    /// Group name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Group policy
+    pub fn policy(&self) -> &GroupPolicy {
+        &self.policy
+    }
+
+    /// Group circuit breaker, if configured
+    pub fn breaker(&self) -> Option<&Arc<CircuitBreaker>> {
+        self.breaker.as_ref()
+    }
+
+    /// Group rate limiter, if configured
+    pub fn limiter(&self) -> Option<&RateLimiter> {
+        self.limiter.as_ref()
+    }
+}
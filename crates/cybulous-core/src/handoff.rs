@@ -0,0 +1,93 @@
+//! Handing tools over between executors
+//!
+//! Replacing or unregistering an executor takes effect for new calls at
+//! once, but calls already dispatched to the old executor run to
+//! completion on it. Calls in flight are counted per executor instance so
+//! that its `on_deregister` teardown waits for them to drain, bounded by a
+//! drain timeout.
+
+use crate::orchestration::ToolExecutor;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Default time a displaced executor's teardown waits for calls in flight
+pub const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Identity of an executor instance, shared by every `Arc` to it
+fn instance(executor: &Arc<dyn ToolExecutor>) -> usize {
+    Arc::as_ptr(executor).cast::<()>() as usize
+}
+
+/// Calls in flight by executor instance
+#[derive(Debug, Default)]
+pub(crate) struct InFlightCalls {
+    /// Dropping a count's sender once it reaches zero wakes drains
+    counts: Mutex<HashMap<usize, watch::Sender<usize>>>,
+}
+
+impl InFlightCalls {
+    /// Count a call against `executor` until the guard drops
+    pub(crate) fn enter(self: &Arc<Self>, executor: &Arc<dyn ToolExecutor>) -> InFlightGuard {
+        let instance = instance(executor);
+        self.counts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(instance)
+            .or_insert_with(|| watch::channel(0).0)
+            .send_modify(|count| *count += 1);
+        InFlightGuard {
+            calls: self.clone(),
+            instance,
+        }
+    }
+
+    /// Calls in flight across all executors
+    pub(crate) fn total(&self) -> usize {
+        self.counts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .map(|count| *count.borrow())
+            .sum()
+    }
+
+    /// Wait up to `timeout` for `executor` to have no calls in flight,
+    /// returning whether it drained
+    pub(crate) async fn drain(&self, executor: &Arc<dyn ToolExecutor>, timeout: Duration) -> bool {
+        let count = self
+            .counts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&instance(executor))
+            .map(watch::Sender::subscribe);
+        let Some(mut count) = count else {
+            return true;
+        };
+        // A closed channel means the last call finished
+        let idle = async move {
+            let _ = count.wait_for(|count| *count == 0).await;
+        };
+        tokio::time::timeout(timeout, idle).await.is_ok()
+    }
+}
+
+/// A call in flight against one executor
+#[derive(Debug)]
+pub(crate) struct InFlightGuard {
+    calls: Arc<InFlightCalls>,
+    instance: usize,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let mut counts = self.calls.counts.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(count) = counts.get(&self.instance) {
+            count.send_modify(|count| *count -= 1);
+            if *count.borrow() == 0 {
+                counts.remove(&self.instance);
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! System health rollup for `/healthz`-style endpoints
+
+use crate::circuit::CircuitState;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// Number of recent outcomes kept per tool for error-rate calculation
+pub const OUTCOME_WINDOW: usize = 100;
+
+/// Error rate above which a tool degrades overall health
+pub const DEGRADED_ERROR_RATE: f64 = 0.5;
+
+/// Overall health status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum HealthStatus {
+    /// All subsystems nominal
+    Healthy,
+    /// Serving, but at least one subsystem is impaired
+    Degraded,
+    /// A critical dependency is unavailable
+    Unhealthy,
+}
+
+/// Aggregated health of the orchestrator and its dependencies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemHealth {
+    /// Rolled-up status
+    pub status: HealthStatus,
+    /// Whether the consent engine answered its health probe
+    pub consent_reachable: bool,
+    /// Readiness per registered tool
+    pub executors: HashMap<String, bool>,
+    /// Calls waiting for admission
+    pub queue_depth: usize,
+    /// Calls currently executing
+    pub inflight: usize,
+    /// Circuit state per breaker (tool or `group:<name>`)
+    pub circuits: HashMap<String, CircuitState>,
+    /// Recent error rate per tool, in `[0, 1]`
+    pub error_rates: HashMap<String, f64>,
+}
+
+impl SystemHealth {
+    /// Derive the overall status from subsystem readings
+    pub fn rollup(&mut self) {
+        self.status = if !self.consent_reachable {
+            HealthStatus::Unhealthy
+        } else if self.executors.values().any(|ready| !ready)
+            || self.circuits.values().any(|s| *s != CircuitState::Closed)
+            || self.error_rates.values().any(|r| *r > DEGRADED_ERROR_RATE)
+        {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Healthy
+        };
+    }
+}
+
+/// Rolling window of recent call outcomes for a tool
+#[derive(Debug, Default)]
+pub struct OutcomeWindow {
+    outcomes: VecDeque<bool>,
+}
+
+impl OutcomeWindow {
+    /// Record one outcome, evicting the oldest beyond [`OUTCOME_WINDOW`]
+    pub fn record(&mut self, success: bool) {
+        if self.outcomes.len() >= OUTCOME_WINDOW {
+            self.outcomes.pop_front();
+        }
+        self.outcomes.push_back(success);
+    }
+
+    /// Fraction of failed outcomes in the window
+    pub fn error_rate(&self) -> f64 {
+        if self.outcomes.is_empty() {
+            return 0.0;
+        }
+        let failures = self.outcomes.iter().filter(|ok| !**ok).count();
+        failures as f64 / self.outcomes.len() as f64
+    }
+}
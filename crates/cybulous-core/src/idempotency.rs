@@ -0,0 +1,174 @@
+//! Replay of retried submissions
+//!
+//! A call carrying an idempotency key executes at most once per user and
+//! key: resubmissions get the original response instead of running a
+//! side-effecting tool again, and resubmissions arriving while the first
+//! is still running wait for its response. Reusing a key for a different
+//! tool or parameters while it is held is rejected. Only successful
+//! responses are stored: any other outcome releases the key so a retry
+//! executes again.
+
+use crate::orchestration::{ExecutionStatus, ToolCall, ToolResponse};
+use crate::Result;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// Default time a response is kept for replay
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 3600);
+
+/// User and key a response is stored under
+type Scope = (String, String);
+
+/// What a key was first used for
+#[derive(Debug, Clone, PartialEq)]
+struct Fingerprint {
+    tool_name: String,
+    parameters: serde_json::Value,
+}
+
+impl Fingerprint {
+    fn of(call: &ToolCall) -> Self {
+        Self {
+            tool_name: call.tool_name.clone(),
+            parameters: call.parameters.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Entry {
+    /// Dropping the sender wakes calls waiting on the key
+    InFlight {
+        fingerprint: Fingerprint,
+        done: watch::Sender<()>,
+    },
+    Done {
+        fingerprint: Fingerprint,
+        response: ToolResponse,
+        stored_at: Instant,
+    },
+}
+
+impl Entry {
+    fn fingerprint(&self) -> &Fingerprint {
+        match self {
+            Self::InFlight { fingerprint, .. } | Self::Done { fingerprint, .. } => fingerprint,
+        }
+    }
+}
+
+/// Outcome of claiming a key
+#[derive(Debug)]
+pub(crate) enum Claim {
+    /// The key is unused; execute the call and complete the guard
+    Acquired(IdempotencyGuard),
+    /// The key already has a response
+    Replay(ToolResponse),
+    /// Another call holds the key; claim again once this resolves
+    Pending(watch::Receiver<()>),
+    /// The key was used for a different call
+    Conflict,
+}
+
+/// Responses stored by idempotency key
+#[derive(Debug)]
+pub(crate) struct IdempotencyStore {
+    ttl: Duration,
+    entries: Mutex<HashMap<Scope, Entry>>,
+}
+
+impl Default for IdempotencyStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_IDEMPOTENCY_TTL)
+    }
+}
+
+impl IdempotencyStore {
+    /// Keep responses for replay for `ttl`
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Claim `key` for `call`
+    pub(crate) fn claim(self: &Arc<Self>, call: &ToolCall, key: &str) -> Claim {
+        let scope = (call.user_id.clone(), key.to_string());
+        let fingerprint = Fingerprint::of(call);
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = entries.get(&scope) {
+            let expired =
+                matches!(entry, Entry::Done { stored_at, .. } if stored_at.elapsed() > self.ttl);
+            if !expired {
+                if *entry.fingerprint() != fingerprint {
+                    return Claim::Conflict;
+                }
+                return match entry {
+                    Entry::InFlight { done, .. } => Claim::Pending(done.subscribe()),
+                    Entry::Done { response, .. } => Claim::Replay(response.clone()),
+                };
+            }
+        }
+
+        let (done, _) = watch::channel(());
+        entries.insert(scope.clone(), Entry::InFlight { fingerprint, done });
+        Claim::Acquired(IdempotencyGuard {
+            store: self.clone(),
+            scope: Some(scope),
+        })
+    }
+
+    fn release(&self, scope: &Scope) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(scope);
+    }
+}
+
+/// Exclusive hold on a key while its call executes
+///
+/// Dropping the guard without completing it releases the key.
+#[derive(Debug)]
+pub(crate) struct IdempotencyGuard {
+    store: Arc<IdempotencyStore>,
+    scope: Option<Scope>,
+}
+
+impl IdempotencyGuard {
+    /// Store the call's response for replay if it succeeded, otherwise
+    /// release the key
+    pub(crate) fn complete(mut self, result: &Result<ToolResponse>) {
+        let Some(scope) = self.scope.take() else {
+            return;
+        };
+        let response = match result {
+            Ok(response) if response.status == ExecutionStatus::Success => response,
+            _ => return self.store.release(&scope),
+        };
+
+        let mut entries = self.store.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let ttl = self.store.ttl;
+        entries.retain(
+            |_, entry| !matches!(entry, Entry::Done { stored_at, .. } if stored_at.elapsed() > ttl),
+        );
+        if let Some(entry) = entries.get_mut(&scope) {
+            *entry = Entry::Done {
+                fingerprint: entry.fingerprint().clone(),
+                response: response.clone(),
+                stored_at: Instant::now(),
+            };
+        }
+    }
+}
+
+impl Drop for IdempotencyGuard {
+    fn drop(&mut self) {
+        if let Some(scope) = self.scope.take() {
+            self.store.release(&scope);
+        }
+    }
+}
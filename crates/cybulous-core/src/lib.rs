@@ -12,17 +12,113 @@
 )]
 
 pub mod agent;
+pub mod alerting;
 pub mod artifact;
+pub mod audit;
+pub mod batch;
+pub mod budget;
+pub mod cache;
+pub mod cancellation;
+pub mod capability;
+pub mod category;
+pub mod circuit;
+pub mod codec;
+pub mod degraded;
+pub mod diff;
+pub mod discovery;
+pub mod encryption;
+pub mod fan_out;
+#[cfg(any(test, feature = "fault-injection"))]
+pub mod fault;
+pub mod group;
+pub mod handoff;
+pub mod health;
+pub mod idempotency;
+#[cfg(any(test, feature = "load-testing"))]
+pub mod load;
+pub mod manifest;
+pub mod metrics;
+pub mod middleware;
+pub mod obligation;
 pub mod orchestration;
+#[cfg(feature = "otlp")]
+pub mod otlp;
+pub mod pagination;
 pub mod platform;
+pub mod postcondition;
+pub mod priority;
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+pub mod propagation;
+pub mod rate_limit;
+pub mod redaction;
+pub mod retention;
+pub mod retry;
+pub mod routing;
+pub mod saga;
+pub mod sampling;
+pub mod schema;
+pub mod signing;
+pub mod sla;
 pub mod state;
+pub mod streaming;
+pub mod termination;
+pub mod transform;
 pub mod types;
+pub mod usage;
+pub mod versioning;
+pub mod warm_pool;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod workflow;
 
 pub use agent::{Agent, AgentCapability, AgentPool};
+pub use alerting::{ErrorAlert, ErrorAlerter, ErrorClass};
 pub use artifact::{Artifact, ArtifactRegistry};
+pub use audit::{AuditLog, AuditRecord, CircuitAuditEvent};
+pub use batch::BatchResponse;
+pub use budget::BudgetLedger;
+pub use cache::{CachePredicate, CacheStore, MemoryCacheStore, PersistedEntry, ResultCache};
+pub use cancellation::CancellationToken;
+pub use capability::{CapabilityClaims, CapabilityToken};
+pub use category::{DrainMode, ToolCategory};
+pub use circuit::{CircuitBreaker, CircuitBreakerConfig, CircuitMetrics, CircuitState};
+pub use codec::{ContextCodec, JsonCodec, ProtobufCodec};
+pub use degraded::DegradedPolicy;
+pub use diff::{FieldChange, ResultDiff};
+pub use discovery::{
+    DiscoveryPoller, DiscoverySync, HttpToolRegistry, RemoteExecutor, RemoteTool, ToolDiscovery,
+    ToolRegistry,
+};
+pub use encryption::EncryptedResult;
+pub use fan_out::{FanOutItem, MergeMode};
+pub use group::{ExecutorGroup, GroupPolicy};
+pub use health::{HealthStatus, SystemHealth};
+pub use manifest::{Manifest, ToolManifest, ToolPolicy};
+pub use metrics::{TenantMetrics, TenantMetricsRegistry};
+pub use middleware::OrchestratorMiddleware;
+pub use obligation::AccessNotice;
 pub use orchestration::{Orchestrator, ToolCall, ToolResponse};
 pub use platform::{PlatformInstance, PlatformType};
+pub use postcondition::Postcondition;
+pub use priority::QueueingBehavior;
+pub use propagation::TraceContext;
+pub use rate_limit::{RateLimit, RateLimiter};
+pub use redaction::ResultScopeFilter;
+pub use retry::RetryPolicy;
+pub use routing::{CapabilitySelection, LocalityRoute, RoutingRule};
+pub use saga::{Saga, SagaAction, SagaOutcome, SagaStep};
+pub use sampling::{OutcomeSampler, TraceSampler};
+pub use schema::{FieldViolation, SchemaViolations};
+pub use signing::{ServiceKeyring, SigningMode};
+pub use sla::{SlaBreach, SlaStatus, SlaTarget};
 pub use state::{StateManager, UserSession};
+pub use streaming::{StreamEvent, ToolChunk};
+pub use termination::{EscalationLadder, StopSignal};
+pub use transform::{DefaultParameters, RequestTransform};
+pub use usage::{CostCenterUsage, UsageLedger, UsageReport};
+pub use warm_pool::{InstanceFactory, WarmPool, WarmPoolConfig};
+pub use workflow::{FailureMode, Workflow, WorkflowNode, WorkflowRun, WorkflowValidation};
 
 use thiserror::Error;
 
@@ -60,6 +156,54 @@ pub enum CybulousError {
     /// Serialization errors
     #[error("serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
+
+    /// Caller not authorized to invoke the tool
+    #[error("access denied: {0}")]
+    AccessDenied(String),
+
+    /// Call rejected by a rate limit
+    #[error("rate limited: {0}")]
+    RateLimited(String),
+
+    /// Call rejected by an open circuit breaker
+    #[error("circuit open: {0}")]
+    CircuitOpen(String),
+
+    /// Wire codec errors
+    #[error("codec error: {0}")]
+    CodecError(String),
+
+    /// Call rejected because the caller's budget is exhausted
+    #[error("budget exceeded: {0}")]
+    BudgetExceeded(String),
+
+    /// Call rejected while the orchestrator is draining for maintenance
+    #[error("draining: {0}")]
+    Draining(String),
+
+    /// Call parameters failed validation
+    #[error("invalid parameters: {0}")]
+    InvalidParameters(String),
+
+    /// Call parameters violate the tool's input schema
+    #[error("schema violation: {0}")]
+    SchemaViolation(SchemaViolations),
+
+    /// Result encryption or decryption errors
+    #[error("encryption error: {0}")]
+    EncryptionError(String),
+
+    /// Call pinned to a retired tool version
+    #[error("version retired: {0}")]
+    VersionRetired(String),
+
+    /// Sandboxed tool module could not be loaded or run
+    #[error("sandbox error: {0}")]
+    SandboxError(String),
+
+    /// Tool manifest could not be parsed or applied
+    #[error("invalid manifest: {0}")]
+    ManifestInvalid(String),
 }
 
 /// Result type alias for Cybulous operations
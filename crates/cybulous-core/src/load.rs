@@ -0,0 +1,270 @@
+//! Synthetic load generation for load tests
+//!
+//! A `LoadGenerator` fires calls at an orchestrator following a
+//! `LoadPattern` and reports latency and throughput. It is meant for CI
+//! load tests against mock executors, not for production traffic.
+
+use crate::orchestration::{ExecutionStatus, Orchestrator, ToolCall};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+
+/// Shape of the offered load over time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoadPattern {
+    /// Fixed rate for the whole run
+    Constant {
+        /// Calls per second
+        rate: f64,
+        /// Length of the run
+        duration: Duration,
+    },
+    /// Rate rising linearly from `from` to `to`
+    Ramp {
+        /// Initial calls per second
+        from: f64,
+        /// Final calls per second
+        to: f64,
+        /// Length of the run
+        duration: Duration,
+    },
+    /// Base rate with a burst in the middle of the run
+    Spike {
+        /// Calls per second outside the spike
+        base: f64,
+        /// Calls per second during the spike
+        peak: f64,
+        /// Offset at which the spike starts
+        spike_at: Duration,
+        /// Length of the spike
+        spike_for: Duration,
+        /// Length of the run
+        duration: Duration,
+    },
+}
+
+impl LoadPattern {
+    /// Length of the run
+    pub fn duration(&self) -> Duration {
+        match *self {
+            LoadPattern::Constant { duration, .. }
+            | LoadPattern::Ramp { duration, .. }
+            | LoadPattern::Spike { duration, .. } => duration,
+        }
+    }
+
+    /// Target calls per second `elapsed` into the run
+    pub fn rate_at(&self, elapsed: Duration) -> f64 {
+        match *self {
+            LoadPattern::Constant { rate, .. } => rate,
+            LoadPattern::Ramp { from, to, duration } => {
+                let progress = elapsed.as_secs_f64() / duration.as_secs_f64().max(f64::EPSILON);
+                from + (to - from) * progress.min(1.0)
+            }
+            LoadPattern::Spike {
+                base,
+                peak,
+                spike_at,
+                spike_for,
+                ..
+            } => {
+                if elapsed >= spike_at && elapsed < spike_at + spike_for {
+                    peak
+                } else {
+                    base
+                }
+            }
+        }
+    }
+}
+
+/// Results of a load run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadStats {
+    /// Calls issued
+    pub sent: usize,
+    /// Calls that returned `Success`
+    pub succeeded: usize,
+    /// Calls that errored or returned any other status
+    pub failed: usize,
+    /// Median call latency
+    pub p50_latency: Duration,
+    /// 95th percentile call latency
+    pub p95_latency: Duration,
+    /// Slowest call
+    pub max_latency: Duration,
+    /// Wall-clock length of the run, including draining in-flight calls
+    pub elapsed: Duration,
+    /// Completed calls per second of wall-clock time
+    pub throughput: f64,
+}
+
+/// Fires synthetic calls at an orchestrator
+pub struct LoadGenerator {
+    orchestrator: Orchestrator,
+    pattern: LoadPattern,
+    make_call: Arc<dyn Fn() -> ToolCall + Send + Sync>,
+}
+
+impl std::fmt::Debug for LoadGenerator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoadGenerator")
+            .field("pattern", &self.pattern)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LoadGenerator {
+    /// Generate `pattern` load of calls built by `make_call`
+    pub fn new(
+        orchestrator: Orchestrator,
+        pattern: LoadPattern,
+        make_call: impl Fn() -> ToolCall + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            orchestrator,
+            pattern,
+            make_call: Arc::new(make_call),
+        }
+    }
+
+    /// Run the pattern to completion and collect stats
+    pub async fn run(&self) -> LoadStats {
+        let start = Instant::now();
+        let mut inflight = JoinSet::new();
+
+        loop {
+            let elapsed = start.elapsed();
+            if elapsed >= self.pattern.duration() {
+                break;
+            }
+            let rate = self.pattern.rate_at(elapsed);
+            if rate <= 0.0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                continue;
+            }
+
+            let orchestrator = self.orchestrator.clone();
+            let call = (self.make_call)();
+            inflight.spawn(async move {
+                let issued = Instant::now();
+                let success = matches!(
+                    orchestrator.execute_tool(call).await,
+                    Ok(response) if response.status == ExecutionStatus::Success
+                );
+                (issued.elapsed(), success)
+            });
+            tokio::time::sleep(Duration::from_secs_f64(1.0 / rate)).await;
+        }
+
+        let mut latencies = Vec::new();
+        let mut stats = LoadStats::default();
+        while let Some(outcome) = inflight.join_next().await {
+            stats.sent += 1;
+            match outcome {
+                Ok((latency, success)) => {
+                    latencies.push(latency);
+                    if success {
+                        stats.succeeded += 1;
+                    } else {
+                        stats.failed += 1;
+                    }
+                }
+                Err(_) => stats.failed += 1,
+            }
+        }
+
+        latencies.sort_unstable();
+        let percentile = |p: usize| match latencies.len() {
+            0 => Duration::ZERO,
+            n => latencies[(n * p).div_ceil(100).saturating_sub(1)],
+        };
+        stats.p50_latency = percentile(50);
+        stats.p95_latency = percentile(95);
+        stats.max_latency = latencies.last().copied().unwrap_or_default();
+        stats.elapsed = start.elapsed();
+        stats.throughput = latencies.len() as f64 / stats.elapsed.as_secs_f64();
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestration::{ExecutionContext, ToolExecutor, ToolResponse};
+    use crate::Result;
+    use async_trait::async_trait;
+    use uuid::Uuid;
+
+    struct EchoExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for EchoExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            tokio::time::sleep(Duration::from_millis(2)).await;
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(call.parameters.clone()),
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn test_constant_rate_load_collects_stats() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 16);
+        orchestrator
+            .register_executor(Arc::new(EchoExecutor))
+            .await
+            .unwrap();
+
+        let generator = LoadGenerator::new(
+            orchestrator,
+            LoadPattern::Constant {
+                rate: 200.0,
+                duration: Duration::from_millis(200),
+            },
+            || ToolCall {
+                id: Uuid::new_v4(),
+                tool_name: "echo".to_string(),
+                parameters: serde_json::json!({}),
+                user_id: "load-user".to_string(),
+                context: ExecutionContext::new(
+                    Uuid::new_v4(),
+                    cybulous_crypto::hash_data(&format!(
+                        "mock-tx-hash:21:{}",
+                        cybulous_crypto::hash_data("load-user")
+                    )),
+                ),
+                timeout_ms: 1000,
+                queue_timeout_ms: None,
+                execution_timeout_ms: None,
+                tool_version: None,
+                required_capability: None,
+                idempotency_key: None,
+                encrypt_to: None,
+            },
+        );
+        let stats = generator.run().await;
+
+        assert!(stats.sent >= 10, "sent {}", stats.sent);
+        assert_eq!(stats.succeeded, stats.sent);
+        assert_eq!(stats.failed, 0);
+        assert!(stats.p50_latency >= Duration::from_millis(2));
+        assert!(stats.p95_latency >= stats.p50_latency);
+        assert!(stats.max_latency >= stats.p95_latency);
+        assert!(stats.throughput > 0.0);
+    }
+}
@@ -0,0 +1,74 @@
+//! Declarative tool wiring
+//!
+//! A `Manifest` describes how registered tools are configured: the
+//! capabilities each must support, consent scopes and timeouts applied to
+//! its calls, its executor group and SLA, and metadata routing rules.
+//! Operators keep manifests in version control and apply them with
+//! [`crate::Orchestrator::apply_manifest`]. Executors themselves are still
+//! registered in code; the manifest only configures them.
+
+use crate::group::GroupPolicy;
+use crate::routing::RoutingRule;
+use crate::sla::SlaTarget;
+use crate::{CybulousError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Desired configuration of an orchestrator's tools
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Executor groups to define, by name
+    #[serde(default)]
+    pub groups: BTreeMap<String, GroupPolicy>,
+    /// Per-tool configuration
+    #[serde(default)]
+    pub tools: Vec<ToolManifest>,
+    /// Metadata routing rules, in precedence order
+    #[serde(default)]
+    pub routes: Vec<RoutingRule>,
+}
+
+impl Manifest {
+    /// Parse a TOML manifest
+    pub fn from_toml(source: &str) -> Result<Self> {
+        toml::from_str(source).map_err(|e| CybulousError::ManifestInvalid(e.to_string()))
+    }
+}
+
+/// Configuration of one registered tool
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolManifest {
+    /// Registered tool being configured
+    pub name: String,
+    /// Capabilities the tool's executor must support
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    /// Consent scopes callers must have granted
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Timeout for calls that specify none, overriding the executor default
+    pub timeout_ms: Option<u64>,
+    /// Executor group the tool joins
+    pub group: Option<String>,
+    /// SLA the tool is evaluated against
+    pub sla: Option<SlaTarget>,
+}
+
+impl ToolManifest {
+    pub(crate) fn policy(&self) -> ToolPolicy {
+        ToolPolicy {
+            required_scopes: self.scopes.clone(),
+            timeout: self.timeout_ms.map(Duration::from_millis),
+        }
+    }
+}
+
+/// Call policy applied to a tool from its manifest
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolPolicy {
+    /// Consent scopes callers must have granted
+    pub required_scopes: Vec<String>,
+    /// Timeout for calls that specify none
+    pub timeout: Option<Duration>,
+}
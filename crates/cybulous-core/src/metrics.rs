@@ -0,0 +1,81 @@
+//! Tenant-labelled call metrics
+//!
+//! In multi-tenant deployments, calls carry their tenant in the context
+//! metadata and the orchestrator counts them per tenant. To keep label
+//! cardinality bounded, only the first `max_labels` distinct tenants get
+//! their own label; later tenants are folded into [`OVERFLOW_TENANT`].
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Context metadata key naming the calling tenant
+pub const TENANT_ID_KEY: &str = "tenant_id";
+
+/// Label shared by tenants beyond the label cap
+pub const OVERFLOW_TENANT: &str = "other";
+
+/// Default cap on distinct tenant labels
+pub const DEFAULT_MAX_TENANT_LABELS: usize = 100;
+
+/// Counters for one tenant label
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantMetrics {
+    /// Calls completed
+    pub calls: u64,
+    /// Calls that did not succeed
+    pub failures: u64,
+    /// Sum of call durations in milliseconds
+    pub total_duration_ms: u64,
+}
+
+/// Per-tenant counters with a bounded label set
+#[derive(Debug)]
+pub struct TenantMetricsRegistry {
+    max_labels: usize,
+    metrics: Mutex<HashMap<String, TenantMetrics>>,
+}
+
+impl Default for TenantMetricsRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_TENANT_LABELS)
+    }
+}
+
+impl TenantMetricsRegistry {
+    /// Give at most `max_labels` tenants their own label
+    pub fn new(max_labels: usize) -> Self {
+        Self {
+            max_labels,
+            metrics: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count a completed call for `tenant`
+    pub fn record(&self, tenant: &str, success: bool, duration_ms: u64) {
+        let mut metrics = self.metrics.lock().unwrap_or_else(|e| e.into_inner());
+        let labelled = metrics.keys().filter(|t| *t != OVERFLOW_TENANT).count();
+        let label = if tenant != OVERFLOW_TENANT
+            && (metrics.contains_key(tenant) || labelled < self.max_labels)
+        {
+            tenant
+        } else {
+            OVERFLOW_TENANT
+        };
+
+        let counters = metrics.entry(label.to_string()).or_default();
+        counters.calls += 1;
+        if !success {
+            counters.failures += 1;
+        }
+        counters.total_duration_ms += duration_ms;
+    }
+
+    /// Counters by tenant label
+    pub fn snapshot(&self) -> HashMap<String, TenantMetrics> {
+        self.metrics
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
@@ -0,0 +1,30 @@
+//! Interceptors around tool execution
+//!
+//! Middleware sees every call before it is dispatched and every successful
+//! dispatch's response afterwards, so cross-cutting concerns such as
+//! logging, quota checks, or parameter redaction can be plugged in without
+//! touching executors. `pre_execute` hooks run in registration order and
+//! may rewrite or reject the call; `post_execute` hooks run in reverse
+//! order, so the first middleware registered wraps all the others.
+
+use crate::orchestration::{ToolCall, ToolResponse};
+use crate::Result;
+use async_trait::async_trait;
+
+/// Hooks run around each call the orchestrator executes
+#[async_trait]
+pub trait OrchestratorMiddleware: Send + Sync {
+    /// Inspect or rewrite `call` before dispatch
+    ///
+    /// Returning an error rejects the call with that error; later
+    /// middleware and the executor are skipped.
+    async fn pre_execute(&self, _call: &mut ToolCall) -> Result<()> {
+        Ok(())
+    }
+
+    /// Observe the response to `call`
+    ///
+    /// Runs for every call that produced a response, whatever its status,
+    /// after result redaction and before encryption.
+    async fn post_execute(&self, _call: &ToolCall, _response: &ToolResponse) {}
+}
@@ -0,0 +1,26 @@
+//! Enforcement of consent obligations
+//!
+//! Consent may carry obligations the orchestrator is responsible for.
+//! Whenever a tool runs on a user's behalf, each notify-on-access
+//! obligation on their consent produces an `AccessNotice` for delivery to
+//! the named recipient.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Notice that a tool accessed a user's data under a notify-on-access
+/// obligation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessNotice {
+    /// Party the obligation requires be notified
+    pub recipient: String,
+    /// User whose consent carries the obligation
+    pub user_id: String,
+    /// Tool that ran
+    pub tool_name: String,
+    /// Call that ran the tool
+    pub call_id: Uuid,
+    /// When the access completed
+    pub accessed_at: DateTime<Utc>,
+}
@@ -2,14 +2,89 @@
 //!
 //! Implements deterministic execution with consent-gated access control.
 
+use crate::alerting::{self, ErrorAlert, ErrorAlerter, ErrorClass};
+use crate::audit::{AuditLog, AuditRecord};
+use crate::batch::BatchResponse;
+use crate::budget::BudgetLedger;
+use crate::cache::{CachePredicate, CacheStore, ResultCache};
+use crate::cancellation::CancellationToken;
+use crate::capability::{CapabilityToken, CAPABILITY_TOKEN_KEY};
+use crate::category::{DrainMode, ToolCategory};
+use crate::circuit::{
+    CircuitBreaker, CircuitBreakerConfig, CircuitMetrics, CircuitPermit, CircuitState,
+};
+use crate::codec::{ContextCodec, JsonCodec};
+use crate::degraded::{DegradedPolicy, LastGoodResults};
+use crate::diff::ResultDiff;
+use crate::encryption::EncryptedResult;
+use crate::fan_out::{FanOutItem, MergeMode};
+#[cfg(any(test, feature = "fault-injection"))]
+use crate::fault::{FaultInjector, FaultOutcome};
+use crate::group::{ExecutorGroup, GroupPolicy};
+use crate::handoff::{InFlightCalls, InFlightGuard, DEFAULT_DRAIN_TIMEOUT};
+use crate::health::{HealthStatus, OutcomeWindow, SystemHealth};
+use crate::idempotency::{Claim, IdempotencyStore};
+use crate::manifest::{Manifest, ToolPolicy};
+use crate::metrics::{TenantMetrics, TenantMetricsRegistry, TENANT_ID_KEY};
+use crate::middleware::OrchestratorMiddleware;
+use crate::obligation::AccessNotice;
+use crate::pagination;
+use crate::postcondition::{self, Postcondition};
+use crate::priority::{self, AdmissionPermit, AdmissionQueue, QueueingBehavior};
+#[cfg(feature = "prometheus")]
+use crate::prometheus::PrometheusMetrics;
+use crate::propagation::TraceContext;
+use crate::redaction::ResultScopeFilter;
+use crate::retry::RetryPolicy;
+use crate::routing::{CapabilitySelection, LocalityRoute, RoutingRule};
+use crate::saga::{Saga, SagaAction, SagaOutcome};
+use crate::sampling::{OutcomeSampler, TraceSampler, TRACE_SAMPLED_KEY};
+use crate::schema::{self, SchemaCache};
+use crate::signing::{ServiceKeyring, SigningMode};
+use crate::sla::{SlaBreach, SlaStatus, SlaTarget, SlaTracker};
+use crate::streaming::{StreamEvent, ToolChunk};
+use crate::termination::{EscalationLadder, StopSignal};
+use crate::transform::{RequestTransform, TransformChain};
+use crate::usage::{UsageLedger, UsageReport};
+use crate::versioning::VersionTable;
+use crate::warm_pool::WarmPool;
+use crate::workflow::{self, FailureMode, Workflow, WorkflowRun, WorkflowValidation};
 use crate::{CybulousError, Result};
 use async_trait::async_trait;
+use ed25519_dalek::VerifyingKey;
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use semver::{Version, VersionReq};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::{error, info, warn};
+use tokio::sync::{broadcast, watch, RwLock};
+use tracing::{debug, error, info, info_span, warn, Instrument, Span};
 use uuid::Uuid;
+use x25519_dalek::PublicKey;
+
+/// Context metadata key marking orchestrator-generated synthetic calls
+pub const SYNTHETIC_CALL_KEY: &str = "synthetic";
+
+/// Context metadata key carrying an emergency consent override token
+pub const EMERGENCY_OVERRIDE_KEY: &str = "emergency_override";
+
+/// Context metadata key carrying an absolute RFC 3339 deadline for the call
+pub const DEADLINE_KEY: &str = "deadline";
+
+/// Buffered SLA breach events per subscriber
+const SLA_BREACH_CHANNEL_CAPACITY: usize = 64;
+
+/// Capacity of the error alert broadcast channel
+const ERROR_ALERT_CHANNEL_CAPACITY: usize = 64;
+
+/// Capacity of the obligation access notice broadcast channel
+const ACCESS_NOTICE_CHANNEL_CAPACITY: usize = 256;
+
+/// Execution timeout applied to calls with `timeout_ms == 0` when the
+/// executor supplies no default of its own
+pub const DEFAULT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
 
 /// Tool invocation request
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +101,27 @@ pub struct ToolCall {
     pub context: ExecutionContext,
     /// Timeout in milliseconds
     pub timeout_ms: u64,
+    /// Maximum time to wait for admission, unbounded if unset
+    #[serde(default)]
+    pub queue_timeout_ms: Option<u64>,
+    /// Maximum execution time once admitted, overriding `timeout_ms`
+    #[serde(default)]
+    pub execution_timeout_ms: Option<u64>,
+    /// Client key to encrypt the result to, returned in plaintext if unset
+    #[serde(default)]
+    pub encrypt_to: Option<PublicKey>,
+    /// Versions of the tool the call accepts, the newest live version if
+    /// unset
+    #[serde(default)]
+    pub tool_version: Option<VersionReq>,
+    /// Capability the executor must advertise; when set, the orchestrator
+    /// picks the tool and overwrites `tool_name`
+    #[serde(default)]
+    pub required_capability: Option<String>,
+    /// Client key identifying retries of the same submission; calls
+    /// sharing a user and key execute once
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
 }
 
 /// Tool execution context
@@ -39,6 +135,140 @@ pub struct ExecutionContext {
     pub biophysical_hash: Option<String>,
     /// Additional metadata
     pub metadata: HashMap<String, String>,
+    /// First call in the causality chain this context belongs to
+    #[serde(default)]
+    pub root_call_id: Option<Uuid>,
+    /// Call that directly caused this one
+    #[serde(default)]
+    pub parent_call_id: Option<Uuid>,
+    /// Region the call originates from, checked against geofenced consent
+    #[serde(default)]
+    pub region: Option<String>,
+}
+
+impl ExecutionContext {
+    /// Create a root context with no metadata or lineage
+    pub fn new(session_id: Uuid, consent_proof: impl Into<String>) -> Self {
+        Self {
+            session_id,
+            consent_proof: consent_proof.into(),
+            biophysical_hash: None,
+            metadata: HashMap::new(),
+            root_call_id: None,
+            parent_call_id: None,
+            region: None,
+        }
+    }
+
+    /// Set the region the call originates from
+    pub fn with_region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    /// Derive a child context for a call caused by `parent`
+    ///
+    /// Session, consent, biophysical hash, and metadata carry over; the
+    /// lineage points at `parent` and inherits its root.
+    pub fn derive(&self, parent_call_id: Uuid) -> Self {
+        Self {
+            root_call_id: Some(self.root_call_id.unwrap_or(parent_call_id)),
+            parent_call_id: Some(parent_call_id),
+            ..self.clone()
+        }
+    }
+}
+
+impl ToolCall {
+    /// Create a call of `tool_name` for `user_id`
+    ///
+    /// Timeouts are left at zero, deferring to the tool's default, and
+    /// version pins, capability routing, idempotency and result
+    /// encryption are unset.
+    pub fn new(
+        tool_name: impl Into<String>,
+        parameters: serde_json::Value,
+        user_id: impl Into<String>,
+        context: ExecutionContext,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            tool_name: tool_name.into(),
+            parameters,
+            user_id: user_id.into(),
+            context,
+            timeout_ms: 0,
+            queue_timeout_ms: None,
+            execution_timeout_ms: None,
+            encrypt_to: None,
+            tool_version: None,
+            required_capability: None,
+            idempotency_key: None,
+        }
+    }
+
+    /// Build a call caused by this one, propagating lineage
+    pub fn derive_child(
+        &self,
+        tool_name: impl Into<String>,
+        parameters: serde_json::Value,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            tool_name: tool_name.into(),
+            parameters,
+            user_id: self.user_id.clone(),
+            context: self.context.derive(self.id),
+            timeout_ms: self.timeout_ms,
+            queue_timeout_ms: self.queue_timeout_ms,
+            execution_timeout_ms: self.execution_timeout_ms,
+            // Child results flow back to the parent executor, not the client
+            encrypt_to: None,
+            // A pin names a version of the parent's tool, not the child's
+            tool_version: None,
+            required_capability: None,
+            idempotency_key: None,
+        }
+    }
+
+    /// Set the call's admission priority; higher is more urgent
+    ///
+    /// Carried in the context metadata under
+    /// [`PRIORITY_KEY`](crate::priority::PRIORITY_KEY) so derived calls
+    /// inherit it.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.context
+            .metadata
+            .insert(priority::PRIORITY_KEY.to_string(), priority.to_string());
+        self
+    }
+
+    /// Route the call to any executor advertising `capability` instead of
+    /// a named tool
+    pub fn with_capability(mut self, capability: impl Into<String>) -> Self {
+        self.required_capability = Some(capability.into());
+        self
+    }
+
+    /// Mark the call as a submission that retries must not repeat
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// Pin the call to tool versions matching `req`
+    ///
+    /// Requirements follow Cargo semantics: `"1.2"` accepts any compatible
+    /// 1.x release from 1.2.0, `"=1.2.0"` exactly that version.
+    pub fn with_version(mut self, req: VersionReq) -> Self {
+        self.tool_version = Some(req);
+        self
+    }
+
+    /// Execution timeout in milliseconds once the call is admitted
+    pub fn execution_timeout(&self) -> u64 {
+        self.execution_timeout_ms.unwrap_or(self.timeout_ms)
+    }
 }
 
 /// Tool execution response
@@ -54,6 +284,9 @@ pub struct ToolResponse {
     pub error: Option<String>,
     /// Execution duration in milliseconds
     pub duration_ms: u64,
+    /// Served from a last-good result after the executor failed
+    #[serde(default)]
+    pub stale: bool,
 }
 
 /// Execution status
@@ -65,8 +298,27 @@ pub enum ExecutionStatus {
     Failed,
     /// Execution timed out
     Timeout,
+    /// The call was not admitted before its queue timeout
+    QueueTimeout,
+    /// The call found no free slot under fail-fast queueing
+    Rejected,
     /// Consent not granted
     ConsentDenied,
+    /// Execution was cancelled before completing
+    Cancelled(CancellationReason),
+}
+
+/// Why an in-flight call was cancelled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CancellationReason {
+    /// The client withdrew the request
+    ClientCancelled,
+    /// The orchestrator is shutting down
+    Shutdown,
+    /// The call's absolute deadline passed
+    Deadline,
+    /// A hedged duplicate of the call completed first
+    SupersededByHedge,
 }
 
 /// Tool executor trait
@@ -75,19 +327,297 @@ pub trait ToolExecutor: Send + Sync {
     /// Execute a tool call
     async fn execute(&self, call: &ToolCall) -> Result<ToolResponse>;
 
+    /// Execute a tool call, observing `token` for cancellation
+    ///
+    /// The orchestrator calls this rather than [`ToolExecutor::execute`].
+    /// Executors that hand work to background tasks should pass the token
+    /// along so that work stops when the call is cancelled; the default
+    /// ignores it.
+    async fn execute_cancellable(
+        &self,
+        call: &ToolCall,
+        _token: CancellationToken,
+    ) -> Result<ToolResponse> {
+        self.execute(call).await
+    }
+
     /// Get tool name
     fn name(&self) -> &str;
 
     /// Check if tool supports given capability
     fn supports_capability(&self, capability: &str) -> bool;
+
+    /// Invariants a successful response must satisfy
+    fn postconditions(&self, _call: &ToolCall, _response: &ToolResponse) -> Vec<Postcondition> {
+        Vec::new()
+    }
+
+    /// Category of effect this tool has
+    ///
+    /// Defaults to `Write` so uncategorized tools are treated as mutating.
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Write
+    }
+
+    /// Called when the executor is registered, e.g. to open connections
+    async fn on_register(&self) {}
+
+    /// Called when the executor is removed or replaced, e.g. to close
+    /// connections
+    async fn on_deregister(&self) {}
+
+    /// Budget units a call consumes
+    fn cost(&self, _call: &ToolCall) -> u64 {
+        0
+    }
+
+    /// Top-level parameters every call must supply
+    fn required_parameters(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// JSON Schema call parameters must satisfy; unchecked if `None`
+    fn input_schema(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Timeout for calls that leave `timeout_ms` at zero, overriding the
+    /// orchestrator's default
+    fn default_timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
+
+    /// Version of the tool this executor implements
+    ///
+    /// Versioned executors registered under the same name live side by
+    /// side; unversioned ones replace whatever is registered.
+    fn version(&self) -> Option<Version> {
+        None
+    }
+
+    /// Whether calls need a proof bound to a recent liveness check
+    ///
+    /// For high-assurance tools where a long-lived proof must not be
+    /// replayable.
+    fn requires_liveness(&self) -> bool {
+        false
+    }
+
+    /// How long successful results may be served from cache; uncached if
+    /// `None`
+    fn cache_ttl(&self) -> Option<std::time::Duration> {
+        None
+    }
+
+    /// Tags naming upstream state a cached result depends on
+    fn cache_tags(&self, _call: &ToolCall) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Whether failures may be answered with a recent last-good result;
+    /// failures are returned as-is if `None`
+    fn degraded_policy(&self) -> Option<DegradedPolicy> {
+        None
+    }
+
+    /// Signals to escalate through if a call keeps running after it was
+    /// abandoned
+    ///
+    /// Empty by default: in-process executors stop when their future is
+    /// dropped.
+    fn escalation_ladder(&self) -> EscalationLadder {
+        EscalationLadder::new()
+    }
+
+    /// Deliver a stop signal for an abandoned call
+    async fn stop(&self, _call: &ToolCall, _signal: StopSignal) {}
+
+    /// Whether an abandoned call has stopped running
+    async fn has_stopped(&self, _call: &ToolCall) -> bool {
+        true
+    }
+
+    /// Minimum provider assurance level the caller's consent must carry
+    ///
+    /// 0, the default, accepts consent from any provider.
+    fn min_assurance(&self) -> u8 {
+        0
+    }
+
+    /// Classify an error this executor returned, for alerting
+    fn classify_error(&self, error: &CybulousError) -> ErrorClass {
+        alerting::classify(error)
+    }
+
+    /// How to retry failed attempts, overriding the orchestrator's default
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        None
+    }
+
+    /// Execute a call, producing its output incrementally
+    ///
+    /// The default runs [`ToolExecutor::execute`] and yields its result as
+    /// a single chunk; long-running tools override this to stream output
+    /// as it is produced.
+    async fn execute_stream(
+        &self,
+        call: &ToolCall,
+    ) -> Result<BoxStream<'static, Result<serde_json::Value>>> {
+        let response = self.execute(call).await?;
+        let chunk = match response.status {
+            ExecutionStatus::Success => Ok(response.result.unwrap_or_default()),
+            status => Err(CybulousError::OrchestrationFailed(
+                response
+                    .error
+                    .unwrap_or_else(|| format!("finished with {:?}", status)),
+            )),
+        };
+        Ok(stream::once(async move { chunk }).boxed())
+    }
+
+    /// Whether an attempt that ended in `response` may be retried
+    ///
+    /// Lets a tool mark failures it knows to be permanent; consulted only
+    /// when the retry policy's predicate already allows a retry.
+    fn is_retryable(&self, _call: &ToolCall, _response: &ToolResponse) -> bool {
+        true
+    }
+}
+
+/// One unit of a shared gauge, given back when dropped
+///
+/// Held across awaits so a caller dropping the future cannot leave the
+/// gauge counting a call that is gone.
+#[derive(Debug)]
+struct GaugeGuard(Arc<AtomicUsize>);
+
+impl GaugeGuard {
+    fn new(gauge: &Arc<AtomicUsize>) -> Self {
+        gauge.fetch_add(1, Ordering::SeqCst);
+        Self(gauge.clone())
+    }
+}
+
+impl Drop for GaugeGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Senders that cancel calls in flight, by call id
+///
+/// Concurrent calls may share an id; cancelling it reaches all of them.
+type CancellationSenders =
+    std::sync::Mutex<HashMap<Uuid, Vec<watch::Sender<Option<CancellationReason>>>>>;
+
+/// A call's entry in the cancellation map, removed when dropped
+///
+/// Only this guard's own sender is removed, so other calls sharing the id
+/// stay cancellable.
+#[derive(Debug)]
+struct CancellationGuard {
+    senders: Arc<CancellationSenders>,
+    call_id: Uuid,
+    sender: watch::Sender<Option<CancellationReason>>,
+}
+
+impl CancellationGuard {
+    /// Make `call_id` cancellable until the guard drops
+    fn register(
+        senders: &Arc<CancellationSenders>,
+        call_id: Uuid,
+    ) -> (Self, watch::Receiver<Option<CancellationReason>>) {
+        let (sender, receiver) = watch::channel(None);
+        senders
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(call_id)
+            .or_default()
+            .push(sender.clone());
+        let guard = Self {
+            senders: senders.clone(),
+            call_id,
+            sender,
+        };
+        (guard, receiver)
+    }
+}
+
+impl Drop for CancellationGuard {
+    fn drop(&mut self) {
+        let mut senders = self.senders.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(registered) = senders.get_mut(&self.call_id) {
+            registered.retain(|sender| !sender.same_channel(&self.sender));
+            if registered.is_empty() {
+                senders.remove(&self.call_id);
+            }
+        }
+    }
 }
 
 /// Orchestrator for managing tool executions
 #[derive(Clone)]
 pub struct Orchestrator {
     executors: Arc<RwLock<HashMap<String, Arc<dyn ToolExecutor>>>>,
+    versions: Arc<RwLock<HashMap<String, VersionTable>>>,
+    capability_selection: CapabilitySelection,
+    capability_turns: Arc<std::sync::Mutex<HashMap<String, usize>>>,
+    idempotency: Arc<IdempotencyStore>,
+    in_flight: Arc<InFlightCalls>,
+    drain_timeout: std::time::Duration,
+    schemas: Arc<SchemaCache>,
+    readiness: Arc<RwLock<HashMap<String, bool>>>,
+    audit_log: Arc<AuditLog>,
+    groups: Arc<RwLock<HashMap<String, Arc<ExecutorGroup>>>>,
+    group_membership: Arc<RwLock<HashMap<String, String>>>,
+    outcomes: Arc<RwLock<HashMap<String, OutcomeWindow>>>,
+    tenant_metrics: Arc<TenantMetricsRegistry>,
+    usage: Arc<UsageLedger>,
+    slas: Arc<RwLock<HashMap<String, SlaTracker>>>,
+    sla_breaches: broadcast::Sender<SlaBreach>,
+    alerter: Arc<ErrorAlerter>,
+    error_alerts: broadcast::Sender<ErrorAlert>,
+    access_notices: broadcast::Sender<AccessNotice>,
+    cancellations: Arc<CancellationSenders>,
+    routing_rules: Arc<RwLock<Vec<RoutingRule>>>,
+    locality_routes: Arc<RwLock<Vec<LocalityRoute>>>,
+    tool_policies: Arc<RwLock<HashMap<String, ToolPolicy>>>,
+    result_filters: Arc<RwLock<HashMap<String, ResultScopeFilter>>>,
+    transforms: Arc<RwLock<TransformChain>>,
+    middleware: Arc<RwLock<Vec<Arc<dyn OrchestratorMiddleware>>>>,
+    budgets: Arc<BudgetLedger>,
+    result_cache: Arc<ResultCache>,
+    last_good: Arc<LastGoodResults>,
+    denied_categories: Arc<RwLock<HashSet<ToolCategory>>>,
+    drain_mode: Arc<RwLock<DrainMode>>,
+    queue_depth: Arc<AtomicUsize>,
     consent_engine: Arc<cybulous_consent::ConsentEngine>,
     max_concurrent: usize,
+    admission: Arc<AdmissionQueue>,
+    queueing: QueueingBehavior,
+    retry_policy: Option<RetryPolicy>,
+    tool_breakers: Arc<RwLock<HashMap<String, Arc<CircuitBreaker>>>>,
+    default_tool_breaker: Option<CircuitBreakerConfig>,
+    max_pages: usize,
+    default_timeout: std::time::Duration,
+    sampler: TraceSampler,
+    outcome_sampler: Option<OutcomeSampler>,
+    context_codec: Arc<dyn ContextCodec>,
+    #[cfg(any(test, feature = "fault-injection"))]
+    fault_injector: Option<Arc<FaultInjector>>,
+    #[cfg(feature = "prometheus")]
+    prometheus: Arc<PrometheusMetrics>,
+    capability_issuer: Option<VerifyingKey>,
+    service_keyring: Option<Arc<ServiceKeyring>>,
+}
+
+impl fmt::Debug for Orchestrator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Orchestrator")
+            .field("max_concurrent", &self.max_concurrent)
+            .field("context_codec", &self.context_codec.content_type())
+            .finish_non_exhaustive()
+    }
 }
 
 impl Orchestrator {
@@ -98,140 +628,6459 @@ impl Orchestrator {
     ) -> Self {
         Self {
             executors: Arc::new(RwLock::new(HashMap::new())),
+            versions: Arc::new(RwLock::new(HashMap::new())),
+            capability_selection: CapabilitySelection::default(),
+            capability_turns: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            idempotency: Arc::new(IdempotencyStore::default()),
+            in_flight: Arc::new(InFlightCalls::default()),
+            drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+            schemas: Arc::new(SchemaCache::default()),
+            readiness: Arc::new(RwLock::new(HashMap::new())),
+            audit_log: Arc::new(AuditLog::default()),
+            groups: Arc::new(RwLock::new(HashMap::new())),
+            group_membership: Arc::new(RwLock::new(HashMap::new())),
+            outcomes: Arc::new(RwLock::new(HashMap::new())),
+            tenant_metrics: Arc::new(TenantMetricsRegistry::default()),
+            usage: Arc::new(UsageLedger::default()),
+            slas: Arc::new(RwLock::new(HashMap::new())),
+            sla_breaches: broadcast::channel(SLA_BREACH_CHANNEL_CAPACITY).0,
+            alerter: Arc::new(ErrorAlerter::default()),
+            error_alerts: broadcast::channel(ERROR_ALERT_CHANNEL_CAPACITY).0,
+            access_notices: broadcast::channel(ACCESS_NOTICE_CHANNEL_CAPACITY).0,
+            cancellations: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            routing_rules: Arc::new(RwLock::new(Vec::new())),
+            locality_routes: Arc::new(RwLock::new(Vec::new())),
+            tool_policies: Arc::new(RwLock::new(HashMap::new())),
+            result_filters: Arc::new(RwLock::new(HashMap::new())),
+            transforms: Arc::new(RwLock::new(Vec::new())),
+            middleware: Arc::new(RwLock::new(Vec::new())),
+            budgets: Arc::new(BudgetLedger::default()),
+            result_cache: Arc::new(ResultCache::default()),
+            last_good: Arc::new(LastGoodResults::default()),
+            denied_categories: Arc::new(RwLock::new(HashSet::new())),
+            drain_mode: Arc::new(RwLock::new(DrainMode::Off)),
+            queue_depth: Arc::new(AtomicUsize::new(0)),
             consent_engine,
             max_concurrent,
+            admission: Arc::new(AdmissionQueue::new(max_concurrent, None)),
+            queueing: QueueingBehavior::default(),
+            retry_policy: None,
+            tool_breakers: Arc::new(RwLock::new(HashMap::new())),
+            default_tool_breaker: None,
+            max_pages: pagination::DEFAULT_MAX_PAGES,
+            default_timeout: DEFAULT_TIMEOUT,
+            sampler: TraceSampler::default(),
+            outcome_sampler: None,
+            context_codec: Arc::new(JsonCodec),
+            #[cfg(any(test, feature = "fault-injection"))]
+            fault_injector: None,
+            #[cfg(feature = "prometheus")]
+            prometheus: Arc::new(PrometheusMetrics::new()),
+            capability_issuer: None,
+            service_keyring: None,
         }
     }
 
+    /// Require callers to present capability tokens signed by `issuer`
+    pub fn with_capability_issuer(mut self, issuer: VerifyingKey) -> Self {
+        self.capability_issuer = Some(issuer);
+        self
+    }
+
+    /// Verify service signatures on incoming calls against `keyring`
+    pub fn with_service_keyring(mut self, keyring: ServiceKeyring) -> Self {
+        self.service_keyring = Some(Arc::new(keyring));
+        self
+    }
+
+    /// Persist cacheable results to `store` and warm the cache from it
+    pub fn with_cache_store(mut self, store: Arc<dyn CacheStore>) -> Self {
+        self.result_cache = Arc::new(ResultCache::with_store(store));
+        self
+    }
+
+    /// Raise a queued call's priority by one level for each `interval` it
+    /// waits, so low-priority calls are not starved
+    pub fn with_priority_aging(mut self, interval: std::time::Duration) -> Self {
+        self.admission = Arc::new(AdmissionQueue::new(self.max_concurrent, Some(interval)));
+        self
+    }
+
+    /// Choose whether calls wait for a concurrency slot or are rejected
+    /// when all `max_concurrent` slots are taken
+    pub fn with_queueing(mut self, queueing: QueueingBehavior) -> Self {
+        self.queueing = queueing;
+        self
+    }
+
+    /// Keep responses to calls with an idempotency key for replay for
+    /// `ttl`
+    pub fn with_idempotency_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.idempotency = Arc::new(IdempotencyStore::new(ttl));
+        self
+    }
+
+    /// Wait up to `timeout` for calls in flight on a replaced or
+    /// unregistered executor before tearing it down
+    pub fn with_drain_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.drain_timeout = timeout;
+        self
+    }
+
+    /// Choose among executors advertising a call's required capability
+    /// with `selection`
+    pub fn with_capability_selection(mut self, selection: CapabilitySelection) -> Self {
+        self.capability_selection = selection;
+        self
+    }
+
+    /// Retry failed attempts under `policy` for executors that declare no
+    /// policy of their own
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Give every tool its own circuit breaker configured by `config`
+    ///
+    /// Tools with a breaker set by [`Orchestrator::set_tool_circuit_breaker`]
+    /// keep that one.
+    pub fn with_tool_circuit_breakers(mut self, config: CircuitBreakerConfig) -> Self {
+        self.default_tool_breaker = Some(config);
+        self
+    }
+
+    /// Follow at most `max_pages` pages in [`Orchestrator::execute_tool_paged`]
+    pub fn with_max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+
+    /// Timeout for zero-timeout calls to executors without their own default
+    pub fn with_default_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.default_timeout = timeout;
+        self
+    }
+
+    /// Record detailed spans for `rate` of call chains; failures are
+    /// always traced
+    pub fn with_trace_sample_rate(mut self, rate: f64) -> Self {
+        self.sampler = TraceSampler::new(rate);
+        self
+    }
+
+    /// Decide full telemetry once each call's outcome is known, overriding
+    /// the head sampling decision in the audit log
+    pub fn with_outcome_sampler(mut self, sampler: OutcomeSampler) -> Self {
+        self.outcome_sampler = Some(sampler);
+        self
+    }
+
+    /// Alert on executor errors using `alerter`'s thresholds
+    pub fn with_error_alerter(mut self, alerter: ErrorAlerter) -> Self {
+        self.alerter = Arc::new(alerter);
+        self
+    }
+
+    /// Give at most `max_labels` tenants their own metrics label
+    pub fn with_max_tenant_labels(mut self, max_labels: usize) -> Self {
+        self.tenant_metrics = Arc::new(TenantMetricsRegistry::new(max_labels));
+        self
+    }
+
+    /// Inject faults into tool calls for resilience testing
+    #[cfg(any(test, feature = "fault-injection"))]
+    pub fn with_fault_injector(mut self, injector: Arc<FaultInjector>) -> Self {
+        self.fault_injector = Some(injector);
+        self
+    }
+
+    /// Use a custom wire codec for execution context
+    pub fn with_context_codec(mut self, codec: Arc<dyn ContextCodec>) -> Self {
+        self.context_codec = codec;
+        self
+    }
+
+    /// Codec transports use to (de)serialize execution context
+    pub fn context_codec(&self) -> Arc<dyn ContextCodec> {
+        self.context_codec.clone()
+    }
+
     /// Register a tool executor
+    ///
+    /// An executor already registered under the same name, or the same
+    /// version for versioned executors, is replaced as by
+    /// [`Orchestrator::replace_executor`]. Fails if the executor publishes
+    /// an input schema that is not valid JSON Schema.
     pub async fn register_executor(&self, executor: Arc<dyn ToolExecutor>) -> Result<()> {
-        let name = executor.name().to_string();
-        let mut executors = self.executors.write().await;
+        self.register(executor, true).await
+    }
 
-        if executors.contains_key(&name) {
+    /// Register `executor`, marking its tool ready or not before any call
+    /// can resolve to it
+    async fn register(&self, executor: Arc<dyn ToolExecutor>, ready: bool) -> Result<()> {
+        let name = executor.name().to_string();
+        if let Some(replaced) = self.install(executor, ready).await? {
             warn!("Overwriting existing executor: {}", name);
+            self.release(replaced).await;
         }
-
-        executors.insert(name.clone(), executor);
-        info!("Registered executor: {}", name);
         Ok(())
     }
 
-    /// Execute a tool call with consent verification
-    pub async fn execute_tool(&self, call: ToolCall) -> Result<ToolResponse> {
-        let start = std::time::Instant::now();
-
-        // Verify consent before execution
-        self.verify_consent(&call).await?;
+    /// Swap in `executor` for the one registered under its name, or its
+    /// version for versioned executors
+    ///
+    /// The swap is atomic: new calls route to `executor` while calls
+    /// already running on the old executor finish there. Returns once they
+    /// have, or the drain timeout passes, and the old executor's
+    /// `on_deregister` hook has run. Fails if nothing is registered to
+    /// replace.
+    pub async fn replace_executor(&self, executor: Arc<dyn ToolExecutor>) -> Result<()> {
+        let name = executor.name().to_string();
+        let version = executor.version();
+        let registered = match (&version, self.versions.read().await.get(&name)) {
+            (Some(version), Some(table)) => table.versions().any(|v| v == version),
+            (None, None) => self.executors.read().await.contains_key(&name),
+            _ => false,
+        };
+        if !registered {
+            let target = match version {
+                Some(version) => format!("{} {}", name, version),
+                None => name,
+            };
+            return Err(CybulousError::OrchestrationFailed(format!(
+                "No executor registered for {} to replace",
+                target
+            )));
+        }
 
-        // Find executor
-        let executors = self.executors.read().await;
-        let executor = executors.get(&call.tool_name).ok_or_else(|| {
-            CybulousError::OrchestrationFailed(format!("Unknown tool: {}", call.tool_name))
-        })?;
+        if let Some(replaced) = self.install(executor, true).await? {
+            self.release(replaced).await;
+        }
+        info!("Replaced executor: {}", name);
+        Ok(())
+    }
 
-        // Execute with timeout
-        let timeout = tokio::time::Duration::from_millis(call.timeout_ms);
-        let execution = executor.execute(&call);
+    /// Make `executor` serve its tool with the given readiness, returning
+    /// the executor it displaces
+    async fn install(
+        &self,
+        executor: Arc<dyn ToolExecutor>,
+        ready: bool,
+    ) -> Result<Option<Arc<dyn ToolExecutor>>> {
+        let name = executor.name().to_string();
+        if let Some(input_schema) = executor.input_schema() {
+            schema::compile(&name, &input_schema)?;
+        }
+        executor.on_register().await;
+        // Set before the executor is visible so it is never seen as ready
+        // ahead of its warmup
+        self.readiness.write().await.insert(name.clone(), ready);
 
-        match tokio::time::timeout(timeout, execution).await {
-            Ok(Ok(mut response)) => {
-                response.duration_ms = start.elapsed().as_millis() as u64;
-                info!(
-                    "Tool {} executed successfully in {}ms",
-                    call.tool_name, response.duration_ms
-                );
-                Ok(response)
-            }
-            Ok(Err(e)) => {
-                error!("Tool {} execution failed: {}", call.tool_name, e);
-                Ok(ToolResponse {
-                    call_id: call.id,
-                    status: ExecutionStatus::Failed,
-                    result: None,
-                    error: Some(e.to_string()),
-                    duration_ms: start.elapsed().as_millis() as u64,
-                })
+        // Hold both maps so no call resolves against a half-made swap
+        let mut versions = self.versions.write().await;
+        let mut executors = self.executors.write().await;
+        let replaced = match executor.version() {
+            Some(version) => {
+                // Other versions stay live; the latest registration serves
+                // lookups by name alone
+                let table = versions.entry(name.clone()).or_default();
+                let replaced = table.insert(version.clone(), executor.clone());
+                let previous = executors.insert(name.clone(), executor);
+                info!("Registered {} version {}", name, version);
+                replaced.or(previous.filter(|p| p.version().is_none()))
             }
-            Err(_) => {
-                warn!("Tool {} execution timed out", call.tool_name);
-                Ok(ToolResponse {
-                    call_id: call.id,
-                    status: ExecutionStatus::Timeout,
-                    result: None,
-                    error: Some("Execution timeout".to_string()),
-                    duration_ms: call.timeout_ms,
-                })
+            None => {
+                versions.remove(&name);
+                executors.insert(name.clone(), executor)
             }
-        }
+        };
+        drop(executors);
+        drop(versions);
+
+        info!("Registered executor: {}", name);
+        Ok(replaced)
     }
 
-    /// Verify user consent for tool execution
-    async fn verify_consent(&self, call: &ToolCall) -> Result<()> {
-        match self
-            .consent_engine
-            .verify_consent(&call.user_id, &call.context.consent_proof)
-            .await
-        {
-            Ok(true) => Ok(()),
-            Ok(false) => Err(CybulousError::ConsentError(
-                "Consent verification failed".to_string(),
-            )),
-            Err(e) => Err(CybulousError::ConsentError(format!(
-                "Consent check error: {}",
-                e
-            ))),
+    /// Tear down a displaced executor once its calls in flight finish
+    async fn release(&self, executor: Arc<dyn ToolExecutor>) {
+        if !self.in_flight.drain(&executor, self.drain_timeout).await {
+            warn!(
+                "Executor {} still has calls in flight after {:?}; tearing it down anyway",
+                executor.name(),
+                self.drain_timeout
+            );
         }
+        executor.on_deregister().await;
     }
 
-    /// List all registered tools
-    pub async fn list_tools(&self) -> Vec<String> {
-        let executors = self.executors.read().await;
-        executors.keys().cloned().collect()
+    /// Register a warm pool as its tool's executor, pre-warming it first
+    ///
+    /// Calls routed to the pool's tool name borrow a pooled instance.
+    pub async fn register_warm_pool(&self, pool: Arc<WarmPool>) -> Result<()> {
+        pool.fill().await?;
+        self.register_executor(pool).await
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Remove a tool executor, and every version of it, returning whether
+    /// it was registered
+    ///
+    /// New calls to the tool fail at once while calls already running
+    /// finish. Returns once they have, or the drain timeout passes, and the
+    /// removed executors' `on_deregister` hooks have run.
+    pub async fn unregister_executor(&self, tool_name: &str) -> bool {
+        let (executor, table) = {
+            let mut versions = self.versions.write().await;
+            let mut executors = self.executors.write().await;
+            let Some(executor) = executors.remove(tool_name) else {
+                return false;
+            };
+            (executor, versions.remove(tool_name))
+        };
+        self.readiness.write().await.remove(tool_name);
+        let removed: Vec<_> = match table {
+            Some(table) => table.executors().cloned().collect(),
+            None => vec![executor],
+        };
+        futures::future::join_all(removed.into_iter().map(|e| self.release(e))).await;
+        info!("Unregistered executor: {}", tool_name);
+        true
+    }
 
-    struct MockExecutor {
-        name: String,
+    /// Remove a tool executor, and every version of it, returning whether
+    /// it was registered
+    #[deprecated(note = "use `unregister_executor`")]
+    pub async fn deregister_executor(&self, tool_name: &str) -> bool {
+        self.unregister_executor(tool_name).await
     }
 
-    #[async_trait]
-    impl ToolExecutor for MockExecutor {
-        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
-            Ok(ToolResponse {
-                call_id: call.id,
-                status: ExecutionStatus::Success,
-                result: Some(serde_json::json!({"executed": true})),
-                error: None,
-                duration_ms: 10,
-            })
+    /// Remove one version of a tool, returning whether it was registered
+    ///
+    /// Other versions stay live, the newest of them taking over lookups by
+    /// name alone; removing the last version removes the tool. Returns
+    /// once calls already running on the removed version finish, or the
+    /// drain timeout passes, and its `on_deregister` hook has run.
+    pub async fn unregister_version(&self, tool_name: &str, version: &Version) -> bool {
+        let (removed, emptied) = {
+            let mut versions = self.versions.write().await;
+            let mut executors = self.executors.write().await;
+            let Some(table) = versions.get_mut(tool_name) else {
+                return false;
+            };
+            let Some(removed) = table.remove(version) else {
+                return false;
+            };
+            match table.latest().cloned() {
+                Some(latest) => {
+                    if executors
+                        .get(tool_name)
+                        .is_some_and(|e| Arc::ptr_eq(e, &removed))
+                    {
+                        executors.insert(tool_name.to_string(), latest);
+                    }
+                    (removed, false)
+                }
+                None => {
+                    versions.remove(tool_name);
+                    executors.remove(tool_name);
+                    (removed, true)
+                }
+            }
+        };
+        if emptied {
+            self.readiness.write().await.remove(tool_name);
         }
+        self.release(removed).await;
+        info!("Unregistered {} version {}", tool_name, version);
+        true
+    }
 
-        fn name(&self) -> &str {
-            &self.name
+    /// Retire a version of a tool, returning whether it is registered
+    ///
+    /// Calls no longer resolve to a retired version; calls that only a
+    /// retired version satisfies fail with
+    /// [`CybulousError::VersionRetired`].
+    pub async fn retire_version(&self, tool_name: &str, version: &Version) -> bool {
+        let retired = self
+            .versions
+            .write()
+            .await
+            .get_mut(tool_name)
+            .is_some_and(|table| table.retire(version));
+        if retired {
+            info!("Retired {} version {}", tool_name, version);
         }
+        retired
+    }
 
-        fn supports_capability(&self, _capability: &str) -> bool {
-            true
-        }
+    /// Registered versions of a tool, oldest first
+    pub async fn tool_versions(&self, tool_name: &str) -> Vec<Version> {
+        self.versions
+            .read()
+            .await
+            .get(tool_name)
+            .map(|table| table.versions().cloned().collect())
+            .unwrap_or_default()
     }
 
-    #[tokio::test]
-    async fn test_executor_registration() {
-        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
-        let orchestrator = Orchestrator::new(consent_engine, 10);
+    /// Register a tool executor and prime it with a synthetic warmup call
+    ///
+    /// The warmup bypasses consent and is flagged with [`SYNTHETIC_CALL_KEY`]
+    /// in its context metadata. The executor is marked ready only if the
+    /// warmup succeeds; the returned flag reports that readiness.
+    pub async fn register_executor_with_warmup(
+        &self,
+        executor: Arc<dyn ToolExecutor>,
+        mut warmup_call: ToolCall,
+    ) -> Result<bool> {
+        let name = executor.name().to_string();
+        self.register(executor.clone(), false).await?;
 
-        let executor = Arc::new(MockExecutor {
-            name: "test-tool".to_string(),
-        });
+        warmup_call.tool_name = name.clone();
+        warmup_call
+            .context
+            .metadata
+            .insert(SYNTHETIC_CALL_KEY.to_string(), "true".to_string());
 
-        orchestrator.register_executor(executor).await.unwrap();
+        let timeout = self.resolve_timeout(executor.as_ref(), &warmup_call).await;
+        let ready = match tokio::time::timeout(timeout, executor.execute(&warmup_call)).await {
+            Ok(Ok(response)) => response.status == ExecutionStatus::Success,
+            Ok(Err(e)) => {
+                warn!("Warmup for {} failed: {}", name, e);
+                false
+            }
+            Err(_) => {
+                warn!("Warmup for {} timed out", name);
+                false
+            }
+        };
 
-        let tools = orchestrator.list_tools().await;
-        assert!(tools.contains(&"test-tool".to_string()));
+        self.readiness.write().await.insert(name.clone(), ready);
+        info!("Executor {} warmup complete, ready: {}", name, ready);
+        Ok(ready)
+    }
+
+    /// Whether a registered tool is ready to serve calls
+    pub async fn is_ready(&self, tool_name: &str) -> bool {
+        self.readiness
+            .read()
+            .await
+            .get(tool_name)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Execute a tool call with consent verification
+    ///
+    /// A call with an idempotency key that already succeeded for its user
+    /// gets the original response, including its call id, without
+    /// executing again.
+    pub async fn execute_tool(&self, call: ToolCall) -> Result<ToolResponse> {
+        let Some(key) = call.idempotency_key.clone() else {
+            return self.execute_call(call).await;
+        };
+        let guard = loop {
+            match self.idempotency.claim(&call, &key) {
+                Claim::Acquired(guard) => break guard,
+                Claim::Replay(response) => {
+                    debug!("Replaying response for idempotency key {}", key);
+                    return Ok(response);
+                }
+                Claim::Pending(mut done) => {
+                    // Resolves when the first submission stores or releases
+                    let _ = done.changed().await;
+                }
+                Claim::Conflict => {
+                    return Err(CybulousError::InvalidParameters(format!(
+                        "idempotency key {} was used for a different call",
+                        key
+                    )))
+                }
+            }
+        };
+        let result = self.execute_call(call).await;
+        guard.complete(&result);
+        result
+    }
+
+    async fn execute_call(&self, mut call: ToolCall) -> Result<ToolResponse> {
+        let routed = self.route_capability(&mut call).await;
+
+        // Decide on tracing once and propagate it to executors
+        let sampled = self.sampler.sample(&call);
+        call.context
+            .metadata
+            .insert(TRACE_SAMPLED_KEY.to_string(), sampled.to_string());
+        let parent_trace = TraceContext::from_metadata(&call.context.metadata);
+        let span = if sampled {
+            Self::call_span(&call)
+        } else {
+            Span::none()
+        };
+
+        // Propagate the call's own span so remote executors join the trace
+        let exported = Self::exported_trace(&span, parent_trace);
+        let trace = exported.unwrap_or_else(|| match parent_trace {
+            Some(parent) => parent.child(sampled),
+            None => TraceContext::root(sampled),
+        });
+        trace.inject(&mut call.context.metadata);
+        span.record("trace_id", trace.trace_id_hex().as_str());
+        span.record("span_id", trace.span_id_hex().as_str());
+
+        let admitted = match routed {
+            Ok(()) => self.pre_execute(&mut call).await,
+            Err(e) => Err(e),
+        };
+        let result = match admitted {
+            Ok(()) => {
+                let (_cancellable, cancel_rx) =
+                    CancellationGuard::register(&self.cancellations, call.id);
+                self.dispatch(&call, cancel_rx).instrument(span).await
+            }
+            Err(e) => {
+                warn!("Tool {} rejected before dispatch: {}", call.tool_name, e);
+                Err(e)
+            }
+        };
+
+        // Degraded responses count against the tool's health and SLA
+        let status = match &result {
+            Ok(response) if response.stale => ExecutionStatus::Failed,
+            Ok(response) => response.status,
+            Err(CybulousError::ConsentError(_)) => ExecutionStatus::ConsentDenied,
+            Err(_) => ExecutionStatus::Failed,
+        };
+
+        // Errors are always traced, even when the call was not sampled
+        let errored = !matches!(
+            status,
+            ExecutionStatus::Success | ExecutionStatus::Cancelled(_)
+        );
+        let detailed = match &self.outcome_sampler {
+            Some(outcome_sampler) => outcome_sampler.records(&call, status),
+            None => sampled || errored,
+        };
+        if detailed != sampled {
+            call.context
+                .metadata
+                .insert(TRACE_SAMPLED_KEY.to_string(), detailed.to_string());
+        }
+        if detailed && !sampled && errored {
+            Self::call_span(&call)
+                .in_scope(|| warn!("Tool {} finished with {:?}", call.tool_name, status));
+        }
+        self.audit_log.record(&call, status).await;
+
+        let duration_ms = result.as_ref().map_or(0, |r| r.duration_ms);
+        let success = status == ExecutionStatus::Success;
+        #[cfg(feature = "prometheus")]
+        {
+            self.prometheus.record(&call.tool_name, status, duration_ms);
+            self.prometheus.set_queue_depth(self.queue_depth());
+        }
+        if let Some(tenant) = call.context.metadata.get(TENANT_ID_KEY) {
+            self.tenant_metrics.record(tenant, success, duration_ms);
+        }
+        self.usage
+            .record(&call, success, duration_ms, chrono::Utc::now());
+
+        if let Ok(response) = &result {
+            if !matches!(
+                status,
+                ExecutionStatus::ConsentDenied
+                    | ExecutionStatus::Cancelled(_)
+                    | ExecutionStatus::QueueTimeout
+                    | ExecutionStatus::Rejected
+            ) {
+                self.outcomes
+                    .write()
+                    .await
+                    .entry(call.tool_name.clone())
+                    .or_default()
+                    .record(success);
+                self.record_sla(&call.tool_name, response.duration_ms, success)
+                    .await;
+            }
+        }
+
+        if result.is_ok()
+            && !matches!(
+                status,
+                ExecutionStatus::ConsentDenied
+                    | ExecutionStatus::QueueTimeout
+                    | ExecutionStatus::Rejected
+            )
+        {
+            self.fulfil_obligations(&call).await;
+        }
+
+        let mut response = result?;
+        self.redact_result(&call, &mut response).await;
+        for middleware in self.middleware.read().await.iter().rev() {
+            middleware.post_execute(&call, &response).await;
+        }
+        if let (Some(recipient), Some(value)) = (&call.encrypt_to, &response.result) {
+            let sealed = EncryptedResult::seal(value, recipient)?;
+            response.result = Some(serde_json::to_value(sealed)?);
+        }
+        Ok(response)
+    }
+
+    /// Remove result fields requiring consent scopes the caller lacks
+    async fn redact_result(&self, call: &ToolCall, response: &mut ToolResponse) {
+        let Some(filter) = self
+            .result_filters
+            .read()
+            .await
+            .get(&call.tool_name)
+            .cloned()
+        else {
+            return;
+        };
+        let Some(result) = response.result.as_mut() else {
+            return;
+        };
+
+        let mut granted = HashSet::new();
+        for scope in filter.scopes() {
+            match self.consent_engine.has_scope(&call.user_id, scope).await {
+                Ok(true) => {
+                    granted.insert(scope.to_string());
+                }
+                Ok(false) => {}
+                // Fail closed: an unverifiable scope is treated as missing
+                Err(e) => warn!("Scope lookup for {} failed: {}", call.user_id, e),
+            }
+        }
+        let redacted = filter.redact(result, |scope| granted.contains(scope));
+        if !redacted.is_empty() {
+            info!(
+                "Redacted {} from {} result for {}",
+                redacted.join(", "),
+                call.tool_name,
+                call.user_id
+            );
+        }
+    }
+
+    /// Raise notices for the notify-on-access obligations on the caller's
+    /// consent
+    async fn fulfil_obligations(&self, call: &ToolCall) {
+        let obligations = match self.consent_engine.obligations_for(&call.user_id).await {
+            Ok(obligations) => obligations,
+            Err(e) => {
+                warn!("Obligation lookup for {} failed: {}", call.user_id, e);
+                return;
+            }
+        };
+        for obligation in obligations {
+            if let cybulous_consent::Obligation::NotifyOnAccess { recipient } = obligation {
+                let _ = self.access_notices.send(AccessNotice {
+                    recipient,
+                    user_id: call.user_id.clone(),
+                    tool_name: call.tool_name.clone(),
+                    call_id: call.id,
+                    accessed_at: chrono::Utc::now(),
+                });
+            }
+        }
+    }
+
+    /// Context of `span` in the exported trace, if spans are exported
+    #[cfg(feature = "otlp")]
+    fn exported_trace(span: &Span, parent: Option<TraceContext>) -> Option<TraceContext> {
+        crate::otlp::link_span(span, parent)
+    }
+
+    #[cfg(not(feature = "otlp"))]
+    fn exported_trace(_span: &Span, _parent: Option<TraceContext>) -> Option<TraceContext> {
+        None
+    }
+
+    fn call_span(call: &ToolCall) -> Span {
+        let span = info_span!(
+            "tool_call",
+            call_id = %call.id,
+            tool = %call.tool_name,
+            user = %call.user_id,
+            parent = ?call.context.parent_call_id,
+            trace_id = tracing::field::Empty,
+            span_id = tracing::field::Empty,
+        );
+        if let Some(trace) = TraceContext::from_metadata(&call.context.metadata) {
+            span.record("trace_id", trace.trace_id_hex().as_str());
+            span.record("span_id", trace.span_id_hex().as_str());
+        }
+        span
+    }
+
+    /// Execute a call and diff its result against a previous run's
+    ///
+    /// Meant for idempotent tools, where the diff shows what changed
+    /// upstream between runs.
+    pub async fn execute_and_diff(
+        &self,
+        call: ToolCall,
+        previous: &ToolResponse,
+    ) -> Result<(ToolResponse, ResultDiff)> {
+        let response = self.execute_tool(call).await?;
+        let diff = ResultDiff::between(previous.result.as_ref(), response.result.as_ref());
+        Ok((response, diff))
+    }
+
+    /// Execute a paginated tool call, following `next_cursor` across pages
+    ///
+    /// Yields one response per page and stops when a page has no cursor,
+    /// a page does not succeed, or the page limit is reached. Errors are
+    /// yielded as `Failed` responses.
+    pub fn execute_tool_paged(&self, call: ToolCall) -> impl Stream<Item = ToolResponse> + '_ {
+        stream::unfold((Some(call), 0), move |(next, pages)| async move {
+            let call = next.filter(|_| pages < self.max_pages)?;
+            let response = match self.execute_tool(call.clone()).await {
+                Ok(response) => response,
+                Err(e) => ToolResponse {
+                    call_id: call.id,
+                    status: ExecutionStatus::Failed,
+                    result: None,
+                    error: Some(e.to_string()),
+                    duration_ms: 0,
+                    stale: false,
+                },
+            };
+            let next = pagination::next_page(&call, &response);
+            Some((response, (next, pages + 1)))
+        })
+    }
+
+    /// Execute a call whose output is streamed in chunks
+    ///
+    /// The call is routed and passes the same admission checks as
+    /// [`Orchestrator::execute_tool`], including consent verification,
+    /// and holds a concurrency slot until the stream ends or is dropped.
+    /// Calls rejected before streaming are audited.
+    /// Middleware runs around it too, with `post_execute` seeing the final
+    /// [`StreamEvent::Finished`] response. The execution timeout covers
+    /// the whole stream: once it elapses the stream finishes with
+    /// [`ExecutionStatus::Timeout`], and cancelling the call through
+    /// [`Orchestrator::cancel`] finishes it with
+    /// [`ExecutionStatus::Cancelled`]. Results are not cached, retried, or
+    /// checked against postconditions.
+    pub async fn execute_tool_streaming(
+        &self,
+        mut call: ToolCall,
+    ) -> Result<BoxStream<'_, StreamEvent>> {
+        let start = std::time::Instant::now();
+        let admitted = match self.route_capability(&mut call).await {
+            Ok(()) => self.pre_execute(&mut call).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = admitted {
+            warn!("Tool {} rejected before dispatch: {}", call.tool_name, e);
+            return Err(self.reject_stream(&call, e).await);
+        }
+        let executor = match self.resolve_executor(&call).await {
+            Ok(executor) => executor,
+            Err(e) => return Err(self.reject_stream(&call, e).await),
+        };
+        let in_flight = self.in_flight.enter(&executor);
+
+        let cost = executor.cost(&call);
+        if !self.budgets.debit(&call.user_id, cost) {
+            let e = CybulousError::BudgetExceeded(format!(
+                "{} cannot afford {} units for {}",
+                call.user_id, cost, call.tool_name
+            ));
+            return Err(self.reject_stream(&call, e).await);
+        }
+        let prepared = async {
+            let circuits = self.admit(executor.as_ref(), &call).await?;
+            let call = self.transform_request(executor.as_ref(), &call).await?;
+            Ok((call, circuits))
+        };
+        let (call, circuits) = match prepared.await {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                self.budgets.refund(&call.user_id, cost);
+                return Err(self.reject_stream(&call, e).await);
+            }
+        };
+
+        let permit = match self.acquire_slot(&call).await {
+            Ok(permit) => permit,
+            Err(status) => {
+                self.budgets.refund(&call.user_id, cost);
+                let stream_end = self.finish_stream(&call, status, None, start).await;
+                return Ok(stream::once(async move { stream_end }).boxed());
+            }
+        };
+        if let Err(e) = self.spend_consent_use(&call).await {
+            self.budgets.refund(&call.user_id, cost);
+            return Err(self.reject_stream(&call, e).await);
+        }
+
+        let (cancellable, cancel) = CancellationGuard::register(&self.cancellations, call.id);
+        let timeout = self.resolve_timeout(executor.as_ref(), &call).await;
+        let deadline = tokio::time::Instant::now() + timeout;
+        let chunks = match tokio::time::timeout_at(deadline, executor.execute_stream(&call)).await {
+            Ok(Ok(chunks)) => chunks,
+            Ok(Err(e)) => stream::once(async move { Err(e) }).boxed(),
+            Err(_) => stream::pending().boxed(),
+        };
+
+        struct Streaming {
+            chunks: BoxStream<'static, Result<serde_json::Value>>,
+            sequence: u64,
+            finished: bool,
+            /// Dropped with the stream if it ends early, releasing any
+            /// half-open probe
+            circuits: Vec<CircuitPermit>,
+            cancel: watch::Receiver<Option<CancellationReason>>,
+            _cancellable: CancellationGuard,
+            _permit: AdmissionPermit,
+            _in_flight: InFlightGuard,
+        }
+        let state = Streaming {
+            chunks,
+            sequence: 0,
+            finished: false,
+            circuits,
+            cancel,
+            _cancellable: cancellable,
+            _permit: permit,
+            _in_flight: in_flight,
+        };
+        Ok(stream::unfold(state, move |mut state| {
+            let call = call.clone();
+            async move {
+                if state.finished {
+                    return None;
+                }
+                let next = tokio::select! {
+                    next = tokio::time::timeout_at(deadline, state.chunks.next()) => Ok(next),
+                    reason = Self::cancellation(&call, state.cancel.clone()) => Err(reason),
+                };
+                let (status, error) = match next {
+                    Ok(Ok(Some(Ok(data)))) => {
+                        let chunk = ToolChunk {
+                            call_id: call.id,
+                            sequence: state.sequence,
+                            data,
+                        };
+                        state.sequence += 1;
+                        return Some((StreamEvent::Chunk(chunk), state));
+                    }
+                    Ok(Ok(Some(Err(e)))) => (ExecutionStatus::Failed, Some(e.to_string())),
+                    Ok(Ok(None)) => (ExecutionStatus::Success, None),
+                    Ok(Err(_)) => (
+                        ExecutionStatus::Timeout,
+                        Some("Execution timeout".to_string()),
+                    ),
+                    Err(reason) => {
+                        warn!("Streamed tool {} cancelled: {:?}", call.tool_name, reason);
+                        (
+                            ExecutionStatus::Cancelled(reason),
+                            Some(format!("Execution cancelled: {:?}", reason)),
+                        )
+                    }
+                };
+                state.finished = true;
+                Self::record_circuit_outcome(std::mem::take(&mut state.circuits), &status);
+                let stream_end = self.finish_stream(&call, status, error, start).await;
+                Some((stream_end, state))
+            }
+        })
+        .boxed())
+    }
+
+    /// Audit a streamed call rejected before streaming, returning the error
+    async fn reject_stream(&self, call: &ToolCall, error: CybulousError) -> CybulousError {
+        let status = match error {
+            CybulousError::ConsentError(_) => ExecutionStatus::ConsentDenied,
+            _ => ExecutionStatus::Failed,
+        };
+        self.audit_log.record(call, status).await;
+        error
+    }
+
+    /// Audit a streamed call's outcome and build its final event, passing
+    /// it through middleware
+    async fn finish_stream(
+        &self,
+        call: &ToolCall,
+        status: ExecutionStatus,
+        error: Option<String>,
+        start: std::time::Instant,
+    ) -> StreamEvent {
+        if status != ExecutionStatus::Success {
+            warn!(
+                "Streamed tool {} finished with {:?}",
+                call.tool_name, status
+            );
+        }
+        self.audit_log.record(call, status).await;
+        #[cfg(feature = "prometheus")]
+        {
+            let duration_ms = start.elapsed().as_millis() as u64;
+            self.prometheus.record(&call.tool_name, status, duration_ms);
+            self.prometheus.set_queue_depth(self.queue_depth());
+        }
+        let response = ToolResponse {
+            call_id: call.id,
+            status,
+            result: None,
+            error: error.or_else(|| match status {
+                ExecutionStatus::QueueTimeout => Some("Queue timeout".to_string()),
+                ExecutionStatus::Rejected => Some("No free concurrency slot".to_string()),
+                _ => None,
+            }),
+            duration_ms: start.elapsed().as_millis() as u64,
+            stale: false,
+        };
+        for middleware in self.middleware.read().await.iter().rev() {
+            middleware.post_execute(call, &response).await;
+        }
+        StreamEvent::Finished(response)
+    }
+
+    /// Execute paged calls concurrently, merging their responses
+    ///
+    /// Each source's pages are yielded in order. In ordered mode at most
+    /// `max_concurrent` sources run ahead of the one being yielded, holding
+    /// their pages until it is their turn.
+    pub fn execute_fan_out(
+        &self,
+        calls: Vec<ToolCall>,
+        mode: MergeMode,
+    ) -> BoxStream<'_, FanOutItem> {
+        let source = |(source, call): (usize, ToolCall)| {
+            self.execute_tool_paged(call)
+                .map(move |response| FanOutItem { source, response })
+        };
+        let sources = calls.into_iter().enumerate();
+        match mode {
+            MergeMode::Ordered => stream::iter(sources)
+                .map(move |call| source(call).collect::<Vec<_>>())
+                .buffered(self.max_concurrent.max(1))
+                .flat_map(stream::iter)
+                .boxed(),
+            MergeMode::Unordered => {
+                stream::select_all(sources.map(|call| source(call).boxed())).boxed()
+            }
+        }
+    }
+
+    /// Execute independent calls concurrently, reporting each separately
+    ///
+    /// At most `max_concurrent` calls from the batch run at once. A call
+    /// that is rejected outright, e.g. for an unknown tool or missing
+    /// consent, gets a `Failed` or `ConsentDenied` response carrying the
+    /// error instead of failing the batch.
+    pub async fn execute_batch(&self, calls: Vec<ToolCall>) -> BatchResponse {
+        let responses = stream::iter(calls)
+            .map(|call| async move {
+                let call_id = call.id;
+                let response = match self.execute_tool(call).await {
+                    Ok(response) => response,
+                    Err(e) => ToolResponse {
+                        call_id,
+                        status: match e {
+                            CybulousError::ConsentError(_) => ExecutionStatus::ConsentDenied,
+                            _ => ExecutionStatus::Failed,
+                        },
+                        result: None,
+                        error: Some(e.to_string()),
+                        duration_ms: 0,
+                        stale: false,
+                    },
+                };
+                (call_id, response)
+            })
+            .buffer_unordered(self.max_concurrent.max(1))
+            .collect()
+            .await;
+        BatchResponse { responses }
+    }
+
+    /// Cancel an in-flight call, returning whether it was found
+    pub async fn cancel(&self, call_id: Uuid, reason: CancellationReason) -> bool {
+        let cancellations = self.cancellations.lock().unwrap_or_else(|e| e.into_inner());
+        match cancellations.get(&call_id) {
+            Some(senders) => {
+                info!("Cancelling call {}: {:?}", call_id, reason);
+                for sender in senders {
+                    sender.send_replace(Some(reason));
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancel every in-flight call for shutdown
+    pub async fn shutdown(&self) {
+        let cancellations = self.cancellations.lock().unwrap_or_else(|e| e.into_inner());
+        for sender in cancellations.values().flatten() {
+            sender.send_replace(Some(CancellationReason::Shutdown));
+        }
+    }
+
+    async fn dispatch(
+        &self,
+        call: &ToolCall,
+        cancel: watch::Receiver<Option<CancellationReason>>,
+    ) -> Result<ToolResponse> {
+        let start = std::time::Instant::now();
+
+        // Find executor, honouring routing rules before the tool's default
+        let executor = self.resolve_executor(call).await?;
+        let _in_flight = self.in_flight.enter(&executor);
+
+        // Debit up front; refund if the call is rejected before execution
+        let cost = executor.cost(call);
+        if !self.budgets.debit(&call.user_id, cost) {
+            return Err(CybulousError::BudgetExceeded(format!(
+                "{} cannot afford {} units for {}",
+                call.user_id, cost, call.tool_name
+            )));
+        }
+        let circuits = match self.admit(executor.as_ref(), call).await {
+            Ok(circuits) => circuits,
+            Err(e) => {
+                self.budgets.refund(&call.user_id, cost);
+                return Err(e);
+            }
+        };
+
+        // Normalize parameters, then check the executor's requirements
+        let call = &match self.transform_request(executor.as_ref(), call).await {
+            Ok(call) => call,
+            Err(e) => {
+                self.budgets.refund(&call.user_id, cost);
+                return Err(e);
+            }
+        };
+
+        // Serve cached results without executing or charging
+        let cache_ttl = executor.cache_ttl();
+        if cache_ttl.is_some() {
+            if let Some(cached) = self.result_cache.get(call) {
+                self.budgets.refund(&call.user_id, cost);
+                return Ok(ToolResponse {
+                    call_id: call.id,
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    ..cached
+                });
+            }
+        }
+
+        // Wait for a concurrency slot, bounded by the queue timeout
+        let _permit = match self.acquire_slot(call).await {
+            Ok(permit) => permit,
+            Err(status) => {
+                self.budgets.refund(&call.user_id, cost);
+                let error = if status == ExecutionStatus::Rejected {
+                    warn!("Tool {} rejected: no free concurrency slot", call.tool_name);
+                    "No free concurrency slot"
+                } else {
+                    warn!("Tool {} not admitted before queue timeout", call.tool_name);
+                    "Queue timeout"
+                };
+                return Ok(ToolResponse {
+                    call_id: call.id,
+                    status,
+                    result: None,
+                    error: Some(error.to_string()),
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    stale: false,
+                });
+            }
+        };
+        if let Err(e) = self.spend_consent_use(call).await {
+            self.budgets.refund(&call.user_id, cost);
+            return Err(e);
+        }
+
+        let mut response = self
+            .run_with_retries(executor.as_ref(), call, start, cancel)
+            .await;
+        Self::enforce_postconditions(executor.as_ref(), call, &mut response);
+
+        if let Some(ttl) = cache_ttl {
+            if response.status == ExecutionStatus::Success {
+                self.result_cache
+                    .insert(call, response.clone(), executor.cache_tags(call), ttl);
+            }
+        }
+
+        if matches!(
+            response.status,
+            ExecutionStatus::Timeout | ExecutionStatus::Cancelled(_)
+        ) {
+            let ladder = executor.escalation_ladder();
+            if !ladder.is_empty() {
+                tokio::spawn(Self::escalate_stop(executor.clone(), call.clone(), ladder));
+            }
+        }
+
+        Self::record_circuit_outcome(circuits, &response.status);
+
+        if let Some(policy) = executor.degraded_policy() {
+            let now = std::time::Instant::now();
+            match response.status {
+                ExecutionStatus::Success => self.last_good.record(call, &response, policy, now),
+                ExecutionStatus::Failed | ExecutionStatus::Timeout => {
+                    if let Some(stale) = self.last_good.stale(call, policy, now) {
+                        warn!(
+                            "Tool {} failed; serving last-good result in degraded mode",
+                            call.tool_name
+                        );
+                        return Ok(ToolResponse {
+                            duration_ms: response.duration_ms,
+                            ..stale
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Acquire a concurrency slot in priority order
+    ///
+    /// Fails with [`ExecutionStatus::QueueTimeout`] if the queue timeout
+    /// elapses, or [`ExecutionStatus::Rejected`] if no slot is free under
+    /// fail-fast queueing.
+    async fn acquire_slot(
+        &self,
+        call: &ToolCall,
+    ) -> std::result::Result<AdmissionPermit, ExecutionStatus> {
+        if self.queueing == QueueingBehavior::FailFast {
+            return self
+                .admission
+                .try_acquire()
+                .ok_or(ExecutionStatus::Rejected);
+        }
+
+        let queued = GaugeGuard::new(&self.queue_depth);
+        let acquire = self.admission.acquire(priority::call_priority(call));
+        let permit = match call.queue_timeout_ms {
+            Some(ms) => tokio::time::timeout(tokio::time::Duration::from_millis(ms), acquire)
+                .await
+                .ok(),
+            None => Some(acquire.await),
+        };
+        drop(queued);
+        permit.ok_or(ExecutionStatus::QueueTimeout)
+    }
+
+    /// Run pre-execution checks, returning permits from the circuit
+    /// breakers the call passed
+    ///
+    /// The call's outcome is recorded through the permits; dropping them
+    /// instead, when the call ends without executing, releases any
+    /// half-open probe slot they claimed.
+    async fn admit(
+        &self,
+        executor: &dyn ToolExecutor,
+        call: &ToolCall,
+    ) -> Result<Vec<CircuitPermit>> {
+        // Check the calling service, client capabilities, then consent
+        self.authenticate_service(call)?;
+        self.authorize_capability(executor, call)?;
+        self.check_category_policy(executor, call).await?;
+        self.verify_consent(call, executor.requires_liveness())
+            .await?;
+        self.check_assurance(call, executor.min_assurance()).await?;
+        self.check_required_scopes(call).await?;
+
+        let mut circuits = Vec::new();
+        if let Some(breaker) = self.tool_breaker(&call.tool_name).await {
+            let permit = breaker.try_admit().ok_or_else(|| {
+                CybulousError::CircuitOpen(format!("tool {} circuit is open", call.tool_name))
+            })?;
+            circuits.push(permit);
+        }
+
+        // Apply group-level policies
+        if let Some(group) = self.group_for(&call.tool_name).await {
+            circuits.extend(Self::admit_to_group(&group)?);
+        }
+        Ok(circuits)
+    }
+
+    /// Reject every call to tools in `category` until allowed again
+    pub async fn deny_category(&self, category: ToolCategory) {
+        warn!("Denying all {:?} tools", category);
+        self.denied_categories.write().await.insert(category);
+    }
+
+    /// Lift a category-wide deny
+    pub async fn allow_category(&self, category: ToolCategory) {
+        self.denied_categories.write().await.remove(&category);
+    }
+
+    /// Enter or leave drain mode for maintenance
+    pub async fn set_drain_mode(&self, mode: DrainMode) {
+        warn!("Drain mode set to {:?}", mode);
+        *self.drain_mode.write().await = mode;
+    }
+
+    /// Current drain mode
+    pub async fn drain_mode(&self) -> DrainMode {
+        *self.drain_mode.read().await
+    }
+
+    async fn check_category_policy(
+        &self,
+        executor: &dyn ToolExecutor,
+        call: &ToolCall,
+    ) -> Result<()> {
+        let category = executor.category();
+        if self.denied_categories.read().await.contains(&category) {
+            return Err(CybulousError::AccessDenied(format!(
+                "{:?} tools are denied; rejected {}",
+                category, call.tool_name
+            )));
+        }
+        let drain_mode = self.drain_mode().await;
+        if !drain_mode.permits(category) {
+            return Err(CybulousError::Draining(format!(
+                "only read-only tools run during maintenance; rejected {:?} tool {}",
+                category, call.tool_name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Purge cached results matching `predicate`, returning how many were
+    /// removed
+    pub fn invalidate_cache(&self, predicate: &CachePredicate) -> usize {
+        self.result_cache.invalidate(predicate)
+    }
+
+    /// Set a user's execution budget in cost units
+    pub fn set_budget(&self, user_id: &str, units: u64) {
+        self.budgets.set(user_id, units);
+    }
+
+    /// Remaining execution budget for a user, if metered
+    pub fn remaining_budget(&self, user_id: &str) -> Option<u64> {
+        self.budgets.remaining(user_id)
+    }
+
+    /// Add middleware run around every call; earlier middleware wraps
+    /// later
+    pub async fn add_middleware(&self, middleware: Arc<dyn OrchestratorMiddleware>) {
+        self.middleware.write().await.push(middleware);
+    }
+
+    /// Point a call that requires a capability at a ready executor
+    /// advertising it
+    async fn route_capability(&self, call: &mut ToolCall) -> Result<()> {
+        let Some(capability) = &call.required_capability else {
+            return Ok(());
+        };
+        let readiness = self.readiness.read().await;
+        let mut candidates: Vec<String> = self
+            .executors
+            .read()
+            .await
+            .iter()
+            .filter(|(name, executor)| {
+                readiness.get(*name).copied().unwrap_or(false)
+                    && executor.supports_capability(capability)
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        candidates.sort();
+
+        let turn = {
+            let mut turns = self
+                .capability_turns
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            let turn = turns.entry(capability.clone()).or_default();
+            *turn += 1;
+            *turn - 1
+        };
+        let tool_name = self
+            .capability_selection
+            .select(&candidates, turn)
+            .ok_or_else(|| {
+                CybulousError::OrchestrationFailed(format!(
+                    "No ready executor supports capability {}",
+                    capability
+                ))
+            })?;
+        debug!("Routed {} call {} to {}", capability, call.id, tool_name);
+        call.tool_name = tool_name.clone();
+        Ok(())
+    }
+
+    async fn pre_execute(&self, call: &mut ToolCall) -> Result<()> {
+        for middleware in self.middleware.read().await.iter() {
+            middleware.pre_execute(call).await?;
+        }
+        Ok(())
+    }
+
+    /// Add a request transform applied to calls for every tool
+    ///
+    /// Transforms run in the order they were added.
+    pub async fn add_request_transform(&self, transform: Arc<dyn RequestTransform>) {
+        self.transforms.write().await.push((None, transform));
+    }
+
+    /// Add a request transform applied only to calls for `tool_name`
+    pub async fn add_tool_request_transform(
+        &self,
+        tool_name: &str,
+        transform: Arc<dyn RequestTransform>,
+    ) {
+        self.transforms
+            .write()
+            .await
+            .push((Some(tool_name.to_string()), transform));
+    }
+
+    /// Apply request transforms and validate required parameters
+    async fn transform_request(
+        &self,
+        executor: &dyn ToolExecutor,
+        call: &ToolCall,
+    ) -> Result<ToolCall> {
+        let mut transformed = call.clone();
+        let mut parameters = std::mem::take(&mut transformed.parameters);
+        for (tool, transform) in self.transforms.read().await.iter() {
+            if tool.as_ref().map_or(true, |t| *t == call.tool_name) {
+                transform.apply(call, &mut parameters)?;
+            }
+        }
+        transformed.parameters = parameters;
+
+        let missing: Vec<_> = executor
+            .required_parameters()
+            .into_iter()
+            .filter(|p| transformed.parameters.get(p).map_or(true, |v| v.is_null()))
+            .collect();
+        if !missing.is_empty() {
+            return Err(CybulousError::InvalidParameters(format!(
+                "{} missing required parameters: {}",
+                call.tool_name,
+                missing.join(", ")
+            )));
+        }
+        if let Some(input_schema) = executor.input_schema() {
+            self.schemas
+                .validate(executor.name(), &input_schema, &transformed.parameters)?;
+        }
+        Ok(transformed)
+    }
+
+    /// Add a routing rule; earlier rules take precedence
+    pub async fn add_routing_rule(&self, rule: RoutingRule) {
+        self.routing_rules.write().await.push(rule);
+    }
+
+    /// Add a locality route, preferring `route.executor` for users whose
+    /// consent is recorded in `route.region`
+    pub async fn add_locality_route(&self, route: LocalityRoute) {
+        self.locality_routes.write().await.push(route);
+    }
+
+    async fn resolve_executor(&self, call: &ToolCall) -> Result<Arc<dyn ToolExecutor>> {
+        let matched = self
+            .routing_rules
+            .read()
+            .await
+            .iter()
+            .find(|rule| rule.matches(&call.tool_name, &call.context.metadata))
+            .map(|rule| rule.executor.clone());
+        let routed = match matched {
+            Some(executor) => Some(executor),
+            None => self.locality_executor(call).await,
+        };
+        let name = routed.as_deref().unwrap_or(&call.tool_name);
+
+        if let Some(table) = self.versions.read().await.get(name) {
+            return table.resolve(name, call.tool_version.as_ref());
+        }
+        if let Some(req) = &call.tool_version {
+            return Err(CybulousError::OrchestrationFailed(format!(
+                "Tool {} is unversioned but the call requires {}",
+                name, req
+            )));
+        }
+        self.executors
+            .read()
+            .await
+            .get(name)
+            .cloned()
+            .ok_or_else(|| match &routed {
+                Some(executor) => CybulousError::OrchestrationFailed(format!(
+                    "Tool {} routed to unregistered executor {}",
+                    call.tool_name, executor
+                )),
+                None => {
+                    CybulousError::OrchestrationFailed(format!("Unknown tool: {}", call.tool_name))
+                }
+            })
+    }
+
+    /// Executor co-located with the region holding the user's consent
+    async fn locality_executor(&self, call: &ToolCall) -> Option<String> {
+        let routes = self.locality_routes.read().await;
+        if !routes.iter().any(|route| route.tool_name == call.tool_name) {
+            return None;
+        }
+
+        // Unknown consent falls back to default routing; verification
+        // reports the actual failure
+        let region = match self.consent_engine.origin_region(&call.user_id).await {
+            Ok(region) => region?,
+            Err(e) => {
+                debug!("No consent region for {}: {}", call.user_id, e);
+                return None;
+            }
+        };
+        routes
+            .iter()
+            .find(|route| route.matches(&call.tool_name, &region))
+            .map(|route| route.executor.clone())
+    }
+
+    /// Downgrade a successful response that violates its postconditions
+    fn enforce_postconditions(
+        executor: &dyn ToolExecutor,
+        call: &ToolCall,
+        response: &mut ToolResponse,
+    ) {
+        if response.status != ExecutionStatus::Success {
+            return;
+        }
+
+        let violations = postcondition::violations(
+            &executor.postconditions(call, response),
+            response.result.as_ref(),
+        );
+        if !violations.is_empty() {
+            error!(
+                "Tool {} violated postconditions: {}",
+                call.tool_name,
+                violations.join("; ")
+            );
+            response.status = ExecutionStatus::Failed;
+            response.error = Some(format!("postcondition violated: {}", violations.join("; ")));
+        }
+    }
+
+    /// Run an executor, retrying retryable attempts under its retry policy
+    async fn run_with_retries(
+        &self,
+        executor: &dyn ToolExecutor,
+        call: &ToolCall,
+        start: std::time::Instant,
+        cancel: watch::Receiver<Option<CancellationReason>>,
+    ) -> ToolResponse {
+        let policy = executor.retry_policy().or(self.retry_policy);
+        let mut attempt = 1;
+        loop {
+            let response = self
+                .run_executor(executor, call, start, cancel.clone())
+                .await;
+            let Some(policy) = policy else {
+                return response;
+            };
+            if !policy.should_retry(attempt, response.status)
+                || !executor.is_retryable(call, &response)
+            {
+                return response;
+            }
+
+            let delay = policy.delay(attempt);
+            warn!(
+                "Tool {} attempt {} ended with {:?}; retrying in {:?}",
+                call.tool_name, attempt, response.status, delay
+            );
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                reason = Self::cancellation(call, cancel.clone()) => {
+                    warn!("Tool {} cancelled: {:?}", call.tool_name, reason);
+                    return ToolResponse {
+                        call_id: call.id,
+                        status: ExecutionStatus::Cancelled(reason),
+                        result: None,
+                        error: Some(format!("Execution cancelled: {:?}", reason)),
+                        duration_ms: start.elapsed().as_millis() as u64,
+                        stale: false,
+                    };
+                }
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Run an executor under the call's timeout, mapping errors to a response
+    async fn run_executor(
+        &self,
+        executor: &dyn ToolExecutor,
+        call: &ToolCall,
+        start: std::time::Instant,
+        cancel: watch::Receiver<Option<CancellationReason>>,
+    ) -> ToolResponse {
+        let timeout = self.resolve_timeout(executor, call).await;
+
+        let latency = match self.inject_faults(call, start, timeout) {
+            Ok(latency) => latency,
+            Err(response) => return response,
+        };
+
+        let (token_tx, token_rx) = watch::channel(None);
+        let execution = async {
+            if let Some(latency) = latency {
+                tokio::time::sleep(latency).await;
+            }
+            executor
+                .execute_cancellable(call, CancellationToken::new(token_rx))
+                .await
+        };
+
+        let outcome = tokio::select! {
+            outcome = tokio::time::timeout(timeout, execution) => outcome,
+            reason = Self::cancellation(call, cancel) => {
+                warn!("Tool {} cancelled: {:?}", call.tool_name, reason);
+                token_tx.send_replace(Some(reason));
+                return ToolResponse {
+                    call_id: call.id,
+                    status: ExecutionStatus::Cancelled(reason),
+                    result: None,
+                    error: Some(format!("Execution cancelled: {:?}", reason)),
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    stale: false,
+                };
+            }
+        };
+
+        match outcome {
+            Ok(Ok(mut response)) => {
+                response.duration_ms = start.elapsed().as_millis() as u64;
+                info!(
+                    "Tool {} executed successfully in {}ms",
+                    call.tool_name, response.duration_ms
+                );
+                response
+            }
+            Ok(Err(e)) => {
+                error!("Tool {} execution failed: {}", call.tool_name, e);
+                self.record_error(&call.tool_name, executor.classify_error(&e));
+                ToolResponse {
+                    call_id: call.id,
+                    status: ExecutionStatus::Failed,
+                    result: None,
+                    error: Some(e.to_string()),
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    stale: false,
+                }
+            }
+            Err(_) => {
+                warn!("Tool {} execution timed out", call.tool_name);
+                self.record_error(&call.tool_name, ErrorClass::Transient);
+                ToolResponse {
+                    call_id: call.id,
+                    status: ExecutionStatus::Timeout,
+                    result: None,
+                    error: Some("Execution timeout".to_string()),
+                    duration_ms: timeout.as_millis() as u64,
+                    stale: false,
+                }
+            }
+        }
+    }
+
+    /// Execution timeout for a call, falling back to the executor's default
+    /// and then the orchestrator's when the call specifies none
+    async fn resolve_timeout(
+        &self,
+        executor: &dyn ToolExecutor,
+        call: &ToolCall,
+    ) -> std::time::Duration {
+        match call.execution_timeout() {
+            0 => match self.tool_policy(&call.tool_name).await {
+                Some(ToolPolicy {
+                    timeout: Some(timeout),
+                    ..
+                }) => timeout,
+                _ => executor.default_timeout().unwrap_or(self.default_timeout),
+            },
+            ms => std::time::Duration::from_millis(ms),
+        }
+    }
+
+    /// Ask an executor to stop an abandoned call, escalating while it keeps
+    /// running
+    async fn escalate_stop(
+        executor: Arc<dyn ToolExecutor>,
+        call: ToolCall,
+        ladder: EscalationLadder,
+    ) {
+        executor.stop(&call, StopSignal::Cancel).await;
+        for step in ladder.steps() {
+            tokio::time::sleep(step.grace).await;
+            if executor.has_stopped(&call).await {
+                return;
+            }
+            warn!(
+                "Tool {} still running call {}; escalating to {:?}",
+                call.tool_name, call.id, step.signal
+            );
+            executor.stop(&call, step.signal).await;
+        }
+    }
+
+    /// Resolve when the call is cancelled or its deadline passes
+    async fn cancellation(
+        call: &ToolCall,
+        mut cancel: watch::Receiver<Option<CancellationReason>>,
+    ) -> CancellationReason {
+        let deadline = call
+            .context
+            .metadata
+            .get(DEADLINE_KEY)
+            .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+            .map(|d| {
+                (d.with_timezone(&chrono::Utc) - chrono::Utc::now())
+                    .to_std()
+                    .unwrap_or_default()
+            });
+        let deadline = async {
+            match deadline {
+                Some(remaining) => tokio::time::sleep(remaining).await,
+                None => std::future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            Ok(reason) = cancel.wait_for(Option::is_some) => {
+                (*reason).unwrap_or(CancellationReason::ClientCancelled)
+            }
+            _ = deadline => CancellationReason::Deadline,
+        }
+    }
+
+    /// Apply injected faults: `Err` replaces the executor's result,
+    /// `Ok` carries any latency to add before dispatch
+    #[cfg(any(test, feature = "fault-injection"))]
+    fn inject_faults(
+        &self,
+        call: &ToolCall,
+        start: std::time::Instant,
+        timeout: std::time::Duration,
+    ) -> std::result::Result<Option<std::time::Duration>, ToolResponse> {
+        let Some(injector) = &self.fault_injector else {
+            return Ok(None);
+        };
+        let decision = injector.decide(&call.tool_name);
+        let Some(outcome) = decision.outcome else {
+            return Ok(decision.latency);
+        };
+
+        warn!("Injecting {:?} into {}", outcome, call.tool_name);
+        let (status, error, duration_ms) = match outcome {
+            FaultOutcome::Error => (
+                ExecutionStatus::Failed,
+                "Injected fault",
+                start.elapsed().as_millis() as u64,
+            ),
+            FaultOutcome::Timeout => (
+                ExecutionStatus::Timeout,
+                "Execution timeout",
+                timeout.as_millis() as u64,
+            ),
+        };
+        Err(ToolResponse {
+            call_id: call.id,
+            status,
+            result: None,
+            error: Some(error.to_string()),
+            duration_ms,
+            stale: false,
+        })
+    }
+
+    #[cfg(not(any(test, feature = "fault-injection")))]
+    fn inject_faults(
+        &self,
+        _call: &ToolCall,
+        _start: std::time::Instant,
+        _timeout: std::time::Duration,
+    ) -> std::result::Result<Option<std::time::Duration>, ToolResponse> {
+        Ok(None)
+    }
+
+    /// Define (or redefine) an executor group and its policy
+    pub async fn define_group(&self, name: &str, policy: GroupPolicy) {
+        self.groups
+            .write()
+            .await
+            .insert(name.to_string(), Arc::new(ExecutorGroup::new(name, policy)));
+        info!("Defined executor group: {}", name);
+    }
+
+    /// Assign a tool to a previously defined group
+    pub async fn assign_to_group(&self, tool_name: &str, group: &str) -> Result<()> {
+        if !self.groups.read().await.contains_key(group) {
+            return Err(CybulousError::OrchestrationFailed(format!(
+                "Unknown executor group: {}",
+                group
+            )));
+        }
+
+        self.group_membership
+            .write()
+            .await
+            .insert(tool_name.to_string(), group.to_string());
+        Ok(())
+    }
+
+    /// State of the circuit breaker guarding a tool: its own if it has
+    /// one, otherwise its group's
+    pub async fn circuit_state(&self, tool_name: &str) -> Option<CircuitState> {
+        if let Some(breaker) = self.tool_breaker(tool_name).await {
+            return Some(breaker.state());
+        }
+        let group = self.group_for(tool_name).await?;
+        group.breaker().map(|b| b.state())
+    }
+
+    /// Guard `tool_name` with its own circuit breaker configured by `config`
+    ///
+    /// Replaces any breaker the tool already has, resetting its state.
+    pub async fn set_tool_circuit_breaker(&self, tool_name: &str, config: CircuitBreakerConfig) {
+        self.tool_breakers
+            .write()
+            .await
+            .insert(tool_name.to_string(), Arc::new(CircuitBreaker::new(config)));
+    }
+
+    /// Transition counts of a tool's own circuit breaker, if it has one
+    pub async fn tool_circuit_metrics(&self, tool_name: &str) -> Option<CircuitMetrics> {
+        self.tool_breaker(tool_name).await.map(|b| b.metrics())
+    }
+
+    /// A tool's own breaker, created from the default config on first use
+    async fn tool_breaker(&self, tool_name: &str) -> Option<Arc<CircuitBreaker>> {
+        if let Some(breaker) = self.tool_breakers.read().await.get(tool_name) {
+            return Some(breaker.clone());
+        }
+        let config = self.default_tool_breaker?;
+        let mut breakers = self.tool_breakers.write().await;
+        let breaker = breakers
+            .entry(tool_name.to_string())
+            .or_insert_with(|| Arc::new(CircuitBreaker::new(config)));
+        Some(breaker.clone())
+    }
+
+    fn record_circuit_outcome(circuits: Vec<CircuitPermit>, status: &ExecutionStatus) {
+        for permit in circuits {
+            match status {
+                ExecutionStatus::Success => permit.record_success(),
+                // Says nothing about the tool's health; dropping the
+                // permit frees the probe slot for another call
+                ExecutionStatus::Cancelled(_) => {}
+                _ => permit.record_failure(),
+            }
+        }
+    }
+
+    /// Open a tool's circuit until [`Orchestrator::force_close`]
+    ///
+    /// Forces the tool's own breaker if it has one, otherwise its group's,
+    /// which opens the circuit for every tool in the group. The change is
+    /// audited.
+    pub async fn force_open(&self, tool_name: &str) -> Result<()> {
+        self.force_circuit(tool_name, CircuitState::Open).await
+    }
+
+    /// Close a tool's circuit, resuming calls; the change is audited
+    pub async fn force_close(&self, tool_name: &str) -> Result<()> {
+        self.force_circuit(tool_name, CircuitState::Closed).await
+    }
+
+    async fn force_circuit(&self, tool_name: &str, state: CircuitState) -> Result<()> {
+        let (group, breaker) = match self.tool_breaker(tool_name).await {
+            Some(breaker) => (None, breaker),
+            None => self
+                .group_for(tool_name)
+                .await
+                .and_then(|g| Some((Some(g.name().to_string()), g.breaker()?.clone())))
+                .ok_or_else(|| {
+                    CybulousError::OrchestrationFailed(format!(
+                        "tool {} has no circuit breaker",
+                        tool_name
+                    ))
+                })?,
+        };
+        match state {
+            CircuitState::Closed => breaker.force_close(),
+            _ => breaker.force_open(),
+        }
+        match &group {
+            Some(group) => warn!(
+                "Circuit for group {} forced {:?} via tool {}",
+                group, state, tool_name
+            ),
+            None => warn!("Circuit for tool {} forced {:?}", tool_name, state),
+        }
+        self.audit_log
+            .record_circuit_change(tool_name, group.as_deref(), state)
+            .await;
+        Ok(())
+    }
+
+    async fn group_for(&self, tool_name: &str) -> Option<Arc<ExecutorGroup>> {
+        let group = self.group_membership.read().await.get(tool_name).cloned()?;
+        self.groups.read().await.get(&group).cloned()
+    }
+
+    fn admit_to_group(group: &ExecutorGroup) -> Result<Option<CircuitPermit>> {
+        let permit = match group.breaker() {
+            Some(breaker) => Some(breaker.try_admit().ok_or_else(|| {
+                CybulousError::CircuitOpen(format!("group {} circuit is open", group.name()))
+            })?),
+            None => None,
+        };
+        if group.limiter().is_some_and(|l| !l.try_acquire()) {
+            return Err(CybulousError::RateLimited(format!(
+                "group {} rate limit exceeded",
+                group.name()
+            )));
+        }
+        Ok(permit)
+    }
+
+    /// Execute a saga as children of `parent`, rolling back on failure
+    ///
+    /// A step fails if its call errors or does not succeed. Compensations
+    /// run even if an earlier compensation failed.
+    pub async fn execute_saga(&self, saga: &Saga, parent: &ToolCall) -> SagaOutcome {
+        let mut outcome = SagaOutcome::default();
+
+        for step in &saga.steps {
+            match self.run_saga_action(&step.forward, parent).await {
+                Ok(()) => outcome.completed.push(step.id.clone()),
+                Err(e) => {
+                    outcome.failed_step = Some(step.id.clone());
+                    outcome.error = Some(e);
+                    break;
+                }
+            }
+        }
+
+        if outcome.failed_step.is_some() {
+            for id in outcome.completed.iter().rev() {
+                let Some(step) = saga.steps.iter().find(|s| &s.id == id) else {
+                    continue;
+                };
+                match self.run_saga_action(&step.compensation, parent).await {
+                    Ok(()) => outcome.compensated.push(id.clone()),
+                    Err(e) => {
+                        error!("Compensation for saga step {} failed: {}", id, e);
+                        outcome.compensation_failures.push(id.clone());
+                    }
+                }
+            }
+        }
+
+        outcome
+    }
+
+    async fn run_saga_action(
+        &self,
+        action: &SagaAction,
+        parent: &ToolCall,
+    ) -> std::result::Result<(), String> {
+        let call = parent.derive_child(action.tool_name.clone(), action.parameters.clone());
+        match self.execute_tool(call).await {
+            Ok(response) if response.status == ExecutionStatus::Success => Ok(()),
+            Ok(response) => Err(response
+                .error
+                .unwrap_or_else(|| format!("finished with {:?}", response.status))),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Validate a workflow for `user_id` without executing any node
+    ///
+    /// Checks DAG structure and template references, then probes each node
+    /// as its tool would admit it: the tool must be registered, its
+    /// parameters must satisfy the tool's requirements and input schema,
+    /// and the user's consent must meet the tool's liveness, assurance and
+    /// scope requirements.
+    pub async fn validate_workflow(
+        &self,
+        workflow: &Workflow,
+        user_id: &str,
+        context: &ExecutionContext,
+    ) -> WorkflowValidation {
+        let mut validation = workflow.validate_structure();
+
+        for node in &workflow.nodes {
+            let executor = self.executors.read().await.get(&node.tool_name).cloned();
+            let Some(executor) = executor else {
+                validation.node_error(&node.id, format!("unknown tool {}", node.tool_name));
+                continue;
+            };
+            let probe = ToolCall::new(
+                node.tool_name.clone(),
+                node.parameters.clone(),
+                user_id,
+                context.clone(),
+            );
+
+            // Templates resolve at run time, so only untemplated parameters
+            // can be checked against the schema
+            if workflow::template_references(&node.parameters).is_empty() {
+                if let Err(e) = self.transform_request(executor.as_ref(), &probe).await {
+                    validation.node_error(&node.id, e.to_string());
+                }
+            } else {
+                for param in executor.required_parameters() {
+                    if node.parameters.get(&param).is_none() {
+                        validation
+                            .node_error(&node.id, format!("missing required parameter {}", param));
+                    }
+                }
+            }
+
+            let admitted = async {
+                self.verify_consent(&probe, executor.requires_liveness())
+                    .await?;
+                self.check_assurance(&probe, executor.min_assurance())
+                    .await?;
+                self.check_required_scopes(&probe).await
+            };
+            if let Err(e) = admitted.await {
+                validation.node_error(&node.id, e.to_string());
+            }
+        }
+
+        validation
+    }
+
+    /// Execute a workflow's nodes in topological order as children of
+    /// `parent`
+    ///
+    /// Each node's parameter templates are resolved against the responses
+    /// of the nodes it depends on. A node fails if its call errors or does
+    /// not succeed; its [`FailureMode`] decides whether the run aborts or
+    /// only its dependents are skipped. Workflows failing structural
+    /// validation are rejected before any node runs.
+    pub async fn execute_workflow(
+        &self,
+        workflow: &Workflow,
+        parent: &ToolCall,
+    ) -> Result<WorkflowRun> {
+        let validation = workflow.validate_structure();
+        if !validation.is_valid() {
+            let mut problems = validation.diagnostics;
+            let mut nodes: Vec<_> = validation.nodes.into_iter().collect();
+            nodes.sort_by(|a, b| a.0.cmp(&b.0));
+            for (node_id, errors) in nodes {
+                problems.extend(errors.into_iter().map(|e| format!("{}: {}", node_id, e)));
+            }
+            return Err(CybulousError::InvalidParameters(format!(
+                "invalid workflow: {}",
+                problems.join("; ")
+            )));
+        }
+
+        let mut run = WorkflowRun::default();
+        let mut outputs = HashMap::new();
+        for node in workflow.topological_order() {
+            let blocked = run.aborted
+                || node
+                    .depends_on
+                    .iter()
+                    .any(|dep| !run.outputs.contains_key(dep));
+            if blocked {
+                run.skipped.push(node.id.clone());
+                continue;
+            }
+
+            let parameters = workflow::resolve_templates(&node.parameters, &outputs);
+            let call = parent.derive_child(node.tool_name.clone(), parameters);
+            let error = match self.execute_tool(call).await {
+                Ok(response) if response.status == ExecutionStatus::Success => {
+                    outputs.insert(node.id.clone(), serde_json::to_value(&response)?);
+                    run.outputs.insert(node.id.clone(), response);
+                    continue;
+                }
+                Ok(response) => response
+                    .error
+                    .unwrap_or_else(|| format!("finished with {:?}", response.status)),
+                Err(e) => e.to_string(),
+            };
+
+            warn!("Workflow node {} failed: {}", node.id, error);
+            run.failures.insert(node.id.clone(), error);
+            if node.on_failure == FailureMode::Abort {
+                run.aborted = true;
+            }
+        }
+        Ok(run)
+    }
+
+    /// Verify the calling service's signature when a keyring is configured
+    fn authenticate_service(&self, call: &ToolCall) -> Result<()> {
+        let Some(keyring) = &self.service_keyring else {
+            return Ok(());
+        };
+
+        match keyring.verify(call) {
+            Ok(_) => Ok(()),
+            Err(e) if keyring.mode() == SigningMode::Monitor => {
+                warn!("Service signature check failed for call {}: {}", call.id, e);
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Check the caller's capability token permits this tool
+    fn authorize_capability(&self, executor: &dyn ToolExecutor, call: &ToolCall) -> Result<()> {
+        let Some(issuer) = &self.capability_issuer else {
+            return Ok(());
+        };
+
+        let token = call
+            .context
+            .metadata
+            .get(CAPABILITY_TOKEN_KEY)
+            .ok_or_else(|| CybulousError::AccessDenied("capability token required".to_string()))?;
+        let claims = CapabilityToken::from_compact(token.as_str()).verify(issuer)?;
+
+        if !claims.permits(&call.tool_name, |c| executor.supports_capability(c)) {
+            warn!(
+                "Capability token for {} does not permit tool {}",
+                claims.subject, call.tool_name
+            );
+            return Err(CybulousError::AccessDenied(format!(
+                "tool {} not permitted for {}",
+                call.tool_name, claims.subject
+            )));
+        }
+        Ok(())
+    }
+
+    /// Verify user consent for tool execution
+    ///
+    /// No use of a use-limited consent is spent here; see
+    /// [`Orchestrator::spend_consent_use`].
+    async fn verify_consent(&self, call: &ToolCall, requires_liveness: bool) -> Result<()> {
+        if let Some(token) = call.context.metadata.get(EMERGENCY_OVERRIDE_KEY) {
+            if self
+                .consent_engine
+                .verify_override(&call.user_id, token)
+                .await
+            {
+                warn!(
+                    "Consent gating overridden for user {} on tool {}",
+                    call.user_id, call.tool_name
+                );
+                return Ok(());
+            }
+            return Err(CybulousError::ConsentError(
+                "Invalid emergency override".to_string(),
+            ));
+        }
+
+        let region = call.context.region.as_deref();
+        let verified = async {
+            if requires_liveness {
+                self.consent_engine
+                    .check_consent_with_liveness_in_region(
+                        &call.user_id,
+                        &call.context.consent_proof,
+                        region,
+                    )
+                    .await
+            } else {
+                self.consent_engine
+                    .check_consent_in_region(&call.user_id, &call.context.consent_proof, region)
+                    .await
+            }
+        }
+        .instrument(info_span!(
+            "consent_verify",
+            user = %call.user_id,
+            liveness = requires_liveness,
+        ))
+        .await;
+        match verified {
+            Ok(true) => Ok(()),
+            Ok(false) if requires_liveness => Err(CybulousError::ConsentError(
+                "Fresh liveness proof required".to_string(),
+            )),
+            Ok(false) => Err(CybulousError::ConsentError(
+                "Consent verification failed".to_string(),
+            )),
+            Err(e) => Err(CybulousError::ConsentError(format!(
+                "Consent check error: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Spend a use of the caller's consent as its executor starts
+    ///
+    /// Calls answered from cache or turned away before executing leave a
+    /// use-limited consent untouched. Emergency overrides bypass consent
+    /// and spend nothing.
+    async fn spend_consent_use(&self, call: &ToolCall) -> Result<()> {
+        if call.context.metadata.contains_key(EMERGENCY_OVERRIDE_KEY) {
+            return Ok(());
+        }
+        match self.consent_engine.spend_use(&call.user_id).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(CybulousError::ConsentError(
+                "Consent uses exhausted".to_string(),
+            )),
+            Err(e) => Err(CybulousError::ConsentError(format!(
+                "Consent check error: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Check the caller's consent came from a provider of sufficient assurance
+    async fn check_assurance(&self, call: &ToolCall, min_assurance: u8) -> Result<()> {
+        if min_assurance == 0 {
+            return Ok(());
+        }
+        let level = self
+            .consent_engine
+            .consent_assurance(&call.user_id)
+            .await
+            .map_err(|e| CybulousError::ConsentError(format!("Consent check error: {}", e)))?;
+        if level < min_assurance {
+            return Err(CybulousError::ConsentError(format!(
+                "Consent assurance level {} below required {} for {}",
+                level, min_assurance, call.tool_name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reject calls unless the caller's consent grants every scope the
+    /// tool's policy requires
+    async fn check_required_scopes(&self, call: &ToolCall) -> Result<()> {
+        let Some(policy) = self.tool_policy(&call.tool_name).await else {
+            return Ok(());
+        };
+        for scope in &policy.required_scopes {
+            let granted = self
+                .consent_engine
+                .verify_consent_scoped(&call.user_id, &call.context.consent_proof, scope)
+                .await
+                .map_err(|e| CybulousError::ConsentError(format!("Consent check error: {}", e)))?;
+            if !granted {
+                return Err(CybulousError::ConsentError(format!(
+                    "Consent scope {} required for {}",
+                    scope, call.tool_name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Configure registered tools as described by `manifest`
+    ///
+    /// The whole manifest is checked before anything is applied: every
+    /// tool and route target must be registered, executors must support
+    /// the listed capabilities, and groups must be defined in the manifest
+    /// or already exist.
+    pub async fn apply_manifest(&self, manifest: &Manifest) -> Result<()> {
+        {
+            let executors = self.executors.read().await;
+            let groups = self.groups.read().await;
+            let invalid = |reason: String| Err(CybulousError::ManifestInvalid(reason));
+            for tool in &manifest.tools {
+                let Some(executor) = executors.get(&tool.name) else {
+                    return invalid(format!("tool {} is not registered", tool.name));
+                };
+                if let Some(capability) = tool
+                    .capabilities
+                    .iter()
+                    .find(|c| !executor.supports_capability(c))
+                {
+                    return invalid(format!(
+                        "tool {} does not support capability {}",
+                        tool.name, capability
+                    ));
+                }
+                if let Some(group) = &tool.group {
+                    if !manifest.groups.contains_key(group) && !groups.contains_key(group) {
+                        return invalid(format!(
+                            "tool {} names unknown group {}",
+                            tool.name, group
+                        ));
+                    }
+                }
+            }
+            if let Some(route) = manifest
+                .routes
+                .iter()
+                .find(|r| !executors.contains_key(&r.executor))
+            {
+                return invalid(format!(
+                    "route for {} targets unregistered executor {}",
+                    route.tool_name, route.executor
+                ));
+            }
+        }
+
+        for (name, policy) in &manifest.groups {
+            self.define_group(name, policy.clone()).await;
+        }
+        for tool in &manifest.tools {
+            self.tool_policies
+                .write()
+                .await
+                .insert(tool.name.clone(), tool.policy());
+            if let Some(group) = &tool.group {
+                self.assign_to_group(&tool.name, group).await?;
+            }
+            if let Some(sla) = tool.sla {
+                self.set_sla(&tool.name, sla).await;
+            }
+        }
+        for route in &manifest.routes {
+            self.add_routing_rule(route.clone()).await;
+        }
+        info!(
+            "Applied manifest configuring {} tools",
+            manifest.tools.len()
+        );
+        Ok(())
+    }
+
+    /// Redact fields of a tool's results that require consent scopes the
+    /// caller lacks
+    pub async fn set_result_scope_filter(&self, tool_name: &str, filter: ResultScopeFilter) {
+        self.result_filters
+            .write()
+            .await
+            .insert(tool_name.to_string(), filter);
+    }
+
+    /// Call policy applied to a tool by a manifest, if any
+    pub async fn tool_policy(&self, tool_name: &str) -> Option<ToolPolicy> {
+        self.tool_policies.read().await.get(tool_name).cloned()
+    }
+
+    /// Set the SLA a tool is evaluated against, resetting its window
+    pub async fn set_sla(&self, tool_name: &str, target: SlaTarget) {
+        self.slas
+            .write()
+            .await
+            .insert(tool_name.to_string(), SlaTracker::new(target));
+    }
+
+    /// Current SLA compliance for a tool, if it has an SLA
+    pub async fn sla_status(&self, tool_name: &str) -> Option<SlaStatus> {
+        self.slas
+            .read()
+            .await
+            .get(tool_name)
+            .map(|tracker| tracker.status(tool_name))
+    }
+
+    /// Subscribe to SLA breach events
+    pub fn subscribe_sla_breaches(&self) -> broadcast::Receiver<SlaBreach> {
+        self.sla_breaches.subscribe()
+    }
+
+    /// Call counters by tenant label
+    pub fn tenant_metrics(&self) -> HashMap<String, TenantMetrics> {
+        self.tenant_metrics.snapshot()
+    }
+
+    /// Usage by cost center for calls completed within `range`
+    pub fn usage_report(
+        &self,
+        range: std::ops::Range<chrono::DateTime<chrono::Utc>>,
+    ) -> UsageReport {
+        self.usage.report(range)
+    }
+
+    /// Subscribe to notices raised by notify-on-access consent obligations
+    pub fn subscribe_access_notices(&self) -> broadcast::Receiver<AccessNotice> {
+        self.access_notices.subscribe()
+    }
+
+    /// Subscribe to alerts raised by classified executor errors
+    pub fn subscribe_error_alerts(&self) -> broadcast::Receiver<ErrorAlert> {
+        self.error_alerts.subscribe()
+    }
+
+    fn record_error(&self, tool_name: &str, class: ErrorClass) {
+        if let Some(alert) = self
+            .alerter
+            .record(tool_name, class, std::time::Instant::now())
+        {
+            error!(
+                "{:?} errors from {} reached {} within the alert window",
+                alert.class, alert.tool_name, alert.count
+            );
+            // No subscribers is fine
+            let _ = self.error_alerts.send(alert);
+        }
+    }
+
+    async fn record_sla(&self, tool_name: &str, duration_ms: u64, success: bool) {
+        let mut slas = self.slas.write().await;
+        let Some(tracker) = slas.get_mut(tool_name) else {
+            return;
+        };
+        let latency = std::time::Duration::from_millis(duration_ms);
+        if let Some(breach) = tracker.record(tool_name, latency, success) {
+            warn!("SLA breached for {}: {:?}", tool_name, breach.violations);
+            // No subscribers is not an error
+            let _ = self.sla_breaches.send(breach);
+        }
+    }
+
+    /// Aggregate health of the orchestrator and its dependencies
+    pub async fn system_health(&self) -> SystemHealth {
+        let consent_reachable = match self.consent_engine.health_check().await {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Consent engine health check failed: {}", e);
+                false
+            }
+        };
+
+        let mut circuits: HashMap<_, _> = self
+            .tool_breakers
+            .read()
+            .await
+            .iter()
+            .map(|(tool, breaker)| (tool.clone(), breaker.state()))
+            .collect();
+        for (name, group) in self.groups.read().await.iter() {
+            if let Some(breaker) = group.breaker() {
+                circuits.insert(format!("group:{}", name), breaker.state());
+            }
+        }
+
+        let mut health = SystemHealth {
+            status: HealthStatus::Healthy,
+            consent_reachable,
+            executors: self.readiness.read().await.clone(),
+            queue_depth: self.queue_depth.load(Ordering::SeqCst),
+            inflight: self.in_flight.total(),
+            circuits,
+            error_rates: self
+                .outcomes
+                .read()
+                .await
+                .iter()
+                .map(|(tool, window)| (tool.clone(), window.error_rate()))
+                .collect(),
+        };
+        health.rollup();
+        health
+    }
+
+    /// Calls currently waiting for a concurrency slot
+    pub fn queue_depth(&self) -> usize {
+        self.queue_depth.load(Ordering::SeqCst)
+    }
+
+    /// Prometheus metrics for this orchestrator
+    #[cfg(feature = "prometheus")]
+    pub fn prometheus_metrics(&self) -> Arc<PrometheusMetrics> {
+        self.prometheus.clone()
+    }
+
+    /// Current metrics as a `/metrics` scrape payload
+    #[cfg(feature = "prometheus")]
+    pub fn encode_metrics(&self) -> String {
+        self.prometheus.set_queue_depth(self.queue_depth());
+        self.prometheus.encode()
+    }
+
+    /// Audit log of executed calls
+    pub fn audit_log(&self) -> Arc<AuditLog> {
+        self.audit_log.clone()
+    }
+
+    /// Reconstruct the lineage of a call from the audit log, root first
+    pub async fn causality_chain(&self, call_id: Uuid) -> Vec<AuditRecord> {
+        self.audit_log.causality_chain(call_id).await
+    }
+
+    /// List all registered tools
+    pub async fn list_tools(&self) -> Vec<String> {
+        let executors = self.executors.read().await;
+        executors.keys().cloned().collect()
+    }
+
+    /// List registered tools in `category`
+    pub async fn list_tools_by_category(&self, category: ToolCategory) -> Vec<String> {
+        let executors = self.executors.read().await;
+        executors
+            .iter()
+            .filter(|(_, executor)| executor.category() == category)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::MemoryCacheStore;
+    use crate::propagation::TRACEPARENT_KEY;
+    use std::sync::atomic::AtomicU32;
+
+    struct MockExecutor {
+        name: String,
+    }
+
+    #[async_trait]
+    impl ToolExecutor for MockExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(serde_json::json!({"executed": true})),
+                error: None,
+                duration_ms: 10,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_executor_registration() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+
+        let executor = Arc::new(MockExecutor {
+            name: "test-tool".to_string(),
+        });
+
+        orchestrator.register_executor(executor).await.unwrap();
+
+        let tools = orchestrator.list_tools().await;
+        assert!(tools.contains(&"test-tool".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_result_encrypted_to_client_key() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "test-tool".to_string(),
+            }))
+            .await
+            .unwrap();
+
+        let secret = x25519_dalek::StaticSecret::random_from_rng(rand::rngs::OsRng);
+        let mut call = test_call("test-tool");
+        call.encrypt_to = Some(PublicKey::from(&secret));
+        let response = orchestrator.execute_tool(call).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+
+        let sealed: EncryptedResult = serde_json::from_value(response.result.unwrap()).unwrap();
+        assert_eq!(
+            sealed.open(&secret).unwrap(),
+            serde_json::json!({"executed": true})
+        );
+
+        let plain = orchestrator
+            .execute_tool(test_call("test-tool"))
+            .await
+            .unwrap();
+        assert_eq!(plain.result, Some(serde_json::json!({"executed": true})));
+    }
+
+    struct WarmupExecutor {
+        synthetic_calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ToolExecutor for WarmupExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            if call
+                .context
+                .metadata
+                .get(SYNTHETIC_CALL_KEY)
+                .map(String::as_str)
+                == Some("true")
+            {
+                self.synthetic_calls.fetch_add(1, Ordering::SeqCst);
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(5)).await;
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: None,
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "warm-tool"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+    }
+
+    /// Consent proof the mock engine expects from `user_id` for a record
+    /// committed in `tx_hash`
+    fn mock_proof(tx_hash: &str, user_id: &str) -> String {
+        let subject = cybulous_crypto::hash_data(user_id);
+        cybulous_crypto::hash_data(&format!("{}:21:{}", tx_hash, subject))
+    }
+
+    fn test_call(tool_name: &str) -> ToolCall {
+        ToolCall {
+            id: Uuid::new_v4(),
+            tool_name: tool_name.to_string(),
+            parameters: serde_json::json!({}),
+            user_id: "test-user".to_string(),
+            context: ExecutionContext::new(Uuid::new_v4(), mock_proof("mock-tx-hash", "test-user")),
+            timeout_ms: 1000,
+            queue_timeout_ms: None,
+            execution_timeout_ms: None,
+            encrypt_to: None,
+            tool_version: None,
+            required_capability: None,
+            idempotency_key: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warmup_marks_executor_ready() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        let executor = Arc::new(WarmupExecutor {
+            synthetic_calls: AtomicUsize::new(0),
+        });
+
+        let warming = tokio::spawn({
+            let orchestrator = orchestrator.clone();
+            let executor = executor.clone();
+            async move {
+                orchestrator
+                    .register_executor_with_warmup(executor, test_call("ignored"))
+                    .await
+            }
+        });
+        // Registered but not ready until the warmup succeeds
+        while !orchestrator
+            .executors
+            .read()
+            .await
+            .contains_key("warm-tool")
+        {
+            tokio::task::yield_now().await;
+        }
+        assert!(!orchestrator.is_ready("warm-tool").await);
+        let ready = warming.await.unwrap().unwrap();
+
+        assert!(ready);
+        assert!(orchestrator.is_ready("warm-tool").await);
+        assert_eq!(executor.synthetic_calls.load(Ordering::SeqCst), 1);
+
+        // A zero timeout means the default, not an immediate timeout
+        let mut warmup = test_call("ignored");
+        warmup.timeout_ms = 0;
+        let ready = orchestrator
+            .register_executor_with_warmup(executor.clone(), warmup)
+            .await
+            .unwrap();
+        assert!(ready);
+        assert_eq!(executor.synthetic_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_causality_chain_reconstruction() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "test-tool".to_string(),
+            }))
+            .await
+            .unwrap();
+
+        let root = test_call("test-tool");
+        let child = root.derive_child("test-tool", serde_json::json!({}));
+        let grandchild = child.derive_child("test-tool", serde_json::json!({}));
+
+        assert_eq!(grandchild.context.root_call_id, Some(root.id));
+        assert_eq!(grandchild.context.parent_call_id, Some(child.id));
+
+        for call in [root.clone(), child.clone(), grandchild.clone()] {
+            orchestrator.execute_tool(call).await.unwrap();
+        }
+
+        let chain: Vec<Uuid> = orchestrator
+            .causality_chain(grandchild.id)
+            .await
+            .iter()
+            .map(|r| r.call_id)
+            .collect();
+        assert_eq!(chain, vec![root.id, child.id, grandchild.id]);
+    }
+
+    #[tokio::test]
+    async fn test_group_shares_rate_limit() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        for name in ["search", "lookup"] {
+            orchestrator
+                .register_executor(Arc::new(MockExecutor {
+                    name: name.to_string(),
+                }))
+                .await
+                .unwrap();
+        }
+
+        orchestrator
+            .define_group(
+                "catalog",
+                GroupPolicy {
+                    rate_limit: Some(crate::rate_limit::RateLimit {
+                        max_calls: 3,
+                        window: std::time::Duration::from_secs(60),
+                    }),
+                    circuit_breaker: None,
+                },
+            )
+            .await;
+        orchestrator
+            .assign_to_group("search", "catalog")
+            .await
+            .unwrap();
+        orchestrator
+            .assign_to_group("lookup", "catalog")
+            .await
+            .unwrap();
+
+        for tool in ["search", "lookup", "search"] {
+            assert!(orchestrator.execute_tool(test_call(tool)).await.is_ok());
+        }
+        let limited = orchestrator.execute_tool(test_call("lookup")).await;
+        assert!(matches!(limited, Err(CybulousError::RateLimited(_))));
+    }
+
+    #[tokio::test]
+    async fn test_forced_circuit_short_circuits_until_closed() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "test-tool".to_string(),
+            }))
+            .await
+            .unwrap();
+        orchestrator
+            .define_group(
+                "backend",
+                GroupPolicy {
+                    rate_limit: None,
+                    circuit_breaker: Some(crate::circuit::CircuitBreakerConfig {
+                        failure_threshold: 5,
+                        reset_timeout: std::time::Duration::from_millis(1),
+                    }),
+                },
+            )
+            .await;
+        orchestrator
+            .assign_to_group("test-tool", "backend")
+            .await
+            .unwrap();
+        assert_eq!(
+            orchestrator.circuit_state("test-tool").await,
+            Some(CircuitState::Closed)
+        );
+
+        orchestrator.force_open("test-tool").await.unwrap();
+        // Forced circuits ignore the reset timeout
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        assert_eq!(
+            orchestrator.circuit_state("test-tool").await,
+            Some(CircuitState::Open)
+        );
+        let rejected = orchestrator.execute_tool(test_call("test-tool")).await;
+        assert!(matches!(rejected, Err(CybulousError::CircuitOpen(_))));
+
+        orchestrator.force_close("test-tool").await.unwrap();
+        let resumed = orchestrator
+            .execute_tool(test_call("test-tool"))
+            .await
+            .unwrap();
+        assert_eq!(resumed.status, ExecutionStatus::Success);
+
+        let events = orchestrator.audit_log().circuit_events().await;
+        let states: Vec<_> = events.iter().map(|e| e.state).collect();
+        assert_eq!(states, vec![CircuitState::Open, CircuitState::Closed]);
+        assert!(orchestrator.force_open("unknown").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_forcing_prefers_the_tools_own_breaker() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "test-tool".to_string(),
+            }))
+            .await
+            .unwrap();
+        orchestrator
+            .set_tool_circuit_breaker("test-tool", CircuitBreakerConfig::default())
+            .await;
+
+        orchestrator.force_open("test-tool").await.unwrap();
+        assert_eq!(
+            orchestrator.circuit_state("test-tool").await,
+            Some(CircuitState::Open)
+        );
+        let rejected = orchestrator.execute_tool(test_call("test-tool")).await;
+        assert!(matches!(rejected, Err(CybulousError::CircuitOpen(_))));
+
+        orchestrator.force_close("test-tool").await.unwrap();
+        assert_eq!(
+            orchestrator.circuit_state("test-tool").await,
+            Some(CircuitState::Closed)
+        );
+        let events = orchestrator.audit_log().circuit_events().await;
+        assert!(events.iter().all(|e| e.group.is_none()));
+    }
+
+    struct FailingExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for FailingExecutor {
+        async fn execute(&self, _call: &ToolCall) -> Result<ToolResponse> {
+            Err(CybulousError::OrchestrationFailed(
+                "backend down".to_string(),
+            ))
+        }
+
+        fn name(&self) -> &str {
+            "flaky-tool"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn test_system_health_degrades_on_unready_executor() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "test-tool".to_string(),
+            }))
+            .await
+            .unwrap();
+        orchestrator
+            .execute_tool(test_call("test-tool"))
+            .await
+            .unwrap();
+
+        let health = orchestrator.system_health().await;
+        assert_eq!(health.status, HealthStatus::Healthy);
+        assert!(health.consent_reachable);
+        assert_eq!(health.inflight, 0);
+        assert_eq!(health.error_rates.get("test-tool"), Some(&0.0));
+
+        let ready = orchestrator
+            .register_executor_with_warmup(Arc::new(FailingExecutor), test_call("flaky-tool"))
+            .await
+            .unwrap();
+        assert!(!ready);
+
+        let health = orchestrator.system_health().await;
+        assert_eq!(health.status, HealthStatus::Degraded);
+        assert_eq!(health.executors.get("flaky-tool"), Some(&false));
+    }
+
+    #[tokio::test]
+    async fn test_injected_faults_trip_circuit_breaker() {
+        use crate::circuit::CircuitBreakerConfig;
+        use crate::fault::FaultConfig;
+
+        let injector = Arc::new(crate::fault::FaultInjector::new(7));
+        injector.configure(
+            "test-tool",
+            FaultConfig {
+                error_rate: 1.0,
+                ..FaultConfig::default()
+            },
+        );
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator =
+            Orchestrator::new(consent_engine, 10).with_fault_injector(injector.clone());
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "test-tool".to_string(),
+            }))
+            .await
+            .unwrap();
+        orchestrator
+            .define_group(
+                "chaos",
+                GroupPolicy {
+                    rate_limit: None,
+                    circuit_breaker: Some(CircuitBreakerConfig {
+                        failure_threshold: 3,
+                        reset_timeout: std::time::Duration::from_secs(60),
+                    }),
+                },
+            )
+            .await;
+        orchestrator
+            .assign_to_group("test-tool", "chaos")
+            .await
+            .unwrap();
+
+        for _ in 0..3 {
+            let response = orchestrator
+                .execute_tool(test_call("test-tool"))
+                .await
+                .unwrap();
+            assert_eq!(response.status, ExecutionStatus::Failed);
+        }
+
+        injector.clear("test-tool");
+        let rejected = orchestrator.execute_tool(test_call("test-tool")).await;
+        assert!(matches!(rejected, Err(CybulousError::CircuitOpen(_))));
+    }
+
+    struct ScoringExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for ScoringExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(call.parameters.clone()),
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "scorer"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+
+        fn postconditions(&self, _call: &ToolCall, _response: &ToolResponse) -> Vec<Postcondition> {
+            vec![
+                Postcondition::FieldPresent("/label".to_string()),
+                Postcondition::InRange {
+                    pointer: "/score".to_string(),
+                    min: 0.0,
+                    max: 1.0,
+                },
+            ]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_postcondition_violation_fails_response() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(ScoringExecutor))
+            .await
+            .unwrap();
+
+        let mut call = test_call("scorer");
+        call.parameters = serde_json::json!({"label": "ok", "score": 0.4});
+        let response = orchestrator.execute_tool(call).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+
+        let mut call = test_call("scorer");
+        call.parameters = serde_json::json!({"label": "bad", "score": 1.7});
+        let response = orchestrator.execute_tool(call).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Failed);
+        assert!(response.error.unwrap().contains("/score"));
+    }
+
+    #[tokio::test]
+    async fn test_emergency_override_permits_call() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine.clone(), 10);
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "test-tool".to_string(),
+            }))
+            .await
+            .unwrap();
+
+        let mut call = test_call("test-tool");
+        call.context.consent_proof = "not-a-valid-proof".to_string();
+        assert!(orchestrator.execute_tool(call.clone()).await.is_err());
+
+        let token = consent_engine
+            .emergency_override("op-1", "test-user", "patient unresponsive")
+            .await
+            .unwrap();
+        call.context
+            .metadata
+            .insert(EMERGENCY_OVERRIDE_KEY.to_string(), token.token);
+        let response = orchestrator.execute_tool(call).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+    }
+
+    #[tokio::test]
+    async fn test_capability_token_scopes_tools() {
+        use crate::capability::CapabilityClaims;
+        use ed25519_dalek::SigningKey;
+
+        let issuer = SigningKey::from_bytes(&[7u8; 32]);
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator =
+            Orchestrator::new(consent_engine, 10).with_capability_issuer(issuer.verifying_key());
+        for name in ["search", "delete"] {
+            orchestrator
+                .register_executor(Arc::new(MockExecutor {
+                    name: name.to_string(),
+                }))
+                .await
+                .unwrap();
+        }
+
+        let token = CapabilityToken::sign(
+            &CapabilityClaims {
+                subject: "client-a".to_string(),
+                tools: vec!["search".to_string()],
+                capabilities: Vec::new(),
+                expires_at: chrono::Utc::now() + chrono::Duration::minutes(5),
+            },
+            &issuer,
+        )
+        .unwrap();
+        let with_token = |tool: &str| {
+            let mut call = test_call(tool);
+            call.context
+                .metadata
+                .insert(CAPABILITY_TOKEN_KEY.to_string(), token.as_str().to_string());
+            call
+        };
+
+        let response = orchestrator
+            .execute_tool(with_token("search"))
+            .await
+            .unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+
+        let denied = orchestrator.execute_tool(with_token("delete")).await;
+        assert!(matches!(denied, Err(CybulousError::AccessDenied(_))));
+
+        let missing = orchestrator.execute_tool(test_call("search")).await;
+        assert!(matches!(missing, Err(CybulousError::AccessDenied(_))));
+    }
+
+    #[tokio::test]
+    async fn test_service_signature_enforced() {
+        use crate::signing::sign_call;
+        use ed25519_dalek::SigningKey;
+
+        let service_key = SigningKey::from_bytes(&[9u8; 32]);
+        let keyring = ServiceKeyring::new(SigningMode::Enforce)
+            .with_service("billing", service_key.verifying_key());
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10).with_service_keyring(keyring);
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "search".to_string(),
+            }))
+            .await
+            .unwrap();
+
+        let mut signed = test_call("search");
+        sign_call(&mut signed, "billing", &service_key).unwrap();
+        let response = orchestrator.execute_tool(signed.clone()).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+
+        // Volatile fields may change without invalidating the signature
+        let mut retimed = signed.clone();
+        retimed.timeout_ms = 5000;
+        assert!(orchestrator.execute_tool(retimed).await.is_ok());
+
+        let mut tampered = signed;
+        tampered.parameters = serde_json::json!({"query": "everything"});
+        let denied = orchestrator.execute_tool(tampered).await;
+        assert!(matches!(denied, Err(CybulousError::AccessDenied(_))));
+
+        let unsigned = orchestrator.execute_tool(test_call("search")).await;
+        assert!(matches!(unsigned, Err(CybulousError::AccessDenied(_))));
+    }
+
+    #[tokio::test]
+    async fn test_failures_emit_sla_breach() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(FailingExecutor))
+            .await
+            .unwrap();
+        orchestrator
+            .set_sla(
+                "flaky-tool",
+                SlaTarget {
+                    p95_latency: std::time::Duration::from_millis(200),
+                    min_success_rate: 0.99,
+                },
+            )
+            .await;
+        let mut breaches = orchestrator.subscribe_sla_breaches();
+
+        orchestrator
+            .execute_tool(test_call("flaky-tool"))
+            .await
+            .unwrap();
+
+        let breach = breaches.try_recv().unwrap();
+        assert_eq!(breach.tool_name, "flaky-tool");
+        let status = orchestrator.sla_status("flaky-tool").await.unwrap();
+        assert!(status.in_breach());
+        assert_eq!(status.success_rate, 0.0);
+        assert!(orchestrator.sla_status("test-tool").await.is_none());
+    }
+
+    struct SessionExecutor {
+        session: usize,
+    }
+
+    #[async_trait]
+    impl ToolExecutor for SessionExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(serde_json::json!({"session": self.session})),
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "model"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            true
+        }
+    }
+
+    struct SessionFactory {
+        created: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl crate::warm_pool::InstanceFactory for SessionFactory {
+        async fn create(&self) -> Result<Arc<dyn ToolExecutor>> {
+            let session = self.created.fetch_add(1, Ordering::SeqCst);
+            Ok(Arc::new(SessionExecutor { session }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warm_pool_reuses_warm_instance() {
+        use crate::warm_pool::WarmPoolConfig;
+
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        let factory = Arc::new(SessionFactory {
+            created: AtomicUsize::new(0),
+        });
+        let pool = Arc::new(WarmPool::new(
+            "model",
+            vec!["inference".to_string()],
+            factory.clone(),
+            WarmPoolConfig {
+                min_idle: 1,
+                max_size: 2,
+                idle_timeout: std::time::Duration::from_secs(60),
+            },
+        ));
+        orchestrator.register_warm_pool(pool.clone()).await.unwrap();
+        assert_eq!(pool.idle_count(), 1);
+
+        for _ in 0..3 {
+            let response = orchestrator.execute_tool(test_call("model")).await.unwrap();
+            assert_eq!(response.result, Some(serde_json::json!({"session": 0})));
+        }
+        assert_eq!(factory.created.load(Ordering::SeqCst), 1);
+        assert_eq!(pool.idle_count(), 1);
+    }
+
+    struct InventoryExecutor {
+        runs: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ToolExecutor for InventoryExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            let result = match self.runs.fetch_add(1, Ordering::SeqCst) {
+                0 => serde_json::json!({"apples": 3, "pears": 1, "owner": "ada"}),
+                _ => serde_json::json!({"apples": 5, "plums": 2, "owner": "ada"}),
+            };
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(result),
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "inventory"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_and_diff_reports_field_changes() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(InventoryExecutor {
+                runs: AtomicUsize::new(0),
+            }))
+            .await
+            .unwrap();
+
+        let previous = orchestrator
+            .execute_tool(test_call("inventory"))
+            .await
+            .unwrap();
+        let (response, diff) = orchestrator
+            .execute_and_diff(test_call("inventory"), &previous)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, ExecutionStatus::Success);
+        assert_eq!(diff.added["/plums"], serde_json::json!(2));
+        assert_eq!(diff.removed["/pears"], serde_json::json!(1));
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed["/apples"].before, serde_json::json!(3));
+        assert_eq!(diff.changed["/apples"].after, serde_json::json!(5));
+    }
+
+    struct VaultExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for VaultExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: None,
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "vault"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            true
+        }
+
+        fn min_assurance(&self) -> u8 {
+            2
+        }
+    }
+
+    #[tokio::test]
+    async fn test_min_assurance_rejects_low_assurance_consent() {
+        let call_as = |user: &str, tool: &str| {
+            let mut call = test_call(tool);
+            call.user_id = user.to_string();
+            call.context.consent_proof = mock_proof(&format!("tx-hash-{}", user), user);
+            call
+        };
+
+        for (assurance, vault_allowed) in [(1, false), (2, true)] {
+            let consent_engine =
+                cybulous_consent::ConsentEngine::mock().with_provider_assurance(assurance);
+            consent_engine.request_consent("holder").await.unwrap();
+            let orchestrator = Orchestrator::new(Arc::new(consent_engine), 10);
+            orchestrator
+                .register_executor(Arc::new(VaultExecutor))
+                .await
+                .unwrap();
+            orchestrator
+                .register_executor(Arc::new(MockExecutor {
+                    name: "test-tool".to_string(),
+                }))
+                .await
+                .unwrap();
+
+            let vault = orchestrator.execute_tool(call_as("holder", "vault")).await;
+            assert_eq!(
+                vault.is_ok(),
+                vault_allowed,
+                "assurance {}: {:?}",
+                assurance,
+                vault
+            );
+            if !vault_allowed {
+                assert!(matches!(vault, Err(CybulousError::ConsentError(_))));
+            }
+            let basic = orchestrator
+                .execute_tool(call_as("holder", "test-tool"))
+                .await
+                .unwrap();
+            assert_eq!(basic.status, ExecutionStatus::Success);
+        }
+    }
+
+    struct ConvertExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for ConvertExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(call.parameters.clone()),
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "convert"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            true
+        }
+
+        fn required_parameters(&self) -> Vec<String> {
+            vec!["unit".to_string()]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transform_default_satisfies_required_parameter() {
+        use crate::transform::DefaultParameters;
+
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(ConvertExecutor))
+            .await
+            .unwrap();
+
+        let rejected = orchestrator.execute_tool(test_call("convert")).await;
+        assert!(matches!(rejected, Err(CybulousError::InvalidParameters(_))));
+
+        orchestrator
+            .add_tool_request_transform(
+                "convert",
+                Arc::new(DefaultParameters::new().with("unit", serde_json::json!("kg"))),
+            )
+            .await;
+        let mut call = test_call("convert");
+        call.parameters = serde_json::json!({"value": 3});
+        let response = orchestrator.execute_tool(call).await.unwrap();
+        assert_eq!(
+            response.result,
+            Some(serde_json::json!({"value": 3, "unit": "kg"}))
+        );
+    }
+
+    struct LedgerExecutor {
+        actions: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl ToolExecutor for LedgerExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            let action = call.parameters["action"].as_str().unwrap_or_default();
+            if call.parameters["fail"] == serde_json::json!(true) {
+                return Err(CybulousError::OrchestrationFailed(format!(
+                    "{} rejected",
+                    action
+                )));
+            }
+            self.actions.lock().unwrap().push(action.to_string());
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: None,
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "ledger"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_saga_compensates_completed_steps_in_reverse() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        let ledger = Arc::new(LedgerExecutor {
+            actions: std::sync::Mutex::new(Vec::new()),
+        });
+        orchestrator
+            .register_executor(ledger.clone())
+            .await
+            .unwrap();
+
+        let action = |name: &str, fail: bool| {
+            SagaAction::new("ledger", serde_json::json!({"action": name, "fail": fail}))
+        };
+        let saga = Saga::new()
+            .step(
+                "reserve",
+                action("reserve", false),
+                action("release", false),
+            )
+            .step("debit", action("debit", false), action("refund", false))
+            .step("ship", action("ship", true), action("recall", false));
+
+        let outcome = orchestrator.execute_saga(&saga, &test_call("ledger")).await;
+
+        assert!(!outcome.is_committed());
+        assert!(outcome.is_rolled_back());
+        assert_eq!(outcome.completed, vec!["reserve", "debit"]);
+        assert_eq!(outcome.failed_step.as_deref(), Some("ship"));
+        assert_eq!(outcome.compensated, vec!["debit", "reserve"]);
+        assert_eq!(
+            *ledger.actions.lock().unwrap(),
+            vec!["reserve", "debit", "refund", "release"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_workflow_reports_node_diagnostics() {
+        use crate::workflow::WorkflowNode;
+
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "search".to_string(),
+            }))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(ForecastExecutor))
+            .await
+            .unwrap();
+
+        let workflow = Workflow {
+            nodes: vec![
+                WorkflowNode {
+                    id: "fetch".to_string(),
+                    tool_name: "search".to_string(),
+                    parameters: serde_json::json!({"query": "rust"}),
+                    depends_on: Vec::new(),
+                    on_failure: FailureMode::Abort,
+                },
+                WorkflowNode {
+                    id: "outlook".to_string(),
+                    tool_name: "forecast".to_string(),
+                    parameters: serde_json::json!({"city": "Oslo", "days": 30}),
+                    depends_on: Vec::new(),
+                    on_failure: FailureMode::Abort,
+                },
+                WorkflowNode {
+                    id: "summarize".to_string(),
+                    tool_name: "summarizer".to_string(),
+                    parameters: serde_json::json!({"text": "{{fetcher.result}}"}),
+                    depends_on: vec!["fetch".to_string()],
+                    on_failure: FailureMode::Abort,
+                },
+            ],
+        };
+        let context = test_call("search").context;
+
+        let validation = orchestrator
+            .validate_workflow(&workflow, "test-user", &context)
+            .await;
+        assert!(!validation.is_valid());
+        assert!(validation.diagnostics.is_empty());
+        assert!(validation.nodes["fetch"].is_empty());
+        assert_eq!(validation.nodes["outlook"].len(), 1);
+        assert!(validation.nodes["outlook"][0].contains("days"));
+        let problems = &validation.nodes["summarize"];
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("unknown node"));
+        assert!(problems[1].contains("unknown tool summarizer"));
+
+        // Each node is probed with its own tool's consent requirements
+        orchestrator.tool_policies.write().await.insert(
+            "search".to_string(),
+            ToolPolicy {
+                required_scopes: vec!["search".to_string()],
+                timeout: None,
+            },
+        );
+        let validation = orchestrator
+            .validate_workflow(&workflow, "test-user", &context)
+            .await;
+        assert_eq!(validation.nodes["fetch"].len(), 1);
+        assert!(validation.nodes["fetch"][0].contains("scope search"));
+        assert_eq!(validation.nodes["outlook"].len(), 1);
+
+        let mut bad_proof = context.clone();
+        bad_proof.consent_proof = "forged".to_string();
+        let validation = orchestrator
+            .validate_workflow(&workflow, "test-user", &bad_proof)
+            .await;
+        assert!(validation.nodes["fetch"][0].contains("Consent verification failed"));
+        assert!(validation.nodes["outlook"][1].contains("Consent verification failed"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_workflow_spends_no_consent_uses() {
+        use crate::workflow::WorkflowNode;
+
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        consent_engine
+            .limit_uses("test-user", Some(2))
+            .await
+            .unwrap();
+        let orchestrator = Orchestrator::new(consent_engine.clone(), 10);
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "search".to_string(),
+            }))
+            .await
+            .unwrap();
+
+        let workflow = Workflow {
+            nodes: (0..3)
+                .map(|i| WorkflowNode {
+                    id: format!("fetch-{}", i),
+                    tool_name: "search".to_string(),
+                    parameters: serde_json::json!({"query": "rust"}),
+                    depends_on: Vec::new(),
+                    on_failure: FailureMode::Abort,
+                })
+                .collect(),
+        };
+        let context = test_call("search").context;
+
+        for _ in 0..2 {
+            let validation = orchestrator
+                .validate_workflow(&workflow, "test-user", &context)
+                .await;
+            assert!(validation.is_valid());
+        }
+        assert_eq!(
+            consent_engine.remaining_uses("test-user").await.unwrap(),
+            Some(2)
+        );
+    }
+
+    /// Returns its parameters as its result
+    struct EchoExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for EchoExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(call.parameters.clone()),
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_workflow_passes_outputs_and_applies_failure_mode() {
+        use crate::workflow::WorkflowNode;
+
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(EchoExecutor))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(FailingExecutor))
+            .await
+            .unwrap();
+
+        let node =
+            |id: &str, tool: &str, parameters, depends_on: &[&str], on_failure| WorkflowNode {
+                id: id.to_string(),
+                tool_name: tool.to_string(),
+                parameters,
+                depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+                on_failure,
+            };
+        let workflow = |on_failure| Workflow {
+            nodes: vec![
+                node(
+                    "fetch",
+                    "echo",
+                    serde_json::json!({"title": "rust"}),
+                    &[],
+                    FailureMode::Abort,
+                ),
+                node(
+                    "summarize",
+                    "echo",
+                    serde_json::json!({"text": "{{fetch.result.title}}"}),
+                    &["fetch"],
+                    FailureMode::Abort,
+                ),
+                node(
+                    "publish",
+                    "flaky-tool",
+                    serde_json::json!({}),
+                    &["fetch"],
+                    on_failure,
+                ),
+                node(
+                    "notify",
+                    "echo",
+                    serde_json::json!({}),
+                    &["publish"],
+                    FailureMode::Abort,
+                ),
+                node(
+                    "audit",
+                    "echo",
+                    serde_json::json!({}),
+                    &[],
+                    FailureMode::Abort,
+                ),
+            ],
+        };
+        let parent = test_call("echo");
+
+        let run = orchestrator
+            .execute_workflow(&workflow(FailureMode::Continue), &parent)
+            .await
+            .unwrap();
+        assert_eq!(
+            run.outputs["summarize"].result,
+            Some(serde_json::json!({"text": "rust"}))
+        );
+        assert!(run.failures["publish"].contains("backend down"));
+        assert_eq!(run.skipped, vec!["notify"]);
+        assert!(run.outputs.contains_key("audit"));
+        assert!(!run.aborted);
+
+        let run = orchestrator
+            .execute_workflow(&workflow(FailureMode::Abort), &parent)
+            .await
+            .unwrap();
+        assert!(run.aborted);
+        assert_eq!(run.skipped, vec!["notify", "audit"]);
+        assert!(!run.is_success());
+
+        let mut cyclic = workflow(FailureMode::Abort);
+        cyclic.nodes[0].depends_on.push("summarize".to_string());
+        let rejected = orchestrator.execute_workflow(&cyclic, &parent).await;
+        assert!(matches!(rejected, Err(CybulousError::InvalidParameters(_))));
+    }
+
+    /// Streams `chunks` numbered chunks, `delay_ms` apart
+    struct ChunkedExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for ChunkedExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: None,
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        async fn execute_stream(
+            &self,
+            call: &ToolCall,
+        ) -> Result<BoxStream<'static, Result<serde_json::Value>>> {
+            let chunks = call.parameters["chunks"].as_u64().unwrap_or(0);
+            let delay =
+                std::time::Duration::from_millis(call.parameters["delay_ms"].as_u64().unwrap_or(0));
+            Ok(stream::iter(0..chunks)
+                .then(move |n| async move {
+                    tokio::time::sleep(delay).await;
+                    Ok(serde_json::json!({ "token": n }))
+                })
+                .boxed())
+        }
+
+        fn name(&self) -> &str {
+            "generate"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_yields_chunks_then_finishes() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(ChunkedExecutor))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "search".to_string(),
+            }))
+            .await
+            .unwrap();
+
+        let mut call = test_call("generate");
+        call.parameters = serde_json::json!({ "chunks": 3, "delay_ms": 1 });
+        let events: Vec<_> = orchestrator
+            .execute_tool_streaming(call)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(events.len(), 4);
+        for (n, event) in events[..3].iter().enumerate() {
+            let StreamEvent::Chunk(chunk) = event else {
+                panic!("expected chunk, got {:?}", event);
+            };
+            assert_eq!(chunk.sequence, n as u64);
+            assert_eq!(chunk.data, serde_json::json!({ "token": n }));
+        }
+        assert!(matches!(
+            &events[3],
+            StreamEvent::Finished(r) if r.status == ExecutionStatus::Success
+        ));
+
+        // Non-streaming tools yield their result as one chunk
+        let events: Vec<_> = orchestrator
+            .execute_tool_streaming(test_call("search"))
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert!(matches!(
+            &events[0],
+            StreamEvent::Chunk(c) if c.data == serde_json::json!({"executed": true})
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_ends_stream() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(ChunkedExecutor))
+            .await
+            .unwrap();
+
+        let mut call = test_call("generate");
+        call.parameters = serde_json::json!({ "chunks": 100, "delay_ms": 20 });
+        let call_id = call.id;
+        let mut events = orchestrator.execute_tool_streaming(call).await.unwrap();
+        assert!(matches!(events.next().await, Some(StreamEvent::Chunk(_))));
+
+        assert!(
+            orchestrator
+                .cancel(call_id, CancellationReason::ClientCancelled)
+                .await
+        );
+        let rest: Vec<_> = events.collect().await;
+        assert!(matches!(
+            rest.last(),
+            Some(StreamEvent::Finished(r))
+                if r.status == ExecutionStatus::Cancelled(CancellationReason::ClientCancelled)
+        ));
+        assert!(
+            !orchestrator
+                .cancel(call_id, CancellationReason::ClientCancelled)
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_streaming_applies_consent_and_timeout() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(ChunkedExecutor))
+            .await
+            .unwrap();
+
+        let mut forged = test_call("generate");
+        forged.context.consent_proof = "forged".to_string();
+        let denied = orchestrator.execute_tool_streaming(forged).await;
+        assert!(matches!(denied, Err(CybulousError::ConsentError(_))));
+
+        let mut slow = test_call("generate");
+        slow.timeout_ms = 50;
+        slow.parameters = serde_json::json!({ "chunks": 10, "delay_ms": 20 });
+        let call_id = slow.id;
+        let events: Vec<_> = orchestrator
+            .execute_tool_streaming(slow)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        let chunks = events
+            .iter()
+            .filter(|e| matches!(e, StreamEvent::Chunk(_)))
+            .count();
+        assert!((1..10).contains(&chunks), "{} chunks", chunks);
+        assert!(matches!(
+            events.last(),
+            Some(StreamEvent::Finished(r)) if r.status == ExecutionStatus::Timeout
+        ));
+        assert_eq!(
+            orchestrator.audit_log().get(call_id).await.unwrap().status,
+            ExecutionStatus::Timeout
+        );
+    }
+
+    /// Appends `label:pre`/`label:post` to a shared log; redacts passwords
+    /// and rejects calls flagged `blocked`
+    struct LoggingMiddleware {
+        label: &'static str,
+        log: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl OrchestratorMiddleware for LoggingMiddleware {
+        async fn pre_execute(&self, call: &mut ToolCall) -> Result<()> {
+            self.log.lock().unwrap().push(format!("{}:pre", self.label));
+            if call.parameters.get("blocked").is_some() {
+                return Err(CybulousError::AccessDenied("blocked".to_string()));
+            }
+            if let Some(password) = call.parameters.get_mut("password") {
+                *password = serde_json::json!("[redacted]");
+            }
+            Ok(())
+        }
+
+        async fn post_execute(&self, _call: &ToolCall, response: &ToolResponse) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{}:post:{:?}", self.label, response.status));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_wraps_execution_and_can_reject() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(EchoExecutor))
+            .await
+            .unwrap();
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        for label in ["outer", "inner"] {
+            orchestrator
+                .add_middleware(Arc::new(LoggingMiddleware {
+                    label,
+                    log: log.clone(),
+                }))
+                .await;
+        }
+
+        let mut call = test_call("echo");
+        call.parameters = serde_json::json!({ "user": "ana", "password": "hunter2" });
+        let response = orchestrator.execute_tool(call).await.unwrap();
+        assert_eq!(
+            response.result,
+            Some(serde_json::json!({ "user": "ana", "password": "[redacted]" }))
+        );
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "outer:pre",
+                "inner:pre",
+                "inner:post:Success",
+                "outer:post:Success"
+            ]
+        );
+
+        log.lock().unwrap().clear();
+        let mut blocked = test_call("echo");
+        blocked.parameters = serde_json::json!({ "blocked": true });
+        let call_id = blocked.id;
+        let rejected = orchestrator.execute_tool(blocked).await;
+        assert!(matches!(rejected, Err(CybulousError::AccessDenied(_))));
+        assert_eq!(*log.lock().unwrap(), ["outer:pre"]);
+        assert_eq!(
+            orchestrator.audit_log().get(call_id).await.unwrap().status,
+            ExecutionStatus::Failed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_middleware_wraps_streamed_calls() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(EchoExecutor))
+            .await
+            .unwrap();
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        for label in ["outer", "inner"] {
+            orchestrator
+                .add_middleware(Arc::new(LoggingMiddleware {
+                    label,
+                    log: log.clone(),
+                }))
+                .await;
+        }
+
+        let mut call = test_call("echo");
+        call.parameters = serde_json::json!({ "password": "hunter2" });
+        let events: Vec<_> = orchestrator
+            .execute_tool_streaming(call)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert!(matches!(
+            &events[0],
+            StreamEvent::Chunk(chunk) if chunk.data == serde_json::json!({ "password": "[redacted]" })
+        ));
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "outer:pre",
+                "inner:pre",
+                "inner:post:Success",
+                "outer:post:Success"
+            ]
+        );
+
+        log.lock().unwrap().clear();
+        let mut blocked = test_call("echo");
+        blocked.parameters = serde_json::json!({ "blocked": true });
+        let rejected = orchestrator.execute_tool_streaming(blocked).await;
+        assert!(matches!(rejected, Err(CybulousError::AccessDenied(_))));
+        assert_eq!(*log.lock().unwrap(), ["outer:pre"]);
+    }
+
+    #[tokio::test]
+    async fn test_execute_batch_reports_partial_results() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 2);
+        orchestrator
+            .register_executor(Arc::new(EchoExecutor))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(FailingExecutor))
+            .await
+            .unwrap();
+
+        let calls: Vec<_> = (0..5)
+            .map(|i| {
+                let mut call = test_call("echo");
+                call.parameters = serde_json::json!({ "n": i });
+                call
+            })
+            .chain([test_call("flaky-tool"), test_call("missing")])
+            .collect();
+        let ids: Vec<_> = calls.iter().map(|c| c.id).collect();
+
+        let batch = orchestrator.execute_batch(calls).await;
+        assert_eq!(batch.len(), 7);
+        assert_eq!(batch.succeeded(), 5);
+        assert!(!batch.is_success());
+        assert_eq!(
+            batch.get(ids[3]).unwrap().result,
+            Some(serde_json::json!({ "n": 3 }))
+        );
+        assert_eq!(batch.status(ids[5]), Some(ExecutionStatus::Failed));
+        let missing = batch.get(ids[6]).unwrap();
+        assert_eq!(missing.status, ExecutionStatus::Failed);
+        assert!(missing.error.as_deref().unwrap().contains("missing"));
+        let mut failed = batch.failed();
+        failed.sort();
+        let mut expected = vec![ids[5], ids[6]];
+        expected.sort();
+        assert_eq!(failed, expected);
+    }
+
+    struct VersionedExecutor(&'static str);
+
+    #[async_trait]
+    impl ToolExecutor for VersionedExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(serde_json::json!({ "version": self.0 })),
+                error: None,
+                duration_ms: 1,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "versioned"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            true
+        }
+
+        fn version(&self) -> Option<Version> {
+            Some(Version::parse(self.0).unwrap())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_calls_resolve_pinned_tool_versions() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        for version in ["2.0.0", "1.0.0", "1.4.2"] {
+            orchestrator
+                .register_executor(Arc::new(VersionedExecutor(version)))
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            orchestrator.tool_versions("versioned").await,
+            ["1.0.0", "1.4.2", "2.0.0"].map(|v| Version::parse(v).unwrap())
+        );
+
+        let resolved = |req: Option<&str>| {
+            let mut call = test_call("versioned");
+            if let Some(req) = req {
+                call = call.with_version(VersionReq::parse(req).unwrap());
+            }
+            let orchestrator = orchestrator.clone();
+            async move {
+                orchestrator
+                    .execute_tool(call)
+                    .await
+                    .map(|r| r.result.unwrap()["version"].as_str().unwrap().to_string())
+            }
+        };
+        assert_eq!(resolved(None).await.unwrap(), "2.0.0");
+        assert_eq!(resolved(Some("1")).await.unwrap(), "1.4.2");
+        assert_eq!(resolved(Some("=1.0.0")).await.unwrap(), "1.0.0");
+        assert!(matches!(
+            resolved(Some("3")).await,
+            Err(CybulousError::OrchestrationFailed(_))
+        ));
+
+        let v1 = Version::parse("1.0.0").unwrap();
+        assert!(orchestrator.retire_version("versioned", &v1).await);
+        assert!(!orchestrator.retire_version("versioned", &v1).await);
+        let retired = resolved(Some("=1.0.0")).await.unwrap_err();
+        assert!(matches!(retired, CybulousError::VersionRetired(_)));
+        assert_eq!(
+            retired.to_string(),
+            "version retired: versioned 1.0.0 is retired"
+        );
+
+        orchestrator
+            .retire_version("versioned", &Version::parse("2.0.0").unwrap())
+            .await;
+        assert_eq!(resolved(None).await.unwrap(), "1.4.2");
+
+        let v2 = Version::parse("2.0.0").unwrap();
+        assert!(orchestrator.unregister_version("versioned", &v2).await);
+        assert!(!orchestrator.unregister_version("versioned", &v2).await);
+        assert_eq!(
+            orchestrator.tool_versions("versioned").await,
+            ["1.0.0", "1.4.2"].map(|v| Version::parse(v).unwrap())
+        );
+        assert_eq!(resolved(None).await.unwrap(), "1.4.2");
+        for version in ["1.0.0", "1.4.2"] {
+            let version = Version::parse(version).unwrap();
+            assert!(orchestrator.unregister_version("versioned", &version).await);
+        }
+        assert!(!orchestrator
+            .list_tools()
+            .await
+            .contains(&"versioned".to_string()));
+    }
+
+    struct CapableExecutor {
+        name: &'static str,
+        capabilities: &'static [&'static str],
+    }
+
+    #[async_trait]
+    impl ToolExecutor for CapableExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(serde_json::json!({ "executor": self.name })),
+                error: None,
+                duration_ms: 1,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn supports_capability(&self, capability: &str) -> bool {
+            self.capabilities.contains(&capability)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_capability_calls_route_by_selection_strategy() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10)
+            .with_capability_selection(CapabilitySelection::RoundRobin);
+        for (name, capabilities) in [
+            ("ocr-b", &["ocr"][..]),
+            ("ocr-a", &["ocr", "translate"][..]),
+            ("speech", &["transcribe"][..]),
+        ] {
+            orchestrator
+                .register_executor(Arc::new(CapableExecutor { name, capabilities }))
+                .await
+                .unwrap();
+        }
+
+        let mut routed = Vec::new();
+        for _ in 0..3 {
+            let call = test_call("").with_capability("ocr");
+            let call_id = call.id;
+            let response = orchestrator.execute_tool(call).await.unwrap();
+            routed.push(response.result.unwrap()["executor"].clone());
+            assert_eq!(
+                orchestrator
+                    .audit_log()
+                    .get(call_id)
+                    .await
+                    .unwrap()
+                    .tool_name,
+                routed.last().unwrap().as_str().unwrap()
+            );
+        }
+        assert_eq!(routed, ["ocr-a", "ocr-b", "ocr-a"]);
+
+        let missing = orchestrator
+            .execute_tool(test_call("").with_capability("render"))
+            .await;
+        assert!(matches!(
+            missing,
+            Err(CybulousError::OrchestrationFailed(msg)) if msg.contains("render")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_streamed_calls_route_by_capability_and_audit_rejections() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(CapableExecutor {
+                name: "ocr-a",
+                capabilities: &["ocr"],
+            }))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(MeteredExecutor))
+            .await
+            .unwrap();
+        let audited = |call_id| {
+            let orchestrator = orchestrator.clone();
+            async move { orchestrator.audit_log().get(call_id).await.unwrap() }
+        };
+
+        let call = test_call("").with_capability("ocr");
+        let call_id = call.id;
+        let events: Vec<_> = orchestrator
+            .execute_tool_streaming(call)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert!(matches!(
+            &events[0],
+            StreamEvent::Chunk(c) if c.data["executor"] == "ocr-a"
+        ));
+        assert_eq!(audited(call_id).await.tool_name, "ocr-a");
+
+        let unknown = test_call("missing");
+        let call_id = unknown.id;
+        assert!(orchestrator.execute_tool_streaming(unknown).await.is_err());
+        assert_eq!(audited(call_id).await.status, ExecutionStatus::Failed);
+
+        let mut unaffordable = test_call("metered");
+        unaffordable.user_id = "metered-user".to_string();
+        unaffordable.context.consent_proof = mock_proof("mock-tx-hash", "metered-user");
+        orchestrator.set_budget("metered-user", 1);
+        let call_id = unaffordable.id;
+        assert!(matches!(
+            orchestrator.execute_tool_streaming(unaffordable).await,
+            Err(CybulousError::BudgetExceeded(_))
+        ));
+        assert_eq!(audited(call_id).await.status, ExecutionStatus::Failed);
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[tokio::test]
+    async fn test_prometheus_metrics_count_executions() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "test-tool".to_string(),
+            }))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(FailingExecutor))
+            .await
+            .unwrap();
+
+        for _ in 0..2 {
+            orchestrator
+                .execute_tool(test_call("test-tool"))
+                .await
+                .unwrap();
+        }
+        orchestrator
+            .execute_tool(test_call("flaky-tool"))
+            .await
+            .unwrap();
+
+        let payload = orchestrator.encode_metrics();
+        for line in [
+            "cybulous_executions_total{tool=\"test-tool\",status=\"success\"} 2",
+            "cybulous_executions_total{tool=\"flaky-tool\",status=\"failed\"} 1",
+            "cybulous_execution_duration_seconds_count{tool=\"test-tool\"} 2",
+            "cybulous_queue_depth 0",
+        ] {
+            assert!(payload.contains(line), "missing {} in\n{}", line, payload);
+        }
+    }
+
+    /// Returns the trace context it was called with
+    struct TracedExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for TracedExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(serde_json::json!(call
+                    .context
+                    .metadata
+                    .get(TRACEPARENT_KEY))),
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "traced"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trace_context_reaches_executors() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(TracedExecutor))
+            .await
+            .unwrap();
+        let seen = |call: ToolCall| {
+            let orchestrator = orchestrator.clone();
+            async move {
+                let response = orchestrator.execute_tool(call).await.unwrap();
+                TraceContext::parse(response.result.unwrap().as_str().unwrap()).unwrap()
+            }
+        };
+
+        let upstream = TraceContext::root(true);
+        let mut call = test_call("traced");
+        upstream.inject(&mut call.context.metadata);
+        let continued = seen(call).await;
+        assert_eq!(continued.trace_id, upstream.trace_id);
+        assert_ne!(continued.span_id, upstream.span_id);
+
+        let started = seen(test_call("traced")).await;
+        assert_ne!(started.trace_id, upstream.trace_id);
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_replays_original_response() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        let ledger = Arc::new(LedgerExecutor {
+            actions: std::sync::Mutex::new(Vec::new()),
+        });
+        orchestrator
+            .register_executor(ledger.clone())
+            .await
+            .unwrap();
+        let submission = |action: &str, fail: bool| {
+            let mut call = test_call("ledger").with_idempotency_key("order-42");
+            call.parameters = serde_json::json!({ "action": action, "fail": fail });
+            call
+        };
+
+        // A failed attempt releases the key so the retry executes
+        let failed = orchestrator
+            .execute_tool(submission("charge", true))
+            .await
+            .unwrap();
+        assert_eq!(failed.status, ExecutionStatus::Failed);
+        let first = submission("charge", false);
+        let first_id = first.id;
+        let (a, b) = tokio::join!(
+            orchestrator.execute_tool(first),
+            orchestrator.execute_tool(submission("charge", false))
+        );
+        assert_eq!(a.unwrap().call_id, first_id);
+        assert_eq!(b.unwrap().call_id, first_id);
+        assert_eq!(*ledger.actions.lock().unwrap(), ["charge"]);
+
+        let conflict = orchestrator.execute_tool(submission("refund", false)).await;
+        assert!(matches!(conflict, Err(CybulousError::InvalidParameters(_))));
+
+        // Keys are scoped to the submitting user
+        let mut other_user = submission("charge", false);
+        other_user.user_id = "other-user".to_string();
+        other_user.context.consent_proof = mock_proof("mock-tx-hash", "other-user");
+        orchestrator.execute_tool(other_user).await.ok();
+        assert_eq!(ledger.actions.lock().unwrap().len(), 2);
+    }
+
+    struct ForecastExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for ForecastExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(serde_json::json!({ "days": call.parameters["days"] })),
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "forecast"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            true
+        }
+
+        fn input_schema(&self) -> Option<serde_json::Value> {
+            Some(serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "city": { "type": "string" },
+                    "days": { "type": "integer", "minimum": 1, "maximum": 14 }
+                },
+                "required": ["city", "days"]
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parameters_are_validated_against_input_schema() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(ForecastExecutor))
+            .await
+            .unwrap();
+
+        let mut call = test_call("forecast");
+        call.parameters = serde_json::json!({ "city": "Oslo", "days": 3 });
+        let response = orchestrator.execute_tool(call).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+
+        let mut call = test_call("forecast");
+        call.parameters = serde_json::json!({ "days": 30 });
+        let call_id = call.id;
+        let Err(CybulousError::SchemaViolation(violations)) = orchestrator.execute_tool(call).await
+        else {
+            panic!("expected a schema violation");
+        };
+        let mut fields = violations.fields();
+        fields.sort();
+        assert_eq!(fields, ["/city", "/days"]);
+        assert_eq!(
+            orchestrator.audit_log().get(call_id).await.unwrap().status,
+            ExecutionStatus::Failed
+        );
+    }
+
+    /// Serves its generation; calls with `hold` wait for the gate
+    struct GenerationExecutor {
+        generation: u64,
+        started: tokio::sync::Notify,
+        gate: tokio::sync::Notify,
+        deregistered: AtomicU32,
+    }
+
+    impl GenerationExecutor {
+        fn new(generation: u64) -> Self {
+            Self {
+                generation,
+                started: tokio::sync::Notify::new(),
+                gate: tokio::sync::Notify::new(),
+                deregistered: AtomicU32::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ToolExecutor for GenerationExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            if call.parameters["hold"] == true {
+                self.started.notify_one();
+                self.gate.notified().await;
+            }
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(serde_json::json!({ "generation": self.generation })),
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "generation"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+
+        async fn on_deregister(&self) {
+            self.deregistered.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replace_executor_drains_in_flight_calls() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        let old = Arc::new(GenerationExecutor::new(1));
+        let new = Arc::new(GenerationExecutor::new(2));
+        orchestrator.register_executor(old.clone()).await.unwrap();
+
+        let mut held = test_call("generation");
+        held.parameters = serde_json::json!({ "hold": true });
+        let in_flight = tokio::spawn({
+            let orchestrator = orchestrator.clone();
+            async move { orchestrator.execute_tool(held).await }
+        });
+        old.started.notified().await;
+
+        let replacing = tokio::spawn({
+            let orchestrator = orchestrator.clone();
+            let new = new.clone();
+            async move { orchestrator.replace_executor(new).await }
+        });
+        // New calls reach the replacement while the old executor drains
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                let response = orchestrator
+                    .execute_tool(test_call("generation"))
+                    .await
+                    .unwrap();
+                if response.result == Some(serde_json::json!({ "generation": 2 })) {
+                    break;
+                }
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("calls never reached the replacement");
+        assert!(!replacing.is_finished());
+        assert_eq!(old.deregistered.load(Ordering::SeqCst), 0);
+
+        old.gate.notify_one();
+        let response = in_flight.await.unwrap().unwrap();
+        assert_eq!(
+            response.result,
+            Some(serde_json::json!({ "generation": 1 }))
+        );
+        replacing.await.unwrap().unwrap();
+        assert_eq!(old.deregistered.load(Ordering::SeqCst), 1);
+
+        assert!(orchestrator.unregister_executor("generation").await);
+        assert_eq!(new.deregistered.load(Ordering::SeqCst), 1);
+        assert!(orchestrator.replace_executor(old).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unregister_gives_up_draining_after_timeout() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10)
+            .with_drain_timeout(std::time::Duration::from_millis(20));
+        let executor = Arc::new(GenerationExecutor::new(1));
+        orchestrator
+            .register_executor(executor.clone())
+            .await
+            .unwrap();
+
+        let mut held = test_call("generation");
+        held.parameters = serde_json::json!({ "hold": true });
+        let in_flight = tokio::spawn({
+            let orchestrator = orchestrator.clone();
+            async move { orchestrator.execute_tool(held).await }
+        });
+        executor.started.notified().await;
+
+        assert!(orchestrator.unregister_executor("generation").await);
+        assert_eq!(executor.deregistered.load(Ordering::SeqCst), 1);
+        assert!(orchestrator
+            .execute_tool(test_call("generation"))
+            .await
+            .is_err());
+
+        executor.gate.notify_one();
+        let response = in_flight.await.unwrap().unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_probe_releases_half_open_circuit() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10).with_tool_circuit_breakers(
+            CircuitBreakerConfig {
+                failure_threshold: 1,
+                reset_timeout: std::time::Duration::from_millis(20),
+            },
+        );
+        orchestrator
+            .register_executor(Arc::new(SlowExecutor))
+            .await
+            .unwrap();
+
+        let mut failing = test_call("slow");
+        failing.timeout_ms = 10;
+        let response = orchestrator.execute_tool(failing).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Timeout);
+        tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+
+        let probe = test_call("slow");
+        let probe_id = probe.id;
+        let handle = {
+            let orchestrator = orchestrator.clone();
+            tokio::spawn(async move { orchestrator.execute_tool(probe).await.unwrap() })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        let rejected = orchestrator.execute_tool(test_call("slow")).await;
+        assert!(matches!(rejected, Err(CybulousError::CircuitOpen(_))));
+
+        assert!(
+            orchestrator
+                .cancel(probe_id, CancellationReason::ClientCancelled)
+                .await
+        );
+        assert_eq!(
+            handle.await.unwrap().status,
+            ExecutionStatus::Cancelled(CancellationReason::ClientCancelled)
+        );
+        assert_eq!(
+            orchestrator.circuit_state("slow").await,
+            Some(CircuitState::HalfOpen)
+        );
+
+        // The slot is free again, so the next call probes
+        let response = orchestrator.execute_tool(test_call("slow")).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+        assert_eq!(
+            orchestrator.circuit_state("slow").await,
+            Some(CircuitState::Closed)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_call_leaves_no_inflight() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 1);
+        orchestrator
+            .register_executor(Arc::new(SlowExecutor))
+            .await
+            .unwrap();
+
+        let abandoned = tokio::time::timeout(
+            tokio::time::Duration::from_millis(20),
+            orchestrator.execute_tool(test_call("slow")),
+        )
+        .await;
+        assert!(abandoned.is_err());
+        assert_eq!(orchestrator.system_health().await.inflight, 0);
+    }
+
+    #[tokio::test]
+    async fn test_reused_call_id_keeps_later_cancellation() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(SlowExecutor))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "test-tool".to_string(),
+            }))
+            .await
+            .unwrap();
+
+        let abandoned = test_call("slow");
+        let abandoned_id = abandoned.id;
+        let dropped = tokio::time::timeout(
+            tokio::time::Duration::from_millis(20),
+            orchestrator.execute_tool(abandoned),
+        )
+        .await;
+        assert!(dropped.is_err());
+        assert!(
+            !orchestrator
+                .cancel(abandoned_id, CancellationReason::ClientCancelled)
+                .await
+        );
+
+        let slow = test_call("slow");
+        let mut quick = test_call("test-tool");
+        quick.id = slow.id;
+        let call_id = slow.id;
+        let handle = {
+            let orchestrator = orchestrator.clone();
+            tokio::spawn(async move { orchestrator.execute_tool(slow).await.unwrap() })
+        };
+        while orchestrator.system_health().await.inflight == 0 {
+            tokio::task::yield_now().await;
+        }
+        let response = orchestrator.execute_tool(quick).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+
+        assert!(
+            orchestrator
+                .cancel(call_id, CancellationReason::ClientCancelled)
+                .await
+        );
+        assert_eq!(
+            handle.await.unwrap().status,
+            ExecutionStatus::Cancelled(CancellationReason::ClientCancelled)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_queued_call_leaves_no_queue_depth() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 1);
+        orchestrator
+            .register_executor(Arc::new(SlowExecutor))
+            .await
+            .unwrap();
+
+        let busy = {
+            let orchestrator = orchestrator.clone();
+            tokio::spawn(async move { orchestrator.execute_tool(test_call("slow")).await })
+        };
+        while orchestrator.system_health().await.inflight == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        let queued = {
+            let orchestrator = orchestrator.clone();
+            tokio::spawn(async move { orchestrator.execute_tool(test_call("slow")).await })
+        };
+        while orchestrator.queue_depth() == 0 {
+            tokio::task::yield_now().await;
+        }
+        queued.abort();
+        let _ = queued.await;
+        assert_eq!(orchestrator.queue_depth(), 0);
+        busy.abort();
+    }
+
+    struct SlowExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for SlowExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: None,
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+
+        fn default_timeout(&self) -> Option<std::time::Duration> {
+            Some(std::time::Duration::from_millis(50))
+        }
+    }
+
+    /// Hands its work to a background task that reports how it was stopped
+    struct BackgroundExecutor {
+        stopped: std::sync::Mutex<Option<tokio::sync::oneshot::Sender<CancellationReason>>>,
+    }
+
+    #[async_trait]
+    impl ToolExecutor for BackgroundExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            self.execute_cancellable(call, CancellationToken::never())
+                .await
+        }
+
+        async fn execute_cancellable(
+            &self,
+            _call: &ToolCall,
+            token: CancellationToken,
+        ) -> Result<ToolResponse> {
+            let stopped = self.stopped.lock().unwrap().take().unwrap();
+            tokio::spawn(async move {
+                let _ = stopped.send(token.cancelled().await);
+            });
+            tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+            Err(CybulousError::OrchestrationFailed(
+                "not cancelled".to_string(),
+            ))
+        }
+
+        fn name(&self) -> &str {
+            "background"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_token_reaches_background_work() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        let (stopped_tx, stopped_rx) = tokio::sync::oneshot::channel();
+        orchestrator
+            .register_executor(Arc::new(BackgroundExecutor {
+                stopped: std::sync::Mutex::new(Some(stopped_tx)),
+            }))
+            .await
+            .unwrap();
+
+        let mut call = test_call("background");
+        call.timeout_ms = 20_000;
+        let call_id = call.id;
+        let handle = {
+            let orchestrator = orchestrator.clone();
+            tokio::spawn(async move { orchestrator.execute_tool(call).await.unwrap() })
+        };
+        while orchestrator.system_health().await.inflight == 0 {
+            tokio::task::yield_now().await;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+        assert!(
+            orchestrator
+                .cancel(call_id, CancellationReason::ClientCancelled)
+                .await
+        );
+
+        let response = handle.await.unwrap();
+        assert_eq!(
+            response.status,
+            ExecutionStatus::Cancelled(CancellationReason::ClientCancelled)
+        );
+        assert!((20..20_000).contains(&response.duration_ms));
+        assert_eq!(
+            stopped_rx.await.unwrap(),
+            CancellationReason::ClientCancelled
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_paths_record_reason() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(SlowExecutor))
+            .await
+            .unwrap();
+        let spawn = |call: ToolCall| {
+            let orchestrator = orchestrator.clone();
+            tokio::spawn(async move { orchestrator.execute_tool(call).await.unwrap() })
+        };
+
+        for reason in [
+            CancellationReason::ClientCancelled,
+            CancellationReason::SupersededByHedge,
+        ] {
+            let call = test_call("slow");
+            let call_id = call.id;
+            let handle = spawn(call);
+            while !orchestrator.cancel(call_id, reason).await {
+                tokio::task::yield_now().await;
+            }
+            let response = handle.await.unwrap();
+            assert_eq!(response.status, ExecutionStatus::Cancelled(reason));
+            assert_eq!(
+                orchestrator.audit_log().get(call_id).await.unwrap().status,
+                ExecutionStatus::Cancelled(reason)
+            );
+        }
+
+        let handle = spawn(test_call("slow"));
+        while orchestrator.system_health().await.inflight == 0 {
+            tokio::task::yield_now().await;
+        }
+        orchestrator.shutdown().await;
+        assert_eq!(
+            handle.await.unwrap().status,
+            ExecutionStatus::Cancelled(CancellationReason::Shutdown)
+        );
+
+        let mut call = test_call("slow");
+        let deadline = chrono::Utc::now() + chrono::Duration::milliseconds(20);
+        call.context
+            .metadata
+            .insert(DEADLINE_KEY.to_string(), deadline.to_rfc3339());
+        let response = orchestrator.execute_tool(call).await.unwrap();
+        assert_eq!(
+            response.status,
+            ExecutionStatus::Cancelled(CancellationReason::Deadline)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_metadata_routing_rule() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(FailingExecutor))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "flaky-tool-eu".to_string(),
+            }))
+            .await
+            .unwrap();
+        orchestrator
+            .add_routing_rule(RoutingRule {
+                tool_name: "flaky-tool".to_string(),
+                key: "region".to_string(),
+                value: "eu".to_string(),
+                executor: "flaky-tool-eu".to_string(),
+            })
+            .await;
+
+        let mut eu_call = test_call("flaky-tool");
+        eu_call
+            .context
+            .metadata
+            .insert("region".to_string(), "eu".to_string());
+        let routed = orchestrator.execute_tool(eu_call).await.unwrap();
+        assert_eq!(routed.status, ExecutionStatus::Success);
+
+        let default = orchestrator
+            .execute_tool(test_call("flaky-tool"))
+            .await
+            .unwrap();
+        assert_eq!(default.status, ExecutionStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_locality_route_follows_consent_region() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine.clone(), 10);
+        // The US executor fails so the test can tell which one ran
+        orchestrator
+            .register_executor(Arc::new(FailingExecutor))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "flaky-tool-eu".to_string(),
+            }))
+            .await
+            .unwrap();
+        for (region, executor) in [("US", "flaky-tool"), ("EU", "flaky-tool-eu")] {
+            orchestrator
+                .add_locality_route(LocalityRoute {
+                    tool_name: "flaky-tool".to_string(),
+                    region: region.to_string(),
+                    executor: executor.to_string(),
+                })
+                .await;
+        }
+
+        consent_engine
+            .set_origin_region("test-user", Some("EU".to_string()))
+            .await
+            .unwrap();
+        let eu = orchestrator
+            .execute_tool(test_call("flaky-tool"))
+            .await
+            .unwrap();
+        assert_eq!(eu.status, ExecutionStatus::Success);
+
+        consent_engine
+            .set_origin_region("test-user", Some("US".to_string()))
+            .await
+            .unwrap();
+        let us = orchestrator
+            .execute_tool(test_call("flaky-tool"))
+            .await
+            .unwrap();
+        assert_eq!(us.status, ExecutionStatus::Failed);
+
+        // Without a known region the call keeps its default executor
+        consent_engine
+            .set_origin_region("test-user", None)
+            .await
+            .unwrap();
+        let default = orchestrator
+            .execute_tool(test_call("flaky-tool"))
+            .await
+            .unwrap();
+        assert_eq!(default.status, ExecutionStatus::Failed);
+    }
+
+    struct MeteredExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for MeteredExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: None,
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "metered"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+
+        fn cost(&self, _call: &ToolCall) -> u64 {
+            3
+        }
+    }
+
+    #[tokio::test]
+    async fn test_budget_refunded_on_pre_execution_rejection() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(MeteredExecutor))
+            .await
+            .unwrap();
+        let mut call = test_call("metered");
+        call.user_id = "metered-user".to_string();
+        call.context.consent_proof = mock_proof("mock-tx-hash", "metered-user");
+        orchestrator.set_budget("metered-user", 5);
+
+        let mut denied = call.clone();
+        denied.context.consent_proof = "forged".to_string();
+        assert!(orchestrator.execute_tool(denied).await.is_err());
+        assert_eq!(orchestrator.remaining_budget("metered-user"), Some(5));
+
+        let response = orchestrator.execute_tool(call.clone()).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+        assert_eq!(orchestrator.remaining_budget("metered-user"), Some(2));
+
+        let exhausted = orchestrator.execute_tool(call).await;
+        assert!(matches!(exhausted, Err(CybulousError::BudgetExceeded(_))));
+        assert_eq!(orchestrator.remaining_budget("metered-user"), Some(2));
+    }
+
+    struct PagedExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for PagedExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            let page = call
+                .parameters
+                .get(pagination::CURSOR_PARAM)
+                .and_then(|c| c.as_u64())
+                .unwrap_or(0);
+            let next_cursor = (page < 2).then_some(page + 1);
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(serde_json::json!({
+                    "items": [page * 2, page * 2 + 1],
+                    "next_cursor": next_cursor,
+                })),
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "paged"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn test_paged_execution_follows_cursors() {
+        use futures::StreamExt;
+
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(PagedExecutor))
+            .await
+            .unwrap();
+
+        let call = test_call("paged");
+        let root_id = call.id;
+        let pages: Vec<ToolResponse> = orchestrator.execute_tool_paged(call).collect().await;
+        assert_eq!(pages.len(), 3);
+        let items: Vec<u64> = pages
+            .iter()
+            .flat_map(|p| {
+                p.result.as_ref().unwrap()["items"]
+                    .as_array()
+                    .unwrap()
+                    .clone()
+            })
+            .map(|i| i.as_u64().unwrap())
+            .collect();
+        assert_eq!(items, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(
+            orchestrator.causality_chain(pages[2].call_id).await.len(),
+            3
+        );
+        assert_eq!(
+            orchestrator.causality_chain(pages[2].call_id).await[0].call_id,
+            root_id
+        );
+
+        let limited = Orchestrator::new(Arc::new(cybulous_consent::ConsentEngine::mock()), 10)
+            .with_max_pages(2);
+        limited
+            .register_executor(Arc::new(PagedExecutor))
+            .await
+            .unwrap();
+        let call = test_call("paged");
+        assert_eq!(limited.execute_tool_paged(call).count().await, 2);
+    }
+
+    #[derive(Default)]
+    struct LifecycleExecutor {
+        registered: AtomicUsize,
+        deregistered: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ToolExecutor for LifecycleExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: None,
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "pooled"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+
+        async fn on_register(&self) {
+            self.registered.fetch_add(1, Ordering::SeqCst);
+        }
+
+        async fn on_deregister(&self) {
+            self.deregistered.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_callbacks() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        let first = Arc::new(LifecycleExecutor::default());
+        let second = Arc::new(LifecycleExecutor::default());
+
+        orchestrator.register_executor(first.clone()).await.unwrap();
+        assert_eq!(first.registered.load(Ordering::SeqCst), 1);
+
+        // Replacing an executor tears down the previous one
+        orchestrator
+            .register_executor(second.clone())
+            .await
+            .unwrap();
+        assert_eq!(first.deregistered.load(Ordering::SeqCst), 1);
+        assert_eq!(second.registered.load(Ordering::SeqCst), 1);
+
+        assert!(orchestrator.unregister_executor("pooled").await);
+        assert!(!orchestrator.unregister_executor("pooled").await);
+        assert_eq!(second.deregistered.load(Ordering::SeqCst), 1);
+        assert!(orchestrator.list_tools().await.is_empty());
+    }
+
+    struct CategorizedExecutor {
+        name: &'static str,
+        category: ToolCategory,
+    }
+
+    #[async_trait]
+    impl ToolExecutor for CategorizedExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: None,
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+
+        fn category(&self) -> ToolCategory {
+            self.category
+        }
+    }
+
+    #[tokio::test]
+    async fn test_category_listing_and_deny() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        for (name, category) in [
+            ("lookup", ToolCategory::Read),
+            ("webhook", ToolCategory::External),
+            ("payments", ToolCategory::External),
+        ] {
+            orchestrator
+                .register_executor(Arc::new(CategorizedExecutor { name, category }))
+                .await
+                .unwrap();
+        }
+
+        let mut external = orchestrator
+            .list_tools_by_category(ToolCategory::External)
+            .await;
+        external.sort();
+        assert_eq!(external, vec!["payments", "webhook"]);
+        assert!(orchestrator
+            .list_tools_by_category(ToolCategory::Admin)
+            .await
+            .is_empty());
+
+        orchestrator.deny_category(ToolCategory::External).await;
+        let denied = orchestrator.execute_tool(test_call("webhook")).await;
+        assert!(matches!(denied, Err(CybulousError::AccessDenied(_))));
+        assert!(orchestrator.execute_tool(test_call("lookup")).await.is_ok());
+
+        orchestrator.allow_category(ToolCategory::External).await;
+        assert!(orchestrator
+            .execute_tool(test_call("webhook"))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_drain_mode_permits_only_reads() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        for (name, category) in [
+            ("lookup", ToolCategory::Read),
+            ("ledger", ToolCategory::Write),
+        ] {
+            orchestrator
+                .register_executor(Arc::new(CategorizedExecutor { name, category }))
+                .await
+                .unwrap();
+        }
+
+        orchestrator.set_drain_mode(DrainMode::ReadOnly).await;
+        let blocked = orchestrator.execute_tool(test_call("ledger")).await;
+        assert!(matches!(blocked, Err(CybulousError::Draining(_))));
+        let read = orchestrator
+            .execute_tool(test_call("lookup"))
+            .await
+            .unwrap();
+        assert_eq!(read.status, ExecutionStatus::Success);
+
+        orchestrator.set_drain_mode(DrainMode::Off).await;
+        assert!(orchestrator.execute_tool(test_call("ledger")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_queue_timeout_distinct_from_execution_timeout() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 1);
+        orchestrator
+            .register_executor(Arc::new(SlowExecutor))
+            .await
+            .unwrap();
+
+        let busy = {
+            let orchestrator = orchestrator.clone();
+            tokio::spawn(async move { orchestrator.execute_tool(test_call("slow")).await })
+        };
+        while orchestrator.system_health().await.inflight == 0 {
+            tokio::task::yield_now().await;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+
+        let mut queued = test_call("slow");
+        queued.queue_timeout_ms = Some(20);
+        let response = orchestrator.execute_tool(queued).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::QueueTimeout);
+        assert_eq!(orchestrator.system_health().await.queue_depth, 0);
+        busy.abort();
+
+        let orchestrator = Orchestrator::new(Arc::new(cybulous_consent::ConsentEngine::mock()), 1);
+        orchestrator
+            .register_executor(Arc::new(SlowExecutor))
+            .await
+            .unwrap();
+        let mut slow = test_call("slow");
+        slow.queue_timeout_ms = Some(20);
+        slow.execution_timeout_ms = Some(20);
+        let response = orchestrator.execute_tool(slow).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Timeout);
+        assert_eq!(response.duration_ms, 20);
+    }
+
+    /// Fails its first `failures` attempts, then succeeds
+    struct RecoveringExecutor {
+        failures: u32,
+        attempts: AtomicU32,
+        permanent: bool,
+    }
+
+    #[async_trait]
+    impl ToolExecutor for RecoveringExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            if self.attempts.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err(CybulousError::OrchestrationFailed("transient".to_string()));
+            }
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: None,
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "recovering"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+
+        fn is_retryable(&self, _call: &ToolCall, _response: &ToolResponse) -> bool {
+            !self.permanent
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_policy_recovers_transient_failures() {
+        let policy = RetryPolicy::new(3, std::time::Duration::from_millis(1));
+        let register = |failures, permanent| async move {
+            let orchestrator =
+                Orchestrator::new(Arc::new(cybulous_consent::ConsentEngine::mock()), 10)
+                    .with_retry_policy(policy);
+            let executor = Arc::new(RecoveringExecutor {
+                failures,
+                attempts: AtomicU32::new(0),
+                permanent,
+            });
+            orchestrator
+                .register_executor(executor.clone())
+                .await
+                .unwrap();
+            (orchestrator, executor)
+        };
+
+        let (orchestrator, executor) = register(2, false).await;
+        let response = orchestrator
+            .execute_tool(test_call("recovering"))
+            .await
+            .unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+        assert_eq!(executor.attempts.load(Ordering::SeqCst), 3);
+
+        // Attempts are capped by the policy
+        let (orchestrator, executor) = register(5, false).await;
+        let response = orchestrator
+            .execute_tool(test_call("recovering"))
+            .await
+            .unwrap();
+        assert_eq!(response.status, ExecutionStatus::Failed);
+        assert_eq!(executor.attempts.load(Ordering::SeqCst), 3);
+
+        // Executors can veto retries of failures they know are permanent
+        let (orchestrator, executor) = register(1, true).await;
+        let response = orchestrator
+            .execute_tool(test_call("recovering"))
+            .await
+            .unwrap();
+        assert_eq!(response.status, ExecutionStatus::Failed);
+        assert_eq!(executor.attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tool_circuit_breaker_opens_per_tool_and_recovers() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10).with_tool_circuit_breakers(
+            CircuitBreakerConfig {
+                failure_threshold: 2,
+                reset_timeout: std::time::Duration::from_millis(20),
+            },
+        );
+        orchestrator
+            .register_executor(Arc::new(RecoveringExecutor {
+                failures: 2,
+                attempts: AtomicU32::new(0),
+                permanent: false,
+            }))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "search".to_string(),
+            }))
+            .await
+            .unwrap();
+
+        for _ in 0..2 {
+            let response = orchestrator
+                .execute_tool(test_call("recovering"))
+                .await
+                .unwrap();
+            assert_eq!(response.status, ExecutionStatus::Failed);
+        }
+        let rejected = orchestrator.execute_tool(test_call("recovering")).await;
+        assert!(matches!(rejected, Err(CybulousError::CircuitOpen(_))));
+        assert_eq!(
+            orchestrator
+                .system_health()
+                .await
+                .circuits
+                .get("recovering"),
+            Some(&CircuitState::Open)
+        );
+
+        // Other tools keep their own closed circuits
+        let other = orchestrator
+            .execute_tool(test_call("search"))
+            .await
+            .unwrap();
+        assert_eq!(other.status, ExecutionStatus::Success);
+
+        // A successful half-open probe closes the circuit
+        tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+        let probe = orchestrator
+            .execute_tool(test_call("recovering"))
+            .await
+            .unwrap();
+        assert_eq!(probe.status, ExecutionStatus::Success);
+        assert_eq!(
+            orchestrator.circuit_state("recovering").await,
+            Some(CircuitState::Closed)
+        );
+        assert_eq!(
+            orchestrator.tool_circuit_metrics("recovering").await,
+            Some(CircuitMetrics {
+                opened: 1,
+                half_opened: 1,
+                closed: 1,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast_queueing_rejects_when_saturated() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator =
+            Orchestrator::new(consent_engine, 1).with_queueing(QueueingBehavior::FailFast);
+        orchestrator
+            .register_executor(Arc::new(SlowExecutor))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "search".to_string(),
+            }))
+            .await
+            .unwrap();
+
+        let busy = {
+            let orchestrator = orchestrator.clone();
+            tokio::spawn(async move { orchestrator.execute_tool(test_call("slow")).await })
+        };
+        while orchestrator.system_health().await.inflight == 0 {
+            tokio::task::yield_now().await;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+
+        let response = orchestrator
+            .execute_tool(test_call("search"))
+            .await
+            .unwrap();
+        assert_eq!(response.status, ExecutionStatus::Rejected);
+        assert_eq!(orchestrator.system_health().await.queue_depth, 0);
+
+        // The slot frees up once the running call ends
+        busy.abort();
+        let _ = busy.await;
+        let response = orchestrator
+            .execute_tool(test_call("search"))
+            .await
+            .unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+    }
+
+    #[tokio::test]
+    async fn test_executor_default_timeout_governs_zero_timeout_call() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10)
+            .with_default_timeout(std::time::Duration::from_secs(10));
+        orchestrator
+            .register_executor(Arc::new(SlowExecutor))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "search".to_string(),
+            }))
+            .await
+            .unwrap();
+
+        let mut slow = test_call("slow");
+        slow.timeout_ms = 0;
+        let response = orchestrator.execute_tool(slow).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Timeout);
+        assert_eq!(response.duration_ms, 50);
+
+        // Without an executor default, the orchestrator's applies
+        let mut search = test_call("search");
+        search.timeout_ms = 0;
+        let response = orchestrator.execute_tool(search).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+    }
+
+    #[tokio::test]
+    async fn test_trace_sampling_rate_and_error_override() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10).with_trace_sample_rate(0.2);
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "search".to_string(),
+            }))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(FailingExecutor))
+            .await
+            .unwrap();
+
+        const CALLS: usize = 2000;
+        for _ in 0..CALLS {
+            orchestrator
+                .execute_tool(test_call("search"))
+                .await
+                .unwrap();
+        }
+        let sampled = orchestrator
+            .audit_log()
+            .records()
+            .await
+            .iter()
+            .filter(|r| r.sampled)
+            .count();
+        let rate = sampled as f64 / CALLS as f64;
+        assert!((0.15..0.25).contains(&rate), "sampled rate {}", rate);
+
+        // Failures are traced even when the head decision was not to sample
+        let mut unsampled = test_call("flaky-tool");
+        unsampled
+            .context
+            .metadata
+            .insert(TRACE_SAMPLED_KEY.to_string(), "false".to_string());
+        let call_id = unsampled.id;
+        let response = orchestrator.execute_tool(unsampled).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Failed);
+        assert!(orchestrator.audit_log().get(call_id).await.unwrap().sampled);
+    }
+
+    #[tokio::test]
+    async fn test_outcome_sampler_keeps_all_failures() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator =
+            Orchestrator::new(consent_engine, 10).with_outcome_sampler(OutcomeSampler::new(0.1));
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "search".to_string(),
+            }))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(FailingExecutor))
+            .await
+            .unwrap();
+
+        const CALLS: usize = 1000;
+        for i in 0..CALLS {
+            let call = match i % 3 {
+                0 => test_call("flaky-tool"),
+                1 => {
+                    let mut denied = test_call("search");
+                    denied.context.consent_proof = "forged".to_string();
+                    denied
+                }
+                _ => test_call("search"),
+            };
+            let _ = orchestrator.execute_tool(call).await;
+        }
+
+        let records = orchestrator.audit_log().records().await;
+        let failures: Vec<_> = records
+            .iter()
+            .filter(|r| r.status != ExecutionStatus::Success)
+            .collect();
+        assert!(!failures.is_empty());
+        assert!(failures.iter().all(|r| r.sampled));
+
+        let successes: Vec<_> = records
+            .iter()
+            .filter(|r| r.status == ExecutionStatus::Success)
+            .collect();
+        let rate = successes.iter().filter(|r| r.sampled).count() as f64 / successes.len() as f64;
+        assert!((0.05..0.15).contains(&rate), "success rate {}", rate);
+    }
+
+    struct ClassifyingExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for ClassifyingExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            match call.parameters["fail"].as_str() {
+                Some("auth") => Err(CybulousError::AccessDenied("token revoked".to_string())),
+                _ => Err(CybulousError::OrchestrationFailed(
+                    "connection reset".to_string(),
+                )),
+            }
+        }
+
+        fn name(&self) -> &str {
+            "classified"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+
+        fn classify_error(&self, error: &CybulousError) -> ErrorClass {
+            match error {
+                CybulousError::OrchestrationFailed(msg) if msg.contains("reset") => {
+                    ErrorClass::Transient
+                }
+                other => alerting::classify(other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_error_classes_drive_alerts() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10).with_error_alerter(
+            ErrorAlerter::new(std::time::Duration::from_secs(60))
+                .with_threshold(ErrorClass::Auth, 2)
+                .with_threshold(ErrorClass::Permanent, 2),
+        );
+        orchestrator
+            .register_executor(Arc::new(ClassifyingExecutor))
+            .await
+            .unwrap();
+        let mut alerts = orchestrator.subscribe_error_alerts();
+        let failing = |kind: &str| {
+            let mut call = test_call("classified");
+            call.parameters = serde_json::json!({ "fail": kind });
+            call
+        };
+
+        // Transient errors have no threshold and never alert
+        for _ in 0..5 {
+            orchestrator.execute_tool(failing("network")).await.unwrap();
+        }
+        assert!(alerts.try_recv().is_err());
+
+        orchestrator.execute_tool(failing("auth")).await.unwrap();
+        assert!(alerts.try_recv().is_err());
+        orchestrator.execute_tool(failing("auth")).await.unwrap();
+        let alert = alerts.try_recv().unwrap();
+        assert_eq!(alert.tool_name, "classified");
+        assert_eq!(alert.class, ErrorClass::Auth);
+        assert_eq!(alert.count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_metrics_fold_overflow() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10).with_max_tenant_labels(2);
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "search".to_string(),
+            }))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(FailingExecutor))
+            .await
+            .unwrap();
+        let call = |tool: &str, tenant: &str| {
+            let mut call = test_call(tool);
+            call.context
+                .metadata
+                .insert(TENANT_ID_KEY.to_string(), tenant.to_string());
+            call
+        };
+
+        for (tool, tenant) in [
+            ("search", "acme"),
+            ("search", "acme"),
+            ("flaky-tool", "acme"),
+            ("search", "globex"),
+            ("search", "initech"),
+            ("flaky-tool", "umbrella"),
+            ("search", "globex"),
+        ] {
+            orchestrator.execute_tool(call(tool, tenant)).await.unwrap();
+        }
+        orchestrator
+            .execute_tool(test_call("search"))
+            .await
+            .unwrap();
+
+        let metrics = orchestrator.tenant_metrics();
+        assert_eq!(metrics.len(), 3);
+        assert_eq!((metrics["acme"].calls, metrics["acme"].failures), (3, 1));
+        assert_eq!(
+            (metrics["globex"].calls, metrics["globex"].failures),
+            (2, 0)
+        );
+        assert_eq!(
+            (
+                metrics[crate::metrics::OVERFLOW_TENANT].calls,
+                metrics[crate::metrics::OVERFLOW_TENANT].failures
+            ),
+            (2, 1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_usage_report_tallies_cost_centers() {
+        use crate::usage::{ATTRIBUTION_PREFIX, COST_CENTER_KEY, UNATTRIBUTED};
+
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "search".to_string(),
+            }))
+            .await
+            .unwrap();
+        orchestrator
+            .register_executor(Arc::new(FailingExecutor))
+            .await
+            .unwrap();
+        let call = |tool: &str, cost_center: &str| {
+            let mut call = test_call(tool);
+            let metadata = &mut call.context.metadata;
+            metadata.insert(COST_CENTER_KEY.to_string(), cost_center.to_string());
+            metadata.insert(
+                format!("{}project", ATTRIBUTION_PREFIX),
+                "atlas".to_string(),
+            );
+            call
+        };
+
+        let start = chrono::Utc::now();
+        for (tool, cost_center) in [
+            ("search", "research"),
+            ("flaky-tool", "research"),
+            ("search", "research"),
+            ("search", "marketing"),
+        ] {
+            orchestrator
+                .execute_tool(call(tool, cost_center))
+                .await
+                .unwrap();
+        }
+        orchestrator
+            .execute_tool(test_call("search"))
+            .await
+            .unwrap();
+        let end = chrono::Utc::now() + chrono::Duration::seconds(1);
+
+        let report = orchestrator.usage_report(start..end);
+        let tally = |cc: &str| {
+            let usage = report.cost_centers[cc];
+            (usage.calls, usage.failures)
+        };
+        assert_eq!(report.cost_centers.len(), 3);
+        assert_eq!(tally("research"), (3, 1));
+        assert_eq!(tally("marketing"), (1, 0));
+        assert_eq!(tally(UNATTRIBUTED), (1, 0));
+        assert!(orchestrator
+            .usage_report(end..end + chrono::Duration::hours(1))
+            .cost_centers
+            .is_empty());
+
+        let records = orchestrator.audit_log().records().await;
+        let tagged = &records[0];
+        assert_eq!(tagged.cost_center.as_deref(), Some("research"));
+        assert_eq!(tagged.attribution["project"], "atlas");
+    }
+
+    struct SensitiveExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for SensitiveExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: None,
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "transfer"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+
+        fn requires_liveness(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_liveness_required_tool_rejects_stale_proof() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine.clone(), 10);
+        orchestrator
+            .register_executor(Arc::new(SensitiveExecutor))
+            .await
+            .unwrap();
+
+        // An ordinary consent proof is not enough
+        let denied = orchestrator.execute_tool(test_call("transfer")).await;
+        assert!(matches!(denied, Err(CybulousError::ConsentError(_))));
+
+        let challenge = consent_engine
+            .issue_liveness_challenge("test-user")
+            .await
+            .unwrap();
+        let proof = consent_engine
+            .complete_liveness_challenge(challenge.challenge_id, &challenge.expected_response())
+            .await
+            .unwrap();
+        let mut call = test_call("transfer");
+        call.context.consent_proof = proof;
+        let response = orchestrator.execute_tool(call).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+    }
+
+    /// Remote executor that ignores cooperative cancellation
+    #[derive(Default)]
+    struct StubbornExecutor {
+        signals: std::sync::Mutex<Vec<StopSignal>>,
+    }
+
+    #[async_trait]
+    impl ToolExecutor for StubbornExecutor {
+        async fn execute(&self, _call: &ToolCall) -> Result<ToolResponse> {
+            std::future::pending().await
+        }
+
+        fn name(&self) -> &str {
+            "remote"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+
+        fn escalation_ladder(&self) -> EscalationLadder {
+            EscalationLadder::new().then(std::time::Duration::from_millis(50), StopSignal::Abort)
+        }
+
+        async fn stop(&self, _call: &ToolCall, signal: StopSignal) {
+            self.signals.lock().unwrap().push(signal);
+        }
+
+        async fn has_stopped(&self, _call: &ToolCall) -> bool {
+            self.signals.lock().unwrap().contains(&StopSignal::Abort)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unresponsive_executor_aborted_after_grace() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        let executor = Arc::new(StubbornExecutor::default());
+        orchestrator
+            .register_executor(executor.clone())
+            .await
+            .unwrap();
+
+        let mut call = test_call("remote");
+        call.timeout_ms = 20;
+        let response = orchestrator.execute_tool(call).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Timeout);
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+        assert_eq!(*executor.signals.lock().unwrap(), vec![StopSignal::Cancel]);
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(60)).await;
+        assert_eq!(
+            *executor.signals.lock().unwrap(),
+            vec![StopSignal::Cancel, StopSignal::Abort]
+        );
+    }
+
+    #[derive(Default)]
+    struct CatalogExecutor {
+        executions: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ToolExecutor for CatalogExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            let n = self.executions.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(serde_json::json!({ "execution": n })),
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "catalog"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+
+        fn cache_ttl(&self) -> Option<std::time::Duration> {
+            Some(std::time::Duration::from_secs(60))
+        }
+
+        fn cache_tags(&self, _call: &ToolCall) -> Vec<String> {
+            vec!["inventory".to_string()]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_invalidation_by_tool_parameters_and_tag() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        let executor = Arc::new(CatalogExecutor::default());
+        orchestrator
+            .register_executor(executor.clone())
+            .await
+            .unwrap();
+        let lookup = |sku: &str| {
+            let mut call = test_call("catalog");
+            call.parameters = serde_json::json!({ "sku": sku, "region": "eu" });
+            let orchestrator = orchestrator.clone();
+            async move { orchestrator.execute_tool(call).await.unwrap() }
+        };
+        let executions = || executor.executions.load(Ordering::SeqCst);
+
+        lookup("a").await;
+        let cached = lookup("a").await;
+        lookup("b").await;
+        assert_eq!(cached.result, Some(serde_json::json!({ "execution": 1 })));
+        assert_eq!(executions(), 2);
+
+        let by_params =
+            CachePredicate::tool("catalog").with_parameters(serde_json::json!({ "sku": "a" }));
+        assert_eq!(orchestrator.invalidate_cache(&by_params), 1);
+        lookup("a").await;
+        lookup("b").await;
+        assert_eq!(executions(), 3);
+
+        assert_eq!(
+            orchestrator.invalidate_cache(&CachePredicate::tool("search")),
+            0
+        );
+        assert_eq!(
+            orchestrator.invalidate_cache(&CachePredicate::tool("catalog")),
+            2
+        );
+        lookup("a").await;
+        assert_eq!(executions(), 4);
+
+        assert_eq!(
+            orchestrator.invalidate_cache(&CachePredicate::tag("inventory")),
+            1
+        );
+        lookup("a").await;
+        assert_eq!(executions(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_consent_use_spent_only_when_executor_runs() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        consent_engine
+            .limit_uses("test-user", Some(2))
+            .await
+            .unwrap();
+        let orchestrator = Orchestrator::new(consent_engine.clone(), 10);
+        let executor = Arc::new(CatalogExecutor::default());
+        orchestrator
+            .register_executor(executor.clone())
+            .await
+            .unwrap();
+        let lookup = |sku: &str| {
+            let mut call = test_call("catalog");
+            call.parameters = serde_json::json!({ "sku": sku });
+            orchestrator.execute_tool(call)
+        };
+        let remaining = || consent_engine.remaining_uses("test-user");
+
+        lookup("a").await.unwrap();
+        assert_eq!(remaining().await.unwrap(), Some(1));
+        // Served from cache without running the executor
+        lookup("a").await.unwrap();
+        assert_eq!(remaining().await.unwrap(), Some(1));
+
+        lookup("b").await.unwrap();
+        assert_eq!(remaining().await.unwrap(), Some(0));
+        let err = lookup("c").await.unwrap_err();
+        assert!(err.to_string().contains("Consent uses exhausted"));
+        assert_eq!(executor.executions.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_persistent_cache_survives_restart() {
+        let store = Arc::new(MemoryCacheStore::default());
+        let lookup = |orchestrator: Orchestrator| async move {
+            let mut call = test_call("catalog");
+            call.parameters = serde_json::json!({ "sku": "a" });
+            orchestrator.execute_tool(call).await.unwrap()
+        };
+
+        let before = Orchestrator::new(Arc::new(cybulous_consent::ConsentEngine::mock()), 10)
+            .with_cache_store(store.clone());
+        let executor = Arc::new(CatalogExecutor::default());
+        before.register_executor(executor.clone()).await.unwrap();
+        lookup(before).await;
+        assert_eq!(executor.executions.load(Ordering::SeqCst), 1);
+
+        let after = Orchestrator::new(Arc::new(cybulous_consent::ConsentEngine::mock()), 10)
+            .with_cache_store(store.clone());
+        let executor = Arc::new(CatalogExecutor::default());
+        after.register_executor(executor.clone()).await.unwrap();
+        let cached = lookup(after.clone()).await;
+        assert_eq!(cached.result, Some(serde_json::json!({ "execution": 1 })));
+        assert_eq!(executor.executions.load(Ordering::SeqCst), 0);
+
+        assert_eq!(after.invalidate_cache(&CachePredicate::tag("inventory")), 1);
+        assert!(store.load().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_notify_on_access_obligation_fires_when_tool_runs() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        consent_engine
+            .add_obligation(
+                "test-user",
+                cybulous_consent::Obligation::NotifyOnAccess {
+                    recipient: "guardian@example.com".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "test-tool".to_string(),
+            }))
+            .await
+            .unwrap();
+        let mut notices = orchestrator.subscribe_access_notices();
+
+        let call = test_call("test-tool");
+        let call_id = call.id;
+        orchestrator.execute_tool(call).await.unwrap();
+
+        let notice = notices.try_recv().unwrap();
+        assert_eq!(notice.recipient, "guardian@example.com");
+        assert_eq!(notice.user_id, "test-user");
+        assert_eq!(notice.tool_name, "test-tool");
+        assert_eq!(notice.call_id, call_id);
+        assert!(notices.try_recv().is_err());
+    }
+
+    #[derive(Default)]
+    struct PricingExecutor {
+        unhealthy: std::sync::atomic::AtomicBool,
+        max_staleness: std::time::Duration,
+    }
+
+    #[async_trait]
+    impl ToolExecutor for PricingExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            if self.unhealthy.load(Ordering::SeqCst) {
+                return Err(CybulousError::OrchestrationFailed(
+                    "pricing backend unavailable".to_string(),
+                ));
+            }
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(serde_json::json!({ "price": 42 })),
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "pricing"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+
+        fn degraded_policy(&self) -> Option<DegradedPolicy> {
+            Some(DegradedPolicy::new(self.max_staleness))
+        }
+    }
+
+    async fn price(orchestrator: &Orchestrator, sku: &str) -> ToolResponse {
+        let mut call = test_call("pricing");
+        call.parameters = serde_json::json!({ "sku": sku });
+        orchestrator.execute_tool(call).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_failing_tool_serves_recent_result_in_degraded_mode() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        let executor = Arc::new(PricingExecutor {
+            max_staleness: std::time::Duration::from_secs(60),
+            ..PricingExecutor::default()
+        });
+        orchestrator
+            .register_executor(executor.clone())
+            .await
+            .unwrap();
+
+        let fresh = price(&orchestrator, "a").await;
+        assert_eq!(fresh.status, ExecutionStatus::Success);
+        assert!(!fresh.stale);
+
+        executor.unhealthy.store(true, Ordering::SeqCst);
+        let degraded = price(&orchestrator, "a").await;
+        assert_eq!(degraded.status, ExecutionStatus::Success);
+        assert!(degraded.stale);
+        assert_eq!(degraded.result, Some(serde_json::json!({ "price": 42 })));
+
+        let uncached = price(&orchestrator, "b").await;
+        assert_eq!(uncached.status, ExecutionStatus::Failed);
+        assert!(!uncached.stale);
+    }
+
+    #[tokio::test]
+    async fn test_degraded_mode_fails_without_fresh_enough_result() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        let executor = Arc::new(PricingExecutor::default());
+        orchestrator
+            .register_executor(executor.clone())
+            .await
+            .unwrap();
+
+        price(&orchestrator, "a").await;
+        executor.unhealthy.store(true, Ordering::SeqCst);
+        let response = price(&orchestrator, "a").await;
+        assert_eq!(response.status, ExecutionStatus::Failed);
+        assert!(!response.stale);
+    }
+
+    #[tokio::test]
+    async fn test_apply_manifest_configures_registered_tools() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine.clone(), 10);
+        for name in ["search", "search-eu"] {
+            orchestrator
+                .register_executor(Arc::new(MockExecutor {
+                    name: name.to_string(),
+                }))
+                .await
+                .unwrap();
+        }
+        let manifest = Manifest::from_toml(
+            r#"
+            [groups.io]
+            circuit_breaker = { failure_threshold = 3, reset_timeout = { secs = 30, nanos = 0 } }
+
+            [[tools]]
+            name = "search"
+            capabilities = ["web"]
+            scopes = ["search"]
+            timeout_ms = 2500
+            group = "io"
+            sla = { p95_latency = { secs = 1, nanos = 0 }, min_success_rate = 0.99 }
+
+            [[routes]]
+            tool_name = "search"
+            key = "region"
+            value = "eu"
+            executor = "search-eu"
+            "#,
+        )
+        .unwrap();
+
+        let mut unregistered = manifest.clone();
+        unregistered.tools[0].name = "missing".to_string();
+        assert!(matches!(
+            orchestrator.apply_manifest(&unregistered).await,
+            Err(CybulousError::ManifestInvalid(_))
+        ));
+        assert!(orchestrator.tool_policy("missing").await.is_none());
+        assert!(orchestrator.circuit_state("search").await.is_none());
+
+        orchestrator.apply_manifest(&manifest).await.unwrap();
+        assert_eq!(
+            orchestrator.tool_policy("search").await,
+            Some(ToolPolicy {
+                required_scopes: vec!["search".to_string()],
+                timeout: Some(std::time::Duration::from_millis(2500)),
+            })
+        );
+        assert_eq!(
+            orchestrator.circuit_state("search").await,
+            Some(CircuitState::Closed)
+        );
+        assert!(orchestrator.sla_status("search").await.is_some());
+        assert_eq!(
+            orchestrator.routing_rules.read().await.as_slice(),
+            manifest.routes.as_slice()
+        );
+
+        let denied = orchestrator.execute_tool(test_call("search")).await;
+        assert!(matches!(denied, Err(CybulousError::ConsentError(_))));
+        consent_engine
+            .grant_scope("test-user", "search", None)
+            .await
+            .unwrap();
+        let response = orchestrator
+            .execute_tool(test_call("search"))
+            .await
+            .unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+    }
+
+    /// Holds its slot while `label` is "gate" until released, recording
+    /// the order calls run in
+    #[derive(Default)]
+    struct OrderedExecutor {
+        release: tokio::sync::Notify,
+        order: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl ToolExecutor for OrderedExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            let label = call.parameters["label"].as_str().unwrap_or_default();
+            if label == "gate" {
+                self.release.notified().await;
+            }
+            self.order.lock().unwrap().push(label.to_string());
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: None,
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "ordered"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+    }
+
+    /// Queue a long-waiting low-priority call behind a held slot, then
+    /// newer calls, and report the order they run in once the slot frees
+    async fn admission_order(orchestrator: Orchestrator) -> Vec<String> {
+        let executor = Arc::new(OrderedExecutor::default());
+        orchestrator
+            .register_executor(executor.clone())
+            .await
+            .unwrap();
+        let submit = |label: &str, priority: i32| {
+            let mut call = test_call("ordered").with_priority(priority);
+            call.timeout_ms = 5000;
+            call.parameters = serde_json::json!({ "label": label });
+            let orchestrator = orchestrator.clone();
+            tokio::spawn(async move { orchestrator.execute_tool(call).await.unwrap() })
+        };
+        let queued = |depth: usize| {
+            let orchestrator = orchestrator.clone();
+            async move {
+                while orchestrator.queue_depth() < depth {
+                    tokio::task::yield_now().await;
+                }
+            }
+        };
+
+        let gate = submit("gate", 0);
+        while orchestrator.system_health().await.inflight == 0 {
+            tokio::task::yield_now().await;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        let waiting = submit("waiting", 0);
+        queued(1).await;
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        let newer = [submit("urgent", 2), submit("newer", 0)];
+        queued(3).await;
+
+        executor.release.notify_one();
+        gate.await.unwrap();
+        waiting.await.unwrap();
+        for call in newer {
+            call.await.unwrap();
+        }
+        let order = executor.order.lock().unwrap().clone();
+        order
+    }
+
+    #[test]
+    fn test_with_priority_sets_admission_priority() {
+        let call = test_call("ordered").with_priority(-3);
+        assert_eq!(priority::call_priority(&call), -3);
+        let child = call.derive_child("search", serde_json::json!({}));
+        assert_eq!(priority::call_priority(&child), -3);
+        assert_eq!(priority::call_priority(&test_call("ordered")), 0);
+    }
+
+    #[tokio::test]
+    async fn test_priority_aging_admits_long_waiting_low_priority_call() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let strict = admission_order(Orchestrator::new(consent_engine, 1)).await;
+        assert_eq!(strict, ["gate", "urgent", "waiting", "newer"]);
+
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let aging = Orchestrator::new(consent_engine, 1)
+            .with_priority_aging(std::time::Duration::from_millis(20));
+        assert_eq!(
+            admission_order(aging).await,
+            ["gate", "waiting", "urgent", "newer"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_geofenced_consent_checked_against_call_region() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        consent_engine
+            .restrict_regions("test-user", Some(vec!["EU".to_string()]))
+            .await
+            .unwrap();
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(MockExecutor {
+                name: "test-tool".to_string(),
+            }))
+            .await
+            .unwrap();
+        let from = |region: &str| {
+            let mut call = test_call("test-tool");
+            call.context.region = Some(region.to_string());
+            call
+        };
+
+        let response = orchestrator.execute_tool(from("EU")).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+        assert!(matches!(
+            orchestrator.execute_tool(from("US")).await,
+            Err(CybulousError::ConsentError(_))
+        ));
+        assert!(matches!(
+            orchestrator.execute_tool(test_call("test-tool")).await,
+            Err(CybulousError::ConsentError(_))
+        ));
+    }
+
+    struct ProfileExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for ProfileExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(serde_json::json!({
+                    "name": "Ada",
+                    "contact": { "email": "ada@example.com", "city": "London" },
+                })),
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "profile"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn test_result_fields_redacted_without_required_scope() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine.clone(), 10);
+        orchestrator
+            .register_executor(Arc::new(ProfileExecutor))
+            .await
+            .unwrap();
+        orchestrator
+            .set_result_scope_filter(
+                "profile",
+                ResultScopeFilter::new()
+                    .require("/contact/email", "email")
+                    .require("/contact/city", "location"),
+            )
+            .await;
+        consent_engine
+            .grant_scope("test-user", "location", None)
+            .await
+            .unwrap();
+
+        let response = orchestrator
+            .execute_tool(test_call("profile"))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.result,
+            Some(serde_json::json!({
+                "name": "Ada",
+                "contact": { "city": "London" },
+            }))
+        );
+
+        consent_engine
+            .grant_scope("test-user", "email", None)
+            .await
+            .unwrap();
+        let response = orchestrator
+            .execute_tool(test_call("profile"))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.result.unwrap()["contact"]["email"],
+            "ada@example.com"
+        );
+    }
+
+    struct DelayedExecutor;
+
+    #[async_trait]
+    impl ToolExecutor for DelayedExecutor {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            let delay = call.parameters["delay_ms"].as_u64().unwrap_or(0);
+            tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: Some(call.parameters.clone()),
+                error: None,
+                duration_ms: delay,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "delayed"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fan_out_merge_modes() {
+        let consent_engine = Arc::new(cybulous_consent::ConsentEngine::mock());
+        let orchestrator = Orchestrator::new(consent_engine, 10);
+        orchestrator
+            .register_executor(Arc::new(DelayedExecutor))
+            .await
+            .unwrap();
+        let calls = || {
+            [80, 5, 40]
+                .into_iter()
+                .map(|delay_ms| {
+                    let mut call = test_call("delayed");
+                    call.parameters = serde_json::json!({ "delay_ms": delay_ms });
+                    call
+                })
+                .collect::<Vec<_>>()
+        };
+        let sources = |mode| {
+            let orchestrator = orchestrator.clone();
+            async move {
+                orchestrator
+                    .execute_fan_out(calls(), mode)
+                    .map(|item| {
+                        assert_eq!(item.response.status, ExecutionStatus::Success);
+                        item.source
+                    })
+                    .collect::<Vec<_>>()
+                    .await
+            }
+        };
+
+        assert_eq!(sources(MergeMode::Ordered).await, [0, 1, 2]);
+        assert_eq!(sources(MergeMode::Unordered).await, [1, 2, 0]);
     }
 }
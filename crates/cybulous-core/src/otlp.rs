@@ -0,0 +1,139 @@
+//! OTLP export of tool call traces
+//!
+//! [`init_otlp_tracing`] installs a global subscriber exporting spans to an
+//! OpenTelemetry collector. Once it is installed, each `tool_call` span
+//! joins the trace named by the call's incoming `traceparent`, and the
+//! context propagated to executors carries the exported span's own id, so
+//! spans from remote executors nest under it.
+
+use crate::propagation::TraceContext;
+use crate::{CybulousError, Result};
+use opentelemetry::trace::{
+    SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState, TracerProvider,
+};
+use opentelemetry::Context;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+/// Name of the tracer spans are exported under
+pub const TRACER_NAME: &str = "cybulous";
+
+/// Export spans over OTLP/HTTP to `endpoint`, e.g.
+/// `http://localhost:4318/v1/traces`, as service `service_name`
+///
+/// Installs a global subscriber, filtered by `RUST_LOG`, that also logs to
+/// stdout. Shut the returned provider down before exiting to flush spans
+/// still queued for export.
+pub fn init_otlp_tracing(endpoint: &str, service_name: &str) -> Result<SdkTracerProvider> {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()
+        .map_err(|e| CybulousError::OrchestrationFailed(format!("OTLP exporter: {}", e)))?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            Resource::builder()
+                .with_service_name(service_name.to_string())
+                .build(),
+        )
+        .build();
+    tracing_subscriber::registry()
+        .with(EnvFilter::from_default_env())
+        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer(TRACER_NAME)))
+        .try_init()
+        .map_err(|e| CybulousError::OrchestrationFailed(format!("tracing subscriber: {}", e)))?;
+    Ok(provider)
+}
+
+/// Make `span` a child of `parent` in the exported trace, returning the
+/// context to propagate for it
+///
+/// Returns `None` if no OpenTelemetry layer records `span`.
+pub(crate) fn link_span(span: &Span, parent: Option<TraceContext>) -> Option<TraceContext> {
+    if let Some(parent) = parent {
+        let flags = if parent.sampled {
+            TraceFlags::SAMPLED
+        } else {
+            TraceFlags::default()
+        };
+        let remote = SpanContext::new(
+            TraceId::from_bytes(parent.trace_id.to_be_bytes()),
+            SpanId::from_bytes(parent.span_id.to_be_bytes()),
+            flags,
+            true,
+            TraceState::default(),
+        );
+        if let Err(e) = span.set_parent(Context::new().with_remote_span_context(remote)) {
+            tracing::debug!(
+                "Could not link span to trace {}: {}",
+                parent.trace_id_hex(),
+                e
+            );
+        }
+    }
+
+    let context = span.context();
+    let own = context.span().span_context().clone();
+    own.is_valid().then(|| TraceContext {
+        trace_id: u128::from_be_bytes(own.trace_id().to_bytes()),
+        span_id: u64::from_be_bytes(own.span_id().to_bytes()),
+        sampled: own.is_sampled(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry_sdk::error::OTelSdkResult;
+    use opentelemetry_sdk::trace::{SpanData, SpanExporter as ExportSpans};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone, Default)]
+    struct CollectingExporter(Arc<Mutex<Vec<SpanData>>>);
+
+    impl ExportSpans for CollectingExporter {
+        async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+            self.0.lock().unwrap().extend(batch);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_linked_span_continues_remote_trace() {
+        let exporter = CollectingExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer(TRACER_NAME)));
+
+        let upstream = TraceContext::root(true);
+        let linked = tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("tool_call");
+            link_span(&span, Some(upstream))
+        })
+        .unwrap();
+        provider.force_flush().unwrap();
+
+        let spans = exporter.0.lock().unwrap();
+        assert_eq!(spans.len(), 1);
+        let exported = &spans[0];
+        assert_eq!(linked.trace_id, upstream.trace_id);
+        assert_eq!(
+            exported.parent_span_id,
+            SpanId::from_bytes(upstream.span_id.to_be_bytes())
+        );
+        assert_eq!(
+            exported.span_context.span_id(),
+            SpanId::from_bytes(linked.span_id.to_be_bytes())
+        );
+    }
+}
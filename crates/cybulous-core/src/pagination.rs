@@ -0,0 +1,40 @@
+//! Cursor-based pagination of tool results
+//!
+//! A tool returning a partial collection includes a `next_cursor` field in
+//! its result. The orchestrator follows it by re-issuing the call with the
+//! cursor in the `cursor` parameter until no cursor is returned.
+
+use crate::orchestration::{ExecutionStatus, ToolCall, ToolResponse};
+
+/// Result field carrying the cursor for the next page
+pub const NEXT_CURSOR_KEY: &str = "next_cursor";
+
+/// Call parameter the cursor is passed back in
+pub const CURSOR_PARAM: &str = "cursor";
+
+/// Default maximum number of pages followed per paged call
+pub const DEFAULT_MAX_PAGES: usize = 100;
+
+/// Cursor for the page after `response`, if any
+pub fn next_cursor(response: &ToolResponse) -> Option<&serde_json::Value> {
+    if response.status != ExecutionStatus::Success {
+        return None;
+    }
+    response
+        .result
+        .as_ref()?
+        .get(NEXT_CURSOR_KEY)
+        .filter(|cursor| !cursor.is_null())
+}
+
+/// Call fetching the page after `response`, if any
+///
+/// The follow-up is a child of `call` so pages share lineage.
+pub fn next_page(call: &ToolCall, response: &ToolResponse) -> Option<ToolCall> {
+    let cursor = next_cursor(response)?.clone();
+    let mut parameters = call.parameters.clone();
+    parameters
+        .as_object_mut()?
+        .insert(CURSOR_PARAM.to_string(), cursor);
+    Some(call.derive_child(call.tool_name.clone(), parameters))
+}
@@ -0,0 +1,69 @@
+//! Post-execution invariants on tool results
+//!
+//! Executors declare postconditions for a response; the orchestrator checks
+//! them before returning and downgrades violating responses to `Failed`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Invariant a successful result must satisfy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Postcondition {
+    /// The JSON pointer must resolve to a non-null value
+    FieldPresent(String),
+    /// The JSON pointer must resolve to a number within `[min, max]`
+    InRange {
+        /// JSON pointer into the result
+        pointer: String,
+        /// Inclusive lower bound
+        min: f64,
+        /// Inclusive upper bound
+        max: f64,
+    },
+    /// An executor-evaluated invariant
+    Custom {
+        /// Human-readable description of the invariant
+        description: String,
+        /// Whether it holds
+        holds: bool,
+    },
+}
+
+impl Postcondition {
+    /// Check against a result, returning a violation message on failure
+    pub fn check(&self, result: Option<&Value>) -> std::result::Result<(), String> {
+        let lookup = |pointer: &str| result.and_then(|r| r.pointer(pointer));
+
+        match self {
+            Postcondition::FieldPresent(pointer) => match lookup(pointer) {
+                Some(value) if !value.is_null() => Ok(()),
+                _ => Err(format!("required field {} missing", pointer)),
+            },
+            Postcondition::InRange { pointer, min, max } => {
+                match lookup(pointer).and_then(Value::as_f64) {
+                    Some(value) if value >= *min && value <= *max => Ok(()),
+                    Some(value) => Err(format!(
+                        "field {} = {} outside [{}, {}]",
+                        pointer, value, min, max
+                    )),
+                    None => Err(format!("numeric field {} missing", pointer)),
+                }
+            }
+            Postcondition::Custom { description, holds } => {
+                if *holds {
+                    Ok(())
+                } else {
+                    Err(description.clone())
+                }
+            }
+        }
+    }
+}
+
+/// Check all postconditions, collecting every violation
+pub fn violations(postconditions: &[Postcondition], result: Option<&Value>) -> Vec<String> {
+    postconditions
+        .iter()
+        .filter_map(|p| p.check(result).err())
+        .collect()
+}
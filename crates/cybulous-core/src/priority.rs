@@ -0,0 +1,199 @@
+//! Priority admission with aging
+//!
+//! Calls waiting for a concurrency slot are admitted highest priority
+//! first, in arrival order within a priority. A call's priority comes from
+//! its `priority` context metadata and defaults to 0.
+//!
+//! Strict priority lets a steady stream of urgent calls starve everything
+//! else. With aging enabled, a waiting call gains one priority level per
+//! aging interval, so a long-waiting low-priority call eventually
+//! outranks newer arrivals.
+//!
+//! Under fail-fast queueing a call that finds no free slot is rejected
+//! immediately instead of waiting.
+
+use crate::orchestration::ToolCall;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore};
+
+/// Context metadata key carrying a call's admission priority; higher is
+/// more urgent
+pub const PRIORITY_KEY: &str = "priority";
+
+/// What a call does when every concurrency slot is taken
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QueueingBehavior {
+    /// Wait for a slot, bounded by the call's queue timeout if set
+    #[default]
+    Wait,
+    /// Reject the call at once
+    FailFast,
+}
+
+/// Admission priority of `call`
+pub fn call_priority(call: &ToolCall) -> i32 {
+    call.context
+        .metadata
+        .get(PRIORITY_KEY)
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(0)
+}
+
+#[derive(Debug)]
+struct Waiter {
+    priority: i32,
+    enqueued_at: Instant,
+}
+
+/// Concurrency slots handed out in priority order
+#[derive(Debug)]
+pub(crate) struct AdmissionQueue {
+    slots: Arc<Semaphore>,
+    waiters: Mutex<HashMap<u64, Waiter>>,
+    next_id: AtomicU64,
+    changed: Arc<Notify>,
+    aging_interval: Option<Duration>,
+}
+
+/// A held concurrency slot; waiters are woken when it is released
+#[derive(Debug)]
+pub(crate) struct AdmissionPermit {
+    _permit: OwnedSemaphorePermit,
+    changed: Arc<Notify>,
+}
+
+impl Drop for AdmissionPermit {
+    fn drop(&mut self) {
+        self.changed.notify_waiters();
+    }
+}
+
+/// Removes a waiter from the queue however its wait ends
+struct Registration<'a> {
+    queue: &'a AdmissionQueue,
+    id: u64,
+}
+
+impl Drop for Registration<'_> {
+    fn drop(&mut self) {
+        self.queue.lock().remove(&self.id);
+        self.queue.changed.notify_waiters();
+    }
+}
+
+impl AdmissionQueue {
+    /// Queue over `slots` concurrency slots, aging waiters by one priority
+    /// level per `aging_interval` if set
+    pub(crate) fn new(slots: usize, aging_interval: Option<Duration>) -> Self {
+        Self {
+            slots: Arc::new(Semaphore::new(slots)),
+            waiters: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(0),
+            changed: Arc::new(Notify::new()),
+            aging_interval,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<u64, Waiter>> {
+        self.waiters.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Wait for a slot as a call of `priority`
+    pub(crate) async fn acquire(&self, priority: i32) -> AdmissionPermit {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.lock().insert(
+            id,
+            Waiter {
+                priority,
+                enqueued_at: Instant::now(),
+            },
+        );
+        let _registration = Registration { queue: self, id };
+
+        loop {
+            // Register for wakeups before checking so a release between
+            // the check and the wait is not missed
+            let changed = self.changed.notified();
+            tokio::pin!(changed);
+            changed.as_mut().enable();
+
+            if self.next_waiter(Instant::now()) == Some(id) {
+                if let Ok(permit) = self.slots.clone().try_acquire_owned() {
+                    return AdmissionPermit {
+                        _permit: permit,
+                        changed: self.changed.clone(),
+                    };
+                }
+            }
+            changed.await;
+        }
+    }
+
+    /// Take a free slot without waiting
+    ///
+    /// Fails if calls are already queued, so fail-fast callers cannot jump
+    /// ahead of waiting ones.
+    pub(crate) fn try_acquire(&self) -> Option<AdmissionPermit> {
+        if !self.lock().is_empty() {
+            return None;
+        }
+        let permit = self.slots.clone().try_acquire_owned().ok()?;
+        Some(AdmissionPermit {
+            _permit: permit,
+            changed: self.changed.clone(),
+        })
+    }
+
+    /// Waiter to admit next: highest effective priority, then earliest
+    fn next_waiter(&self, now: Instant) -> Option<u64> {
+        self.lock()
+            .iter()
+            .map(|(id, waiter)| (self.effective_priority(waiter, now), *id, waiter))
+            .max_by(|(a, a_id, a_waiter), (b, b_id, b_waiter)| {
+                a.total_cmp(b)
+                    .then(b_waiter.enqueued_at.cmp(&a_waiter.enqueued_at))
+                    .then(b_id.cmp(a_id))
+            })
+            .map(|(_, id, _)| id)
+    }
+
+    fn effective_priority(&self, waiter: &Waiter, now: Instant) -> f64 {
+        let aged = match self.aging_interval {
+            Some(interval) if !interval.is_zero() => {
+                now.duration_since(waiter.enqueued_at).as_secs_f64() / interval.as_secs_f64()
+            }
+            _ => 0.0,
+        };
+        f64::from(waiter.priority) + aged.floor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aging_raises_priority_per_interval() {
+        let queue = AdmissionQueue::new(1, Some(Duration::from_millis(100)));
+        let now = Instant::now();
+        let waiter = Waiter {
+            priority: 1,
+            enqueued_at: now,
+        };
+        assert_eq!(queue.effective_priority(&waiter, now), 1.0);
+        assert_eq!(
+            queue.effective_priority(&waiter, now + Duration::from_millis(250)),
+            3.0
+        );
+
+        let unaged = AdmissionQueue::new(1, None);
+        assert_eq!(
+            unaged.effective_priority(&waiter, now + Duration::from_secs(60)),
+            1.0
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Prometheus metrics for orchestration
+//!
+//! Counts executions by tool and status, records their durations, tracks
+//! the admission queue depth and counts consent denials. Hosting services
+//! scrape [`PrometheusMetrics::encode`] from their `/metrics` endpoint or
+//! merge [`PrometheusMetrics::registry`] into a registry of their own.
+
+use crate::orchestration::ExecutionStatus;
+use prometheus_client::encoding::text::encode;
+use prometheus_client::encoding::EncodeLabelSet;
+use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::metrics::histogram::{exponential_buckets, Histogram};
+use prometheus_client::registry::{Registry, Unit};
+
+/// Prefix of every metric name
+pub const METRICS_PREFIX: &str = "cybulous";
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ExecutionLabels {
+    tool: String,
+    status: &'static str,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ToolLabels {
+    tool: String,
+}
+
+/// Orchestration metrics and the registry exposing them
+#[derive(Debug)]
+pub struct PrometheusMetrics {
+    registry: Registry,
+    executions: Family<ExecutionLabels, Counter>,
+    durations: Family<ToolLabels, Histogram>,
+    queue_depth: Gauge,
+    consent_denials: Family<ToolLabels, Counter>,
+}
+
+impl Default for PrometheusMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrometheusMetrics {
+    /// Register the orchestration metrics in a fresh registry
+    pub fn new() -> Self {
+        let mut registry = Registry::with_prefix(METRICS_PREFIX);
+        let executions = Family::<ExecutionLabels, Counter>::default();
+        registry.register(
+            "executions",
+            "Tool calls completed, by tool and status",
+            executions.clone(),
+        );
+        // 1ms to ~33s
+        let durations = Family::<ToolLabels, Histogram>::new_with_constructor(duration_histogram);
+        registry.register_with_unit(
+            "execution_duration",
+            "Tool call duration",
+            Unit::Seconds,
+            durations.clone(),
+        );
+        let queue_depth = Gauge::default();
+        registry.register(
+            "queue_depth",
+            "Calls waiting for a concurrency slot",
+            queue_depth.clone(),
+        );
+        let consent_denials = Family::<ToolLabels, Counter>::default();
+        registry.register(
+            "consent_denials",
+            "Tool calls denied for lack of consent, by tool",
+            consent_denials.clone(),
+        );
+        Self {
+            registry,
+            executions,
+            durations,
+            queue_depth,
+            consent_denials,
+        }
+    }
+
+    /// Registry holding the metrics
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Metrics in the Prometheus text exposition format
+    pub fn encode(&self) -> String {
+        let mut payload = String::new();
+        encode(&mut payload, &self.registry).expect("writing to a String cannot fail");
+        payload
+    }
+
+    /// Record a completed call
+    pub(crate) fn record(&self, tool: &str, status: ExecutionStatus, duration_ms: u64) {
+        self.executions
+            .get_or_create(&ExecutionLabels {
+                tool: tool.to_string(),
+                status: status_label(status),
+            })
+            .inc();
+        let tool = ToolLabels {
+            tool: tool.to_string(),
+        };
+        self.durations
+            .get_or_create(&tool)
+            .observe(duration_ms as f64 / 1000.0);
+        if status == ExecutionStatus::ConsentDenied {
+            self.consent_denials.get_or_create(&tool).inc();
+        }
+    }
+
+    /// Report the current admission queue depth
+    pub(crate) fn set_queue_depth(&self, depth: usize) {
+        self.queue_depth.set(depth as i64);
+    }
+}
+
+fn duration_histogram() -> Histogram {
+    Histogram::new(exponential_buckets(0.001, 2.0, 16))
+}
+
+/// Label value for `status`
+fn status_label(status: ExecutionStatus) -> &'static str {
+    match status {
+        ExecutionStatus::Success => "success",
+        ExecutionStatus::Failed => "failed",
+        ExecutionStatus::Timeout => "timeout",
+        ExecutionStatus::QueueTimeout => "queue_timeout",
+        ExecutionStatus::Rejected => "rejected",
+        ExecutionStatus::ConsentDenied => "consent_denied",
+        ExecutionStatus::Cancelled(_) => "cancelled",
+    }
+}
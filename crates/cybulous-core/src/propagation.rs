@@ -0,0 +1,133 @@
+//! Trace context propagation across tool calls
+//!
+//! Every call carries a W3C `traceparent` in its context metadata naming
+//! the trace it belongs to and its own span, so remote executors can
+//! continue the distributed trace. A call arriving with a `traceparent`
+//! joins that trace as a child span; one without starts a new trace.
+//! Derived calls inherit the parent's metadata and so become its children.
+
+use rand::Rng;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Context metadata key carrying the call's W3C trace context
+pub const TRACEPARENT_KEY: &str = "traceparent";
+
+/// Position of a call in a distributed trace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    /// Trace the call belongs to
+    pub trace_id: u128,
+    /// The call's own span
+    pub span_id: u64,
+    /// Whether the trace is being recorded
+    pub sampled: bool,
+}
+
+impl TraceContext {
+    /// Start a new trace
+    pub fn root(sampled: bool) -> Self {
+        let mut rng = rand::thread_rng();
+        Self {
+            trace_id: rng.gen_range(1..=u128::MAX),
+            span_id: rng.gen_range(1..=u64::MAX),
+            sampled,
+        }
+    }
+
+    /// Open a new span in the same trace
+    pub fn child(&self, sampled: bool) -> Self {
+        Self {
+            span_id: rand::thread_rng().gen_range(1..=u64::MAX),
+            sampled,
+            ..*self
+        }
+    }
+
+    /// Parse a `traceparent` header value
+    ///
+    /// Returns `None` for malformed values and the all-zero ids the W3C
+    /// spec declares invalid.
+    pub fn parse(traceparent: &str) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next().filter(|v| v.len() == 2 && *v != "ff")?;
+        let trace_id = parts.next().filter(|t| t.len() == 32)?;
+        let span_id = parts.next().filter(|s| s.len() == 16)?;
+        let flags = parts.next().filter(|f| f.len() == 2)?;
+        // Version 00 has exactly four fields; later versions may append
+        if version == "00" && parts.next().is_some() {
+            return None;
+        }
+        u8::from_str_radix(version, 16).ok()?;
+        Some(Self {
+            trace_id: u128::from_str_radix(trace_id, 16)
+                .ok()
+                .filter(|&t| t != 0)?,
+            span_id: u64::from_str_radix(span_id, 16).ok().filter(|&s| s != 0)?,
+            sampled: u8::from_str_radix(flags, 16).ok()? & 1 == 1,
+        })
+    }
+
+    /// Trace context carried in `metadata`, if any
+    pub fn from_metadata(metadata: &HashMap<String, String>) -> Option<Self> {
+        metadata.get(TRACEPARENT_KEY).and_then(|t| Self::parse(t))
+    }
+
+    /// Store this context in `metadata`, replacing any already there
+    pub fn inject(&self, metadata: &mut HashMap<String, String>) {
+        metadata.insert(TRACEPARENT_KEY.to_string(), self.to_string());
+    }
+
+    /// Trace id as 32 lowercase hex digits
+    pub fn trace_id_hex(&self) -> String {
+        format!("{:032x}", self.trace_id)
+    }
+
+    /// Span id as 16 lowercase hex digits
+    pub fn span_id_hex(&self) -> String {
+        format!("{:016x}", self.span_id)
+    }
+}
+
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "00-{}-{}-{:02x}",
+            self.trace_id_hex(),
+            self.span_id_hex(),
+            u8::from(self.sampled)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traceparent_round_trips() {
+        let header = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let parsed = TraceContext::parse(header).unwrap();
+        assert_eq!(parsed.trace_id_hex(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(parsed.span_id, 0x00f067aa0ba902b7);
+        assert!(parsed.sampled);
+        assert_eq!(parsed.to_string(), header);
+
+        let child = parsed.child(false);
+        assert_eq!(child.trace_id, parsed.trace_id);
+        assert_ne!(child.span_id, parsed.span_id);
+        assert!(child.to_string().ends_with("-00"));
+
+        for invalid in [
+            "",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "00-4bf92f3577b34da6a3ce929d0e0e473g-00f067aa0ba902b7-01",
+        ] {
+            assert_eq!(TraceContext::parse(invalid), None, "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Fixed-window rate limiting
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Rate limit configuration: at most `max_calls` per `window`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// Calls admitted per window
+    pub max_calls: u32,
+    /// Window length
+    pub window: Duration,
+}
+
+#[derive(Debug)]
+struct Window {
+    started: Instant,
+    count: u32,
+}
+
+/// Fixed-window rate limiter
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    window: Mutex<Window>,
+}
+
+impl RateLimiter {
+    /// Create a limiter enforcing `limit`
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            window: Mutex::new(Window {
+                started: Instant::now(),
+                count: 0,
+            }),
+        }
+    }
+
+    /// Configured limit
+    pub fn limit(&self) -> RateLimit {
+        self.limit
+    }
+
+    /// Admit one call if the current window has capacity
+    pub fn try_acquire(&self) -> bool {
+        let mut window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+
+        if window.started.elapsed() >= self.limit.window {
+            window.started = Instant::now();
+            window.count = 0;
+        }
+
+        if window.count < self.limit.max_calls {
+            window.count += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Scope-based redaction of tool results
+//!
+//! A tool's result may carry fields the user has not consented to
+//! receive. A `ResultScopeFilter` maps result fields, addressed by JSON
+//! pointer, to the consent scope each requires; fields whose scope the
+//! user lacks are removed before the response leaves the orchestrator.
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Consent scopes required to receive fields of a tool's result
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResultScopeFilter {
+    fields: BTreeMap<String, String>,
+}
+
+impl ResultScopeFilter {
+    /// Filter requiring no scopes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require `scope` to receive the field at JSON pointer `pointer`
+    pub fn require(mut self, pointer: impl Into<String>, scope: impl Into<String>) -> Self {
+        self.fields.insert(pointer.into(), scope.into());
+        self
+    }
+
+    /// Distinct scopes the filter checks
+    pub fn scopes(&self) -> impl Iterator<Item = &str> {
+        let mut scopes: Vec<&str> = self.fields.values().map(String::as_str).collect();
+        scopes.sort_unstable();
+        scopes.dedup();
+        scopes.into_iter()
+    }
+
+    /// Remove fields whose scope `granted` rejects, returning the pointers
+    /// of the fields removed
+    pub fn redact(&self, result: &mut Value, granted: impl Fn(&str) -> bool) -> Vec<String> {
+        let mut redacted = Vec::new();
+        // Reverse order removes nested fields before their parents
+        for (pointer, scope) in self.fields.iter().rev() {
+            if !granted(scope) && remove_pointer(result, pointer) {
+                redacted.push(pointer.clone());
+            }
+        }
+        redacted
+    }
+}
+
+/// Remove the object member at `pointer`, returning whether it existed
+fn remove_pointer(value: &mut Value, pointer: &str) -> bool {
+    let Some((parent, key)) = pointer.rsplit_once('/') else {
+        return false;
+    };
+    let key = key.replace("~1", "/").replace("~0", "~");
+    value
+        .pointer_mut(parent)
+        .and_then(Value::as_object_mut)
+        .and_then(|object| object.remove(&key))
+        .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_nested_and_escaped_fields() {
+        let filter = ResultScopeFilter::new()
+            .require("/profile/email", "contact")
+            .require("/profile", "profile")
+            .require("/a~1b", "contact");
+        let mut result = serde_json::json!({
+            "id": 7,
+            "profile": { "name": "Ada", "email": "ada@example.com" },
+            "a/b": true,
+        });
+
+        let redacted = filter.redact(&mut result, |scope| scope == "profile");
+        assert_eq!(redacted, ["/profile/email", "/a~1b"]);
+        assert_eq!(
+            result,
+            serde_json::json!({ "id": 7, "profile": { "name": "Ada" } })
+        );
+        assert_eq!(filter.scopes().collect::<Vec<_>>(), ["contact", "profile"]);
+    }
+}
@@ -0,0 +1,61 @@
+//! Consent-driven data retention
+//!
+//! Cascades the retention period a user's consent dictates into the expiry
+//! of artifacts stored on their behalf.
+
+use chrono::{DateTime, Utc};
+use cybulous_consent::ConsentEngine;
+use std::time::Duration;
+
+/// Effective expiry of an artifact owned by `user_id`
+///
+/// The earlier of the artifact's own TTL and the consent retention bound
+/// wins. Returns `None` when neither imposes a limit.
+pub async fn artifact_expiry(
+    consent_engine: &ConsentEngine,
+    user_id: &str,
+    created_at: DateTime<Utc>,
+    artifact_ttl: Option<Duration>,
+) -> Option<DateTime<Utc>> {
+    let retention = consent_engine.retention_for(user_id).await;
+
+    [artifact_ttl, retention]
+        .into_iter()
+        .flatten()
+        .filter_map(|ttl| chrono::Duration::from_std(ttl).ok())
+        .map(|ttl| created_at + ttl)
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_consent_retention_drives_artifact_expiry() {
+        let engine = ConsentEngine::mock();
+        engine.request_consent("retained-user").await.unwrap();
+        let created_at = Utc::now();
+        let day = Duration::from_secs(86_400);
+
+        // No consent bound: the artifact TTL applies
+        assert_eq!(
+            artifact_expiry(&engine, "retained-user", created_at, Some(day * 30)).await,
+            Some(created_at + chrono::Duration::days(30))
+        );
+
+        // A shorter consent retention cuts the artifact's life short
+        engine
+            .set_retention("retained-user", Some(day * 7))
+            .await
+            .unwrap();
+        assert_eq!(
+            artifact_expiry(&engine, "retained-user", created_at, Some(day * 30)).await,
+            Some(created_at + chrono::Duration::days(7))
+        );
+        assert_eq!(
+            artifact_expiry(&engine, "retained-user", created_at, None).await,
+            Some(created_at + chrono::Duration::days(7))
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Automatic retries with exponential backoff
+//!
+//! A `RetryPolicy` re-runs a call whose attempt ended in a retryable status,
+//! waiting `base_delay * 2^(attempt - 1)` between attempts, capped at
+//! `max_delay` and spread by a jitter fraction so retries from many callers
+//! do not land together. Executors may veto individual retries, e.g. for
+//! failures they know to be permanent.
+
+use crate::orchestration::ExecutionStatus;
+use rand::Rng;
+use std::time::Duration;
+
+/// Default cap on the delay between attempts
+pub const DEFAULT_MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Whether `status` is a transient outcome worth retrying
+///
+/// The default predicate: executor failures and timeouts.
+pub fn transient_status(status: ExecutionStatus) -> bool {
+    matches!(status, ExecutionStatus::Failed | ExecutionStatus::Timeout)
+}
+
+/// How often and how patiently to retry a call
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: f64,
+    retryable: fn(ExecutionStatus) -> bool,
+}
+
+impl RetryPolicy {
+    /// Make up to `max_attempts` attempts in total, backing off from
+    /// `base_delay`
+    pub fn new(max_attempts: u32, base_delay: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay,
+            max_delay: DEFAULT_MAX_RETRY_DELAY,
+            jitter: 0.0,
+            retryable: transient_status,
+        }
+    }
+
+    /// Cap the delay between attempts at `max_delay`
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Spread each delay uniformly within `jitter` of itself, as a fraction
+    /// clamped to `[0, 1]`
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Retry only attempts whose status satisfies `retryable`
+    pub fn retry_if(mut self, retryable: fn(ExecutionStatus) -> bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// Attempts made in total, including the first
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Whether to make another attempt after `attempt` ended in `status`
+    pub fn should_retry(&self, attempt: u32, status: ExecutionStatus) -> bool {
+        attempt < self.max_attempts && (self.retryable)(status)
+    }
+
+    /// Delay before the attempt following `attempt`, before jitter
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+
+    /// Jittered delay before the attempt following `attempt`
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let backoff = self.backoff(attempt);
+        if self.jitter == 0.0 {
+            return backoff;
+        }
+        let spread = rand::thread_rng().gen_range(-self.jitter..=self.jitter);
+        backoff.mul_f64(1.0 + spread)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let policy = RetryPolicy::new(6, Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(500));
+        let delays: Vec<_> = (1..=5).map(|a| policy.backoff(a).as_millis()).collect();
+        assert_eq!(delays, vec![100, 200, 400, 500, 500]);
+        assert_eq!(policy.backoff(40), Duration::from_millis(500));
+
+        let jittered = policy.with_jitter(0.5);
+        for _ in 0..100 {
+            let delay = jittered.delay(2).as_millis();
+            assert!((100..=300).contains(&delay), "delay {}", delay);
+        }
+    }
+
+    #[test]
+    fn test_should_retry_respects_attempts_and_predicate() {
+        let policy = RetryPolicy::new(3, Duration::ZERO);
+        assert!(policy.should_retry(1, ExecutionStatus::Failed));
+        assert!(policy.should_retry(2, ExecutionStatus::Timeout));
+        assert!(!policy.should_retry(3, ExecutionStatus::Failed));
+        assert!(!policy.should_retry(1, ExecutionStatus::ConsentDenied));
+
+        let timeouts_only = policy.retry_if(|s| s == ExecutionStatus::Timeout);
+        assert!(!timeouts_only.should_retry(1, ExecutionStatus::Failed));
+    }
+}
@@ -0,0 +1,83 @@
+//! Metadata-based routing of tool calls
+//!
+//! Routing rules send a call to a specific executor when its context
+//! metadata matches, e.g. `region=eu` to an EU-hosted executor. Calls that
+//! match no rule resolve to the executor registered under the tool name.
+//!
+//! Locality routes prefer an executor co-located with the region where the
+//! user's consent is recorded, so verification stays in-region. They apply
+//! only to calls no metadata rule claimed.
+//!
+//! Calls that name a required capability instead of a tool go to any ready
+//! executor advertising it, chosen by a `CapabilitySelection` strategy.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Route calls for a tool to another executor when metadata matches
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoutingRule {
+    /// Tool whose calls this rule applies to
+    pub tool_name: String,
+    /// Metadata key to match
+    pub key: String,
+    /// Required metadata value
+    pub value: String,
+    /// Executor to route matching calls to
+    pub executor: String,
+}
+
+impl RoutingRule {
+    /// Whether this rule applies to a call for `tool_name` with `metadata`
+    pub fn matches(&self, tool_name: &str, metadata: &HashMap<String, String>) -> bool {
+        self.tool_name == tool_name && metadata.get(&self.key) == Some(&self.value)
+    }
+}
+
+/// Route calls for a tool to the executor in the user's consent region
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalityRoute {
+    /// Tool whose calls this route applies to
+    pub tool_name: String,
+    /// Region where the user's consent is recorded
+    pub region: String,
+    /// Executor co-located with that region
+    pub executor: String,
+}
+
+/// How to choose among executors advertising a required capability
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CapabilitySelection {
+    /// The executor whose tool name sorts first
+    #[default]
+    First,
+    /// Rotate through the matching executors call by call
+    RoundRobin,
+    /// A matching executor chosen uniformly at random
+    Random,
+}
+
+impl CapabilitySelection {
+    /// Pick from `candidates`, sorted by tool name, for the `turn`th call
+    /// requiring the capability
+    pub(crate) fn select<'a>(&self, candidates: &'a [String], turn: usize) -> Option<&'a String> {
+        if candidates.is_empty() {
+            return None;
+        }
+        let index = match self {
+            Self::First => 0,
+            Self::RoundRobin => turn % candidates.len(),
+            Self::Random => rand::thread_rng().gen_range(0..candidates.len()),
+        };
+        candidates.get(index)
+    }
+}
+
+impl LocalityRoute {
+    /// Whether this route applies to a call for `tool_name` whose user's
+    /// consent is recorded in `region`
+    pub fn matches(&self, tool_name: &str, region: &str) -> bool {
+        self.tool_name == tool_name && self.region == region
+    }
+}
@@ -0,0 +1,93 @@
+//! Sagas: tool pipelines with compensating actions
+//!
+//! Each step pairs a forward action with a compensating action that undoes
+//! it. Steps run in order; if one fails, the compensations of every step
+//! that already completed run in reverse order.
+
+use serde::{Deserialize, Serialize};
+
+/// A single tool invocation within a saga
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SagaAction {
+    /// Tool to invoke
+    pub tool_name: String,
+    /// Tool parameters
+    pub parameters: serde_json::Value,
+}
+
+impl SagaAction {
+    /// Action invoking `tool_name` with `parameters`
+    pub fn new(tool_name: impl Into<String>, parameters: serde_json::Value) -> Self {
+        Self {
+            tool_name: tool_name.into(),
+            parameters,
+        }
+    }
+}
+
+/// A forward action and the action that compensates for it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SagaStep {
+    /// Step identifier, unique within the saga
+    pub id: String,
+    /// Action performing the step
+    pub forward: SagaAction,
+    /// Action undoing the step after a later step fails
+    pub compensation: SagaAction,
+}
+
+/// Ordered steps executed with rollback on failure
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Saga {
+    /// Saga steps, in execution order
+    pub steps: Vec<SagaStep>,
+}
+
+impl Saga {
+    /// Saga with no steps
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a step
+    pub fn step(
+        mut self,
+        id: impl Into<String>,
+        forward: SagaAction,
+        compensation: SagaAction,
+    ) -> Self {
+        self.steps.push(SagaStep {
+            id: id.into(),
+            forward,
+            compensation,
+        });
+        self
+    }
+}
+
+/// Result of executing a saga
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SagaOutcome {
+    /// Steps whose forward action succeeded, in execution order
+    pub completed: Vec<String>,
+    /// Step whose forward action failed, if any
+    pub failed_step: Option<String>,
+    /// Why the failed step failed
+    pub error: Option<String>,
+    /// Steps successfully compensated, in compensation order
+    pub compensated: Vec<String>,
+    /// Steps whose compensation failed, in compensation order
+    pub compensation_failures: Vec<String>,
+}
+
+impl SagaOutcome {
+    /// Whether every step completed without needing rollback
+    pub fn is_committed(&self) -> bool {
+        self.failed_step.is_none()
+    }
+
+    /// Whether a failure was fully rolled back
+    pub fn is_rolled_back(&self) -> bool {
+        self.failed_step.is_some() && self.compensation_failures.is_empty()
+    }
+}
@@ -0,0 +1,105 @@
+//! Head-based trace sampling
+//!
+//! Recording a detailed span for every call is costly at scale. The
+//! orchestrator decides once, at the head of a call chain, whether to trace
+//! it and records the decision in the context metadata so derived calls and
+//! remote executors follow the same decision. Failed calls are traced
+//! regardless of the decision.
+//!
+//! [`OutcomeSampler`] makes a second, tail-based decision once the outcome
+//! is known: successes are kept at a low rate while failures, timeouts and
+//! consent denials are always recorded in full.
+
+use crate::orchestration::{ExecutionStatus, ToolCall};
+
+/// Context metadata key carrying the sampling decision (`"true"`/`"false"`)
+pub const TRACE_SAMPLED_KEY: &str = "trace_sampled";
+
+/// Samples a fixed fraction of call chains
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TraceSampler {
+    rate: f64,
+}
+
+impl Default for TraceSampler {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl TraceSampler {
+    /// Sample `rate` of calls, clamped to `[0, 1]`
+    pub fn new(rate: f64) -> Self {
+        Self {
+            rate: rate.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Configured sampling rate
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Whether to trace `call`, honouring an upstream decision if present
+    ///
+    /// Fresh decisions are derived from the call id, so they are stable for
+    /// a given call and uniformly distributed across calls.
+    pub fn sample(&self, call: &ToolCall) -> bool {
+        if let Some(decision) = sampling_decision(call) {
+            return decision;
+        }
+        call_bucket(call) < self.rate
+    }
+}
+
+/// Decides which finished calls get full telemetry, based on their outcome
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutcomeSampler {
+    success_rate: f64,
+}
+
+impl OutcomeSampler {
+    /// Keep `success_rate` of successful calls, clamped to `[0, 1]`
+    pub fn new(success_rate: f64) -> Self {
+        Self {
+            success_rate: success_rate.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Configured rate for successful calls
+    pub fn success_rate(&self) -> f64 {
+        self.success_rate
+    }
+
+    /// Whether `call`, finishing with `status`, gets full telemetry
+    ///
+    /// Failures, timeouts and consent denials are always recorded.
+    /// Successes and cancellations are kept at the success rate, decided
+    /// from the call id so the choice is stable for a given call.
+    pub fn records(&self, call: &ToolCall, status: ExecutionStatus) -> bool {
+        match status {
+            ExecutionStatus::Success | ExecutionStatus::Cancelled(_) => {
+                call_bucket(call) < self.success_rate
+            }
+            ExecutionStatus::Failed
+            | ExecutionStatus::Timeout
+            | ExecutionStatus::QueueTimeout
+            | ExecutionStatus::Rejected
+            | ExecutionStatus::ConsentDenied => true,
+        }
+    }
+}
+
+// Position of `call` in `[0, 1]`. Only the high half of the id is used; the
+// low half carries fixed variant bits.
+fn call_bucket(call: &ToolCall) -> f64 {
+    (call.id.as_u128() >> 64) as u64 as f64 / u64::MAX as f64
+}
+
+/// Sampling decision propagated with `call`, if any
+pub fn sampling_decision(call: &ToolCall) -> Option<bool> {
+    call.context
+        .metadata
+        .get(TRACE_SAMPLED_KEY)
+        .and_then(|d| d.parse().ok())
+}
@@ -0,0 +1,174 @@
+//! JSON Schema validation of call parameters
+//!
+//! Executors may publish a JSON Schema for their input. Calls are checked
+//! against it after request transforms run, before dispatch, and rejected
+//! with every violation found, each naming the offending field as a JSON
+//! pointer. Compiled schemas are cached per tool and recompiled when the
+//! executor's schema changes.
+
+use crate::{CybulousError, Result};
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::Validator;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// One way call parameters violate a tool's input schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldViolation {
+    /// JSON pointer to the offending field; empty for the parameters as a
+    /// whole
+    pub field: String,
+    /// What is wrong with it
+    pub message: String,
+}
+
+/// Every violation found in a call's parameters
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolations {
+    /// Tool whose schema was violated
+    pub tool_name: String,
+    /// Violations, in schema order
+    pub violations: Vec<FieldViolation>,
+}
+
+impl SchemaViolations {
+    /// Pointers to the offending fields
+    pub fn fields(&self) -> Vec<&str> {
+        self.violations.iter().map(|v| v.field.as_str()).collect()
+    }
+}
+
+impl fmt::Display for SchemaViolations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} parameters", self.tool_name)?;
+        for (i, violation) in self.violations.iter().enumerate() {
+            let separator = if i == 0 { ": " } else { "; " };
+            let field = match violation.field.as_str() {
+                "" => "/",
+                field => field,
+            };
+            write!(f, "{}{} {}", separator, field, violation.message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compile `schema`, rejecting schemas that are not valid JSON Schema
+pub(crate) fn compile(tool_name: &str, schema: &serde_json::Value) -> Result<Validator> {
+    jsonschema::validator_for(schema).map_err(|e| {
+        CybulousError::OrchestrationFailed(format!(
+            "{} has an invalid input schema: {}",
+            tool_name, e
+        ))
+    })
+}
+
+/// Compiled input schemas by tool
+#[derive(Default)]
+pub(crate) struct SchemaCache {
+    compiled: Mutex<HashMap<String, (serde_json::Value, Arc<Validator>)>>,
+}
+
+impl fmt::Debug for SchemaCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let compiled = self.compiled.lock().unwrap_or_else(|e| e.into_inner());
+        f.debug_struct("SchemaCache")
+            .field("tools", &compiled.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl SchemaCache {
+    /// Check `parameters` against `tool_name`'s input `schema`
+    pub(crate) fn validate(
+        &self,
+        tool_name: &str,
+        schema: &serde_json::Value,
+        parameters: &serde_json::Value,
+    ) -> Result<()> {
+        let validator = {
+            let mut compiled = self.compiled.lock().unwrap_or_else(|e| e.into_inner());
+            match compiled.get(tool_name) {
+                Some((cached, validator)) if cached == schema => validator.clone(),
+                _ => {
+                    let validator = Arc::new(compile(tool_name, schema)?);
+                    compiled.insert(tool_name.to_string(), (schema.clone(), validator.clone()));
+                    validator
+                }
+            }
+        };
+
+        let violations: Vec<_> = validator
+            .iter_errors(parameters)
+            .map(|error| {
+                // Point at the missing property rather than its parent
+                let field = match &error.kind {
+                    ValidationErrorKind::Required {
+                        property: serde_json::Value::String(property),
+                    } => error.instance_path.join(property).as_str().to_string(),
+                    _ => error.instance_path.as_str().to_string(),
+                };
+                FieldViolation {
+                    field,
+                    message: error.to_string(),
+                }
+            })
+            .collect();
+        if violations.is_empty() {
+            return Ok(());
+        }
+        Err(CybulousError::SchemaViolation(SchemaViolations {
+            tool_name: tool_name.to_string(),
+            violations,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_violations_name_offending_fields() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "unit": { "enum": ["c", "f"] },
+                "readings": { "type": "array", "items": { "type": "number" } }
+            },
+            "required": ["unit", "readings"]
+        });
+        let cache = SchemaCache::default();
+        cache
+            .validate(
+                "convert",
+                &schema,
+                &json!({ "unit": "c", "readings": [1.5] }),
+            )
+            .unwrap();
+
+        let err = cache
+            .validate(
+                "convert",
+                &schema,
+                &json!({ "unit": "k", "readings": [1, "x"] }),
+            )
+            .unwrap_err();
+        let CybulousError::SchemaViolation(violations) = err else {
+            panic!("expected a schema violation, got {:?}", err);
+        };
+        let mut fields = violations.fields();
+        fields.sort();
+        assert_eq!(fields, ["/readings/1", "/unit"]);
+
+        let err = cache
+            .validate("convert", &schema, &json!({ "unit": "c" }))
+            .unwrap_err();
+        assert!(err.to_string().contains("/readings"), "{}", err);
+
+        assert!(compile("broken", &json!({ "type": 5 })).is_err());
+    }
+}
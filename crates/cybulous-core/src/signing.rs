@@ -0,0 +1,116 @@
+//! Service-to-service request signing
+//!
+//! An internal service signs the stable fields of a `ToolCall` with its
+//! Ed25519 key and attaches the signature to the context metadata. The
+//! orchestrator verifies it against a keyring of registered services before
+//! execution. Timeouts and metadata are excluded from the signed payload
+//! since intermediaries may adjust them in flight.
+
+use crate::orchestration::ToolCall;
+use crate::{CybulousError, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::Serialize;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Context metadata key naming the calling service
+pub const SERVICE_ID_KEY: &str = "service_id";
+
+/// Context metadata key carrying the calling service's signature
+pub const SERVICE_SIGNATURE_KEY: &str = "service_signature";
+
+/// How the orchestrator treats unsigned or invalid calls
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigningMode {
+    /// Log verification failures but allow the call
+    Monitor,
+    /// Reject calls that are unsigned or fail verification
+    Enforce,
+}
+
+#[derive(Serialize)]
+struct SignedFields<'a> {
+    id: Uuid,
+    tool_name: &'a str,
+    parameters: &'a serde_json::Value,
+    user_id: &'a str,
+    session_id: Uuid,
+    consent_proof: &'a str,
+}
+
+/// Canonical bytes covered by a service signature
+pub fn signing_payload(call: &ToolCall) -> Result<Vec<u8>> {
+    Ok(serde_json::to_vec(&SignedFields {
+        id: call.id,
+        tool_name: &call.tool_name,
+        parameters: &call.parameters,
+        user_id: &call.user_id,
+        session_id: call.context.session_id,
+        consent_proof: &call.context.consent_proof,
+    })?)
+}
+
+/// Sign a call as `service_id`, attaching the signature to its metadata
+pub fn sign_call(call: &mut ToolCall, service_id: &str, key: &SigningKey) -> Result<()> {
+    let signature = key.sign(&signing_payload(call)?);
+    let metadata = &mut call.context.metadata;
+    metadata.insert(SERVICE_ID_KEY.to_string(), service_id.to_string());
+    metadata.insert(
+        SERVICE_SIGNATURE_KEY.to_string(),
+        URL_SAFE_NO_PAD.encode(signature.to_bytes()),
+    );
+    Ok(())
+}
+
+/// Registered service verifying keys
+#[derive(Debug, Clone)]
+pub struct ServiceKeyring {
+    mode: SigningMode,
+    keys: HashMap<String, VerifyingKey>,
+}
+
+impl ServiceKeyring {
+    /// Create an empty keyring
+    pub fn new(mode: SigningMode) -> Self {
+        Self {
+            mode,
+            keys: HashMap::new(),
+        }
+    }
+
+    /// Register a service's verifying key
+    pub fn with_service(mut self, service_id: impl Into<String>, key: VerifyingKey) -> Self {
+        self.keys.insert(service_id.into(), key);
+        self
+    }
+
+    /// Enforcement mode
+    pub fn mode(&self) -> SigningMode {
+        self.mode
+    }
+
+    /// Verify a call's signature, returning the authenticated service id
+    pub fn verify(&self, call: &ToolCall) -> Result<String> {
+        let denied = |reason: String| CybulousError::AccessDenied(reason);
+        let metadata = &call.context.metadata;
+
+        let service_id = metadata
+            .get(SERVICE_ID_KEY)
+            .ok_or_else(|| denied("call is not signed".to_string()))?;
+        let key = self
+            .keys
+            .get(service_id)
+            .ok_or_else(|| denied(format!("unknown service {}", service_id)))?;
+        let signature = metadata
+            .get(SERVICE_SIGNATURE_KEY)
+            .and_then(|s| URL_SAFE_NO_PAD.decode(s).ok())
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or_else(|| denied(format!("malformed signature from {}", service_id)))?;
+
+        key.verify(&signing_payload(call)?, &signature)
+            .map_err(|_| denied(format!("invalid signature from {}", service_id)))?;
+        Ok(service_id.clone())
+    }
+}
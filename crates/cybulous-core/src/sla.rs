@@ -0,0 +1,177 @@
+//! Per-tool service level agreements
+//!
+//! The orchestrator evaluates each tool's SLA over a rolling window of
+//! recent calls and emits an `SlaBreach` when the tool moves out of
+//! compliance. Recovery is visible through the tool's `SlaStatus`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Number of recent calls an SLA is evaluated over
+pub const SLA_WINDOW: usize = 100;
+
+/// Latency and success targets for a tool
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SlaTarget {
+    /// Maximum acceptable 95th percentile latency
+    pub p95_latency: Duration,
+    /// Minimum acceptable success rate, in `[0, 1]`
+    pub min_success_rate: f64,
+}
+
+/// Current SLA compliance for a tool
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlaStatus {
+    /// Tool the SLA applies to
+    pub tool_name: String,
+    /// Configured target
+    pub target: SlaTarget,
+    /// Calls in the evaluation window
+    pub samples: usize,
+    /// Observed 95th percentile latency
+    pub p95_latency: Duration,
+    /// Observed success rate
+    pub success_rate: f64,
+    /// Targets currently missed
+    pub violations: Vec<String>,
+}
+
+impl SlaStatus {
+    /// Whether any target is currently missed
+    pub fn in_breach(&self) -> bool {
+        !self.violations.is_empty()
+    }
+}
+
+/// Event emitted when a tool moves out of SLA compliance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlaBreach {
+    /// Tool in breach
+    pub tool_name: String,
+    /// Targets missed
+    pub violations: Vec<String>,
+    /// When the breach was detected
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Rolling SLA evaluation for one tool
+#[derive(Debug)]
+pub struct SlaTracker {
+    target: SlaTarget,
+    samples: VecDeque<(Duration, bool)>,
+    breached: bool,
+}
+
+impl SlaTracker {
+    /// Track compliance with `target`
+    pub fn new(target: SlaTarget) -> Self {
+        Self {
+            target,
+            samples: VecDeque::new(),
+            breached: false,
+        }
+    }
+
+    /// Record a call, returning a breach if this call moved the tool out of
+    /// compliance
+    pub fn record(
+        &mut self,
+        tool_name: &str,
+        latency: Duration,
+        success: bool,
+    ) -> Option<SlaBreach> {
+        if self.samples.len() >= SLA_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((latency, success));
+
+        let status = self.status(tool_name);
+        let was_breached = std::mem::replace(&mut self.breached, status.in_breach());
+        (self.breached && !was_breached).then(|| SlaBreach {
+            tool_name: tool_name.to_string(),
+            violations: status.violations,
+            detected_at: Utc::now(),
+        })
+    }
+
+    /// Evaluate the window against the target
+    pub fn status(&self, tool_name: &str) -> SlaStatus {
+        let mut latencies: Vec<Duration> = self.samples.iter().map(|(l, _)| *l).collect();
+        latencies.sort_unstable();
+        let p95_latency = match latencies.len() {
+            0 => Duration::ZERO,
+            n => latencies[((n * 95).div_ceil(100)).saturating_sub(1)],
+        };
+        let success_rate = if self.samples.is_empty() {
+            1.0
+        } else {
+            let successes = self.samples.iter().filter(|(_, ok)| *ok).count();
+            successes as f64 / self.samples.len() as f64
+        };
+
+        let mut violations = Vec::new();
+        if p95_latency > self.target.p95_latency {
+            violations.push(format!(
+                "p95 latency {:?} exceeds {:?}",
+                p95_latency, self.target.p95_latency
+            ));
+        }
+        if success_rate < self.target.min_success_rate {
+            violations.push(format!(
+                "success rate {:.3} below {:.3}",
+                success_rate, self.target.min_success_rate
+            ));
+        }
+
+        SlaStatus {
+            tool_name: tool_name.to_string(),
+            target: self.target,
+            samples: self.samples.len(),
+            p95_latency,
+            success_rate,
+            violations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_breach_and_recovery() {
+        let mut tracker = SlaTracker::new(SlaTarget {
+            p95_latency: Duration::from_millis(200),
+            min_success_rate: 0.9,
+        });
+        let ms = Duration::from_millis;
+
+        for _ in 0..20 {
+            assert!(tracker.record("search", ms(50), true).is_none());
+        }
+        assert!(!tracker.status("search").in_breach());
+
+        // Two slow calls in 22 push p95 over target
+        assert!(tracker.record("search", ms(500), true).is_none());
+        let breach = tracker.record("search", ms(500), true).unwrap();
+        assert_eq!(breach.tool_name, "search");
+        assert!(breach.violations[0].contains("p95 latency"));
+
+        // Breach is only emitted on transition
+        assert!(tracker.record("search", ms(50), false).is_none());
+
+        for _ in 0..SLA_WINDOW {
+            tracker.record("search", ms(50), true);
+        }
+        let status = tracker.status("search");
+        assert!(!status.in_breach());
+        assert_eq!(status.success_rate, 1.0);
+
+        for _ in 0..11 {
+            tracker.record("search", ms(50), false);
+        }
+        assert!(tracker.status("search").violations[0].contains("success rate"));
+    }
+}
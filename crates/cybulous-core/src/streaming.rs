@@ -0,0 +1,31 @@
+//! Incremental responses from long-running tools
+//!
+//! Tools such as generators or transcoders produce output over time.
+//! A streamed call yields a `ToolChunk` per piece of output and ends with a
+//! single `StreamEvent::Finished` carrying the call's final status; the
+//! finishing response has no result of its own, since the output was
+//! delivered in chunks.
+
+use crate::orchestration::ToolResponse;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// One piece of a streamed call's output
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolChunk {
+    /// Call the chunk belongs to
+    pub call_id: Uuid,
+    /// Position of the chunk in the stream, from 0
+    pub sequence: u64,
+    /// Chunk payload
+    pub data: serde_json::Value,
+}
+
+/// Item of a streamed call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum StreamEvent {
+    /// Output produced so far
+    Chunk(ToolChunk),
+    /// The stream ended; always the last event
+    Finished(ToolResponse),
+}
@@ -0,0 +1,61 @@
+//! Escalating termination of unresponsive executors
+//!
+//! When a call times out or is cancelled the orchestrator stops awaiting
+//! it, which is enough for in-process executors. Remote and process-backed
+//! executors may keep running, so the orchestrator first asks them to stop
+//! and then walks their `EscalationLadder`, sending progressively harder
+//! signals until the executor reports it has stopped.
+
+use std::time::Duration;
+
+/// Signal sent to an executor to stop a call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopSignal {
+    /// Cooperative cancellation request
+    Cancel,
+    /// Abort message for a remote executor
+    Abort,
+    /// Graceful process termination, e.g. SIGTERM
+    Terminate,
+    /// Forced process termination, e.g. SIGKILL
+    Kill,
+}
+
+/// One rung of an escalation ladder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EscalationStep {
+    /// Time to wait after the previous signal before sending this one
+    pub grace: Duration,
+    /// Signal to send if the executor has not stopped by then
+    pub signal: StopSignal,
+}
+
+/// Ordered signals sent after the initial cancellation request
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EscalationLadder {
+    steps: Vec<EscalationStep>,
+}
+
+impl EscalationLadder {
+    /// Ladder with no escalation beyond the cancellation request
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send `signal` if the executor is still running `grace` after the
+    /// previous step
+    pub fn then(mut self, grace: Duration, signal: StopSignal) -> Self {
+        self.steps.push(EscalationStep { grace, signal });
+        self
+    }
+
+    /// Steps in escalation order
+    pub fn steps(&self) -> &[EscalationStep] {
+        &self.steps
+    }
+
+    /// Whether the ladder escalates at all
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
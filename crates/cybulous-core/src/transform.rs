@@ -0,0 +1,56 @@
+//! Request transforms applied to call parameters before dispatch
+//!
+//! Transforms normalize inputs (units, defaults, canonical forms) once,
+//! rather than in every executor. They run after consent is verified and
+//! before parameters are validated against the executor's requirements,
+//! so a transform may supply a parameter the executor requires.
+
+use crate::orchestration::ToolCall;
+use crate::Result;
+use serde_json::{Map, Value};
+use std::sync::Arc;
+
+/// Transforms in application order, each scoped to a tool if named
+pub(crate) type TransformChain = Vec<(Option<String>, Arc<dyn RequestTransform>)>;
+
+/// Rewrites a call's parameters before it reaches an executor
+pub trait RequestTransform: Send + Sync {
+    /// Transform `parameters` in place
+    fn apply(&self, call: &ToolCall, parameters: &mut Value) -> Result<()>;
+}
+
+/// Fills top-level parameters the caller left out
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DefaultParameters {
+    defaults: Map<String, Value>,
+}
+
+impl DefaultParameters {
+    /// Transform with no defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Default `name` to `value` when absent or null
+    pub fn with(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.defaults.insert(name.into(), value);
+        self
+    }
+}
+
+impl RequestTransform for DefaultParameters {
+    fn apply(&self, _call: &ToolCall, parameters: &mut Value) -> Result<()> {
+        if parameters.is_null() {
+            *parameters = Value::Object(Map::new());
+        }
+        if let Some(parameters) = parameters.as_object_mut() {
+            for (name, value) in &self.defaults {
+                let slot = parameters.entry(name.clone()).or_insert(Value::Null);
+                if slot.is_null() {
+                    *slot = value.clone();
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+//! Cost-center usage attribution for chargeback
+//!
+//! Calls name the cost center they are billed to, and optionally extra
+//! attribution tags, in their context metadata. The orchestrator keeps a
+//! bounded ledger of completed calls from which per-cost-center usage
+//! reports are built for any time range. Calls without a cost center are
+//! reported under [`UNATTRIBUTED`].
+
+use crate::orchestration::ToolCall;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
+use std::sync::Mutex;
+
+/// Context metadata key naming the cost center billed for a call
+pub const COST_CENTER_KEY: &str = "cost_center";
+
+/// Prefix of context metadata keys carrying attribution tags
+pub const ATTRIBUTION_PREFIX: &str = "attribution.";
+
+/// Cost center reported for calls that name none
+pub const UNATTRIBUTED: &str = "unattributed";
+
+/// Default number of completed calls retained for reporting
+pub const DEFAULT_USAGE_CAPACITY: usize = 100_000;
+
+/// Cost center a call is billed to, if named
+pub fn cost_center(call: &ToolCall) -> Option<&str> {
+    call.context
+        .metadata
+        .get(COST_CENTER_KEY)
+        .map(String::as_str)
+}
+
+/// Attribution tags on a call, keyed without the prefix
+pub fn attribution(call: &ToolCall) -> HashMap<String, String> {
+    call.context
+        .metadata
+        .iter()
+        .filter_map(|(key, value)| {
+            let tag = key.strip_prefix(ATTRIBUTION_PREFIX)?;
+            Some((tag.to_string(), value.clone()))
+        })
+        .collect()
+}
+
+/// Usage billed to one cost center
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CostCenterUsage {
+    /// Calls completed
+    pub calls: u64,
+    /// Calls that did not succeed
+    pub failures: u64,
+    /// Sum of call durations in milliseconds
+    pub total_duration_ms: u64,
+}
+
+/// Usage by cost center over a time range
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageReport {
+    /// Start of the range, inclusive
+    pub from: DateTime<Utc>,
+    /// End of the range, exclusive
+    pub to: DateTime<Utc>,
+    /// Usage keyed by cost center
+    pub cost_centers: HashMap<String, CostCenterUsage>,
+}
+
+#[derive(Debug)]
+struct UsageEntry {
+    cost_center: String,
+    success: bool,
+    duration_ms: u64,
+    completed_at: DateTime<Utc>,
+}
+
+/// Bounded ledger of completed calls by cost center
+#[derive(Debug)]
+pub struct UsageLedger {
+    capacity: usize,
+    entries: Mutex<VecDeque<UsageEntry>>,
+}
+
+impl Default for UsageLedger {
+    fn default() -> Self {
+        Self::new(DEFAULT_USAGE_CAPACITY)
+    }
+}
+
+impl UsageLedger {
+    /// Retain at most `capacity` completed calls
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Record a call completed at `completed_at`
+    pub fn record(
+        &self,
+        call: &ToolCall,
+        success: bool,
+        duration_ms: u64,
+        completed_at: DateTime<Utc>,
+    ) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(UsageEntry {
+            cost_center: cost_center(call).unwrap_or(UNATTRIBUTED).to_string(),
+            success,
+            duration_ms,
+            completed_at,
+        });
+    }
+
+    /// Tally calls completed within `range` by cost center
+    pub fn report(&self, range: Range<DateTime<Utc>>) -> UsageReport {
+        let mut cost_centers: HashMap<String, CostCenterUsage> = HashMap::new();
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        for entry in entries.iter().filter(|e| range.contains(&e.completed_at)) {
+            let usage = cost_centers.entry(entry.cost_center.clone()).or_default();
+            usage.calls += 1;
+            if !entry.success {
+                usage.failures += 1;
+            }
+            usage.total_duration_ms += entry.duration_ms;
+        }
+        UsageReport {
+            from: range.start,
+            to: range.end,
+            cost_centers,
+        }
+    }
+}
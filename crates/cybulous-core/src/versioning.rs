@@ -0,0 +1,97 @@
+//! Multiple live versions of a tool
+//!
+//! An executor that reports a semantic version is registered alongside the
+//! tool's other versions instead of replacing them. Calls may pin a version
+//! requirement in `ToolCall::tool_version`; they resolve to the newest live
+//! version that satisfies it, or to the newest live version when unpinned. Retired versions stay registered so calls
+//! pinned to them fail with a clear error rather than as unknown tools.
+
+use crate::orchestration::ToolExecutor;
+use crate::{CybulousError, Result};
+use semver::{Version, VersionReq};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+/// Registered versions of one tool
+#[derive(Default)]
+pub(crate) struct VersionTable {
+    versions: BTreeMap<Version, Arc<dyn ToolExecutor>>,
+    retired: BTreeSet<Version>,
+}
+
+impl std::fmt::Debug for VersionTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VersionTable")
+            .field("versions", &self.versions.keys().collect::<Vec<_>>())
+            .field("retired", &self.retired)
+            .finish()
+    }
+}
+
+impl VersionTable {
+    /// Add `executor` as `version`, returning the executor it replaces
+    pub(crate) fn insert(
+        &mut self,
+        version: Version,
+        executor: Arc<dyn ToolExecutor>,
+    ) -> Option<Arc<dyn ToolExecutor>> {
+        self.versions.insert(version, executor)
+    }
+
+    /// Remove `version`, returning its executor
+    pub(crate) fn remove(&mut self, version: &Version) -> Option<Arc<dyn ToolExecutor>> {
+        self.retired.remove(version);
+        self.versions.remove(version)
+    }
+
+    /// Executor for the newest registered version
+    pub(crate) fn latest(&self) -> Option<&Arc<dyn ToolExecutor>> {
+        self.versions.values().next_back()
+    }
+
+    /// Mark `version` retired, returning whether it is registered
+    pub(crate) fn retire(&mut self, version: &Version) -> bool {
+        self.versions.contains_key(version) && self.retired.insert(version.clone())
+    }
+
+    /// Registered versions, oldest first
+    pub(crate) fn versions(&self) -> impl Iterator<Item = &Version> {
+        self.versions.keys()
+    }
+
+    /// Executors for every registered version
+    pub(crate) fn executors(&self) -> impl Iterator<Item = &Arc<dyn ToolExecutor>> {
+        self.versions.values()
+    }
+
+    /// Newest live version of `tool` satisfying `req`, or the newest live
+    /// version if `req` is `None`
+    pub(crate) fn resolve(
+        &self,
+        tool: &str,
+        req: Option<&VersionReq>,
+    ) -> Result<Arc<dyn ToolExecutor>> {
+        let mut retired_match = None;
+        for (version, executor) in self.versions.iter().rev() {
+            if req.is_some_and(|req| !req.matches(version)) {
+                continue;
+            }
+            if !self.retired.contains(version) {
+                return Ok(executor.clone());
+            }
+            retired_match.get_or_insert(version);
+        }
+        Err(match (retired_match, req) {
+            (Some(version), _) => {
+                CybulousError::VersionRetired(format!("{} {} is retired", tool, version))
+            }
+            (None, Some(req)) => CybulousError::OrchestrationFailed(format!(
+                "No version of {} matches {}",
+                tool, req
+            )),
+            (None, None) => {
+                CybulousError::OrchestrationFailed(format!("No versions of {} registered", tool))
+            }
+        })
+    }
+}
@@ -0,0 +1,283 @@
+//! Warm pools of pre-initialized executor instances
+//!
+//! Tools backed by expensive resources, such as model sessions, keep a pool
+//! of ready instances. A `WarmPool` is itself registered as the tool's
+//! executor: each call borrows an idle instance, creating one only when
+//! none is idle, and returns it afterwards. The pool keeps at least
+//! `min_idle` instances warm, never holds more than `max_size`, and evicts
+//! instances left idle beyond `idle_timeout`.
+
+use crate::orchestration::{ToolCall, ToolExecutor, ToolResponse};
+use crate::Result;
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+/// Sizing and eviction policy for a warm pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WarmPoolConfig {
+    /// Idle instances kept ready, and exempt from idle eviction
+    pub min_idle: usize,
+    /// Maximum instances alive at once, idle or in use
+    pub max_size: usize,
+    /// How long an instance beyond `min_idle` may sit idle
+    pub idle_timeout: Duration,
+}
+
+impl Default for WarmPoolConfig {
+    fn default() -> Self {
+        Self {
+            min_idle: 1,
+            max_size: 4,
+            idle_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Creates fresh instances for a warm pool
+#[async_trait]
+pub trait InstanceFactory: Send + Sync {
+    /// Create and initialize an instance
+    async fn create(&self) -> Result<Arc<dyn ToolExecutor>>;
+}
+
+struct IdleInstance {
+    executor: Arc<dyn ToolExecutor>,
+    idle_since: Instant,
+}
+
+/// Pool of warm instances serving a single tool
+pub struct WarmPool {
+    tool_name: String,
+    capabilities: Vec<String>,
+    factory: Arc<dyn InstanceFactory>,
+    config: WarmPoolConfig,
+    idle: Mutex<VecDeque<IdleInstance>>,
+    live: AtomicUsize,
+    in_use: Semaphore,
+}
+
+impl fmt::Debug for WarmPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WarmPool")
+            .field("tool_name", &self.tool_name)
+            .field("config", &self.config)
+            .field("live", &self.live.load(Ordering::SeqCst))
+            .finish_non_exhaustive()
+    }
+}
+
+impl WarmPool {
+    /// Pool serving `tool_name` with instances from `factory`
+    ///
+    /// `capabilities` are advertised for capability checks and routing on
+    /// behalf of every instance.
+    pub fn new(
+        tool_name: impl Into<String>,
+        capabilities: Vec<String>,
+        factory: Arc<dyn InstanceFactory>,
+        config: WarmPoolConfig,
+    ) -> Self {
+        Self {
+            tool_name: tool_name.into(),
+            capabilities,
+            factory,
+            idle: Mutex::new(VecDeque::new()),
+            live: AtomicUsize::new(0),
+            in_use: Semaphore::new(config.max_size),
+            config,
+        }
+    }
+
+    /// Instances currently idle
+    pub fn idle_count(&self) -> usize {
+        self.lock_idle().len()
+    }
+
+    /// Instances currently alive, idle or in use
+    pub fn live_count(&self) -> usize {
+        self.live.load(Ordering::SeqCst)
+    }
+
+    /// Create instances until `min_idle` are idle or the pool is full
+    pub async fn fill(&self) -> Result<()> {
+        while self.idle_count() < self.config.min_idle && self.reserve_slot() {
+            match self.factory.create().await {
+                Ok(executor) => self.release(executor),
+                Err(e) => {
+                    self.live.fetch_sub(1, Ordering::SeqCst);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Evict instances beyond `min_idle` idle longer than the timeout
+    pub async fn evict_idle(&self, now: Instant) -> usize {
+        let evicted: Vec<_> = {
+            let mut idle = self.lock_idle();
+            let mut evicted = Vec::new();
+            // Oldest instances sit at the front
+            while idle.len() > self.config.min_idle
+                && idle.front().is_some_and(|i| {
+                    now.saturating_duration_since(i.idle_since) > self.config.idle_timeout
+                })
+            {
+                evicted.extend(idle.pop_front());
+            }
+            evicted
+        };
+        for instance in &evicted {
+            self.live.fetch_sub(1, Ordering::SeqCst);
+            instance.executor.on_deregister().await;
+        }
+        evicted.len()
+    }
+
+    /// Borrow the most recently used idle instance, creating one if none
+    async fn acquire(&self) -> Result<Arc<dyn ToolExecutor>> {
+        loop {
+            if let Some(instance) = self.lock_idle().pop_back() {
+                return Ok(instance.executor);
+            }
+            if self.reserve_slot() {
+                break;
+            }
+            // The pool is full only while a refill is creating an instance
+            tokio::task::yield_now().await;
+        }
+        let created = self.factory.create().await;
+        if created.is_err() {
+            self.live.fetch_sub(1, Ordering::SeqCst);
+        }
+        created
+    }
+
+    fn release(&self, executor: Arc<dyn ToolExecutor>) {
+        self.lock_idle().push_back(IdleInstance {
+            executor,
+            idle_since: Instant::now(),
+        });
+    }
+
+    /// Count a new instance against `max_size` if there is room
+    fn reserve_slot(&self) -> bool {
+        self.live
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |live| {
+                (live < self.config.max_size).then_some(live + 1)
+            })
+            .is_ok()
+    }
+
+    fn lock_idle(&self) -> std::sync::MutexGuard<'_, VecDeque<IdleInstance>> {
+        self.idle.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for WarmPool {
+    async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+        // At most `max_size` calls hold an instance, so live never exceeds it
+        let _permit = self
+            .in_use
+            .acquire()
+            .await
+            .expect("warm pool semaphore is never closed");
+        self.evict_idle(Instant::now()).await;
+
+        let instance = self.acquire().await?;
+        let response = instance.execute(call).await;
+        self.release(instance);
+
+        if let Err(e) = self.fill().await {
+            tracing::warn!("Refilling warm pool for {} failed: {}", self.tool_name, e);
+        }
+        response
+    }
+
+    fn name(&self) -> &str {
+        &self.tool_name
+    }
+
+    fn supports_capability(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|c| c == capability)
+    }
+
+    async fn on_deregister(&self) {
+        let drained: Vec<_> = self.lock_idle().drain(..).collect();
+        for instance in drained {
+            self.live.fetch_sub(1, Ordering::SeqCst);
+            instance.executor.on_deregister().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestration::ExecutionStatus;
+
+    struct Session;
+
+    #[async_trait]
+    impl ToolExecutor for Session {
+        async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+            Ok(ToolResponse {
+                call_id: call.id,
+                status: ExecutionStatus::Success,
+                result: None,
+                error: None,
+                duration_ms: 0,
+                stale: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "session"
+        }
+
+        fn supports_capability(&self, _capability: &str) -> bool {
+            true
+        }
+    }
+
+    struct Factory;
+
+    #[async_trait]
+    impl InstanceFactory for Factory {
+        async fn create(&self) -> Result<Arc<dyn ToolExecutor>> {
+            Ok(Arc::new(Session))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pool_refills_after_use_and_evicts_surplus() {
+        let config = WarmPoolConfig {
+            min_idle: 1,
+            max_size: 2,
+            idle_timeout: Duration::from_secs(60),
+        };
+        let pool = WarmPool::new("session", Vec::new(), Arc::new(Factory), config);
+        pool.fill().await.unwrap();
+        assert_eq!((pool.idle_count(), pool.live_count()), (1, 1));
+
+        let borrowed = pool.acquire().await.unwrap();
+        assert_eq!(pool.idle_count(), 0);
+        pool.fill().await.unwrap();
+        assert_eq!((pool.idle_count(), pool.live_count()), (1, 2));
+
+        // Full: a further refill creates nothing
+        pool.release(borrowed);
+        pool.fill().await.unwrap();
+        assert_eq!((pool.idle_count(), pool.live_count()), (2, 2));
+
+        let later = Instant::now() + Duration::from_secs(120);
+        assert_eq!(pool.evict_idle(later).await, 1);
+        assert_eq!((pool.idle_count(), pool.live_count()), (1, 1));
+    }
+}
@@ -0,0 +1,266 @@
+//! Sandboxed WebAssembly tool executors
+//!
+//! A `WasmExecutor` runs a user-supplied tool compiled to WebAssembly with
+//! no host access: modules may not import anything, so all they can touch
+//! is their own linear memory. Each call gets a fresh instance bounded by
+//! a fuel budget and a memory cap.
+//!
+//! Modules exchange JSON through their memory. They export:
+//!
+//! - `memory`, their linear memory
+//! - `alloc(len: i32) -> i32`, returning space for `len` input bytes
+//! - `run(ptr: i32, len: i32) -> i64`, taking the call parameters as JSON
+//!   at `ptr` and returning the result's JSON location packed as
+//!   `(ptr << 32) | len`
+
+use crate::orchestration::{ExecutionStatus, ToolCall, ToolExecutor, ToolResponse};
+use crate::{CybulousError, Result};
+use anyhow::Context;
+use async_trait::async_trait;
+use std::collections::HashSet;
+use wasmtime::{Config, Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder, Trap};
+
+/// Resources a single call may consume
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmLimits {
+    /// Fuel available to the call, roughly one unit per instruction
+    pub fuel: u64,
+    /// Largest linear memory the module may allocate
+    pub max_memory_bytes: usize,
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        Self {
+            fuel: 10_000_000,
+            max_memory_bytes: 16 << 20,
+        }
+    }
+}
+
+/// Executor running a tool compiled to WebAssembly
+pub struct WasmExecutor {
+    name: String,
+    engine: Engine,
+    module: Module,
+    limits: WasmLimits,
+    capabilities: HashSet<String>,
+}
+
+impl std::fmt::Debug for WasmExecutor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmExecutor")
+            .field("name", &self.name)
+            .field("limits", &self.limits)
+            .field("capabilities", &self.capabilities)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WasmExecutor {
+    /// Compile `module`, in binary or text format, as tool `name`
+    ///
+    /// Fails if the module does not compile or imports anything.
+    pub fn new(name: impl Into<String>, module: impl AsRef<[u8]>) -> Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(sandbox_error)?;
+        let module = Module::new(&engine, module).map_err(sandbox_error)?;
+        if let Some(import) = module.imports().next() {
+            return Err(CybulousError::SandboxError(format!(
+                "module imports {}::{}, but sandboxed tools get no host access",
+                import.module(),
+                import.name()
+            )));
+        }
+        Ok(Self {
+            name: name.into(),
+            engine,
+            module,
+            limits: WasmLimits::default(),
+            capabilities: HashSet::new(),
+        })
+    }
+
+    /// Bound each call by `limits`
+    pub fn with_limits(mut self, limits: WasmLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Advertise `capability`
+    pub fn with_capability(mut self, capability: impl Into<String>) -> Self {
+        self.capabilities.insert(capability.into());
+        self
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for WasmExecutor {
+    async fn execute(&self, call: &ToolCall) -> Result<ToolResponse> {
+        let start = std::time::Instant::now();
+        let input = serde_json::to_vec(&call.parameters)?;
+        let (engine, module, limits) = (self.engine.clone(), self.module.clone(), self.limits);
+        let outcome = tokio::task::spawn_blocking(move || run(&engine, &module, limits, &input))
+            .await
+            .map_err(sandbox_error)?;
+
+        let (status, result, error) = match outcome {
+            Ok(result) => (ExecutionStatus::Success, Some(result), None),
+            Err(e) => {
+                let error = match e.downcast_ref::<Trap>() {
+                    Some(Trap::OutOfFuel) => format!("fuel limit of {} exhausted", limits.fuel),
+                    _ => format!("{:#}", e),
+                };
+                (ExecutionStatus::Failed, None, Some(error))
+            }
+        };
+        Ok(ToolResponse {
+            call_id: call.id,
+            status,
+            result,
+            error,
+            duration_ms: start.elapsed().as_millis() as u64,
+            stale: false,
+        })
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn supports_capability(&self, capability: &str) -> bool {
+        self.capabilities.contains(capability)
+    }
+}
+
+fn sandbox_error(e: impl std::fmt::Display) -> CybulousError {
+    CybulousError::SandboxError(e.to_string())
+}
+
+/// Run `module` once on `input` in a fresh, limited instance
+fn run(
+    engine: &Engine,
+    module: &Module,
+    limits: WasmLimits,
+    input: &[u8],
+) -> anyhow::Result<serde_json::Value> {
+    let store_limits = StoreLimitsBuilder::new()
+        .memory_size(limits.max_memory_bytes)
+        .instances(1)
+        .build();
+    let mut store = Store::new(engine, store_limits);
+    store.limiter(|limits: &mut StoreLimits| limits);
+    store.set_fuel(limits.fuel)?;
+
+    let instance = Instance::new(&mut store, module, &[])?;
+    let memory = instance
+        .get_memory(&mut store, "memory")
+        .context("module exports no memory")?;
+    let alloc = instance.get_typed_func::<i32, i32>(&mut store, "alloc")?;
+    let run = instance.get_typed_func::<(i32, i32), i64>(&mut store, "run")?;
+
+    let len = i32::try_from(input.len()).context("parameters too large")?;
+    let ptr = alloc.call(&mut store, len)?;
+    memory.write(&mut store, ptr as u32 as usize, input)?;
+    let packed = run.call(&mut store, (ptr, len))?;
+
+    let (out_ptr, out_len) = ((packed >> 32) as u32 as usize, packed as u32 as usize);
+    let output = memory
+        .data(&store)
+        .get(out_ptr..out_ptr.saturating_add(out_len))
+        .context("result lies outside module memory")?;
+    Ok(serde_json::from_slice(output)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestration::ExecutionContext;
+    use uuid::Uuid;
+
+    /// Bump allocator shared by the test modules
+    const ALLOC: &str = r#"
+        (global $next (mut i32) (i32.const 1024))
+        (func (export "alloc") (param $len i32) (result i32)
+          (local $ptr i32)
+          (local.set $ptr (global.get $next))
+          (global.set $next (i32.add (global.get $next) (local.get $len)))
+          (local.get $ptr))
+    "#;
+
+    fn module(memory_pages: u32, run: &str) -> String {
+        format!(
+            r#"(module (memory (export "memory") {}) {} {})"#,
+            memory_pages, ALLOC, run
+        )
+    }
+
+    fn call() -> ToolCall {
+        ToolCall {
+            id: Uuid::new_v4(),
+            tool_name: "sandboxed".to_string(),
+            parameters: serde_json::json!({ "text": "hello" }),
+            user_id: "test-user".to_string(),
+            context: ExecutionContext::new(Uuid::new_v4(), "proof".to_string()),
+            timeout_ms: 1000,
+            queue_timeout_ms: None,
+            execution_timeout_ms: None,
+            encrypt_to: None,
+            tool_version: None,
+            required_capability: None,
+            idempotency_key: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_module_round_trips_json() {
+        // Returns its input unchanged
+        let echo = module(
+            1,
+            r#"(func (export "run") (param $ptr i32) (param $len i32) (result i64)
+                 (i64.or
+                   (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+                   (i64.extend_i32_u (local.get $len))))"#,
+        );
+        let executor = WasmExecutor::new("sandboxed", echo).unwrap();
+        let call = call();
+        let response = executor.execute(&call).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Success);
+        assert_eq!(response.result, Some(call.parameters));
+    }
+
+    #[tokio::test]
+    async fn test_limits_and_imports_are_enforced() {
+        let spin = module(
+            1,
+            r#"(func (export "run") (param i32 i32) (result i64)
+                 (loop $forever (br $forever))
+                 (i64.const 0))"#,
+        );
+        let executor = WasmExecutor::new("sandboxed", spin)
+            .unwrap()
+            .with_limits(WasmLimits {
+                fuel: 10_000,
+                ..WasmLimits::default()
+            });
+        let response = executor.execute(&call()).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Failed);
+        assert_eq!(response.error.unwrap(), "fuel limit of 10000 exhausted");
+
+        // 512 pages is 32 MiB, over the default 16 MiB cap
+        let hungry = module(
+            512,
+            r#"(func (export "run") (param i32 i32) (result i64) (i64.const 0))"#,
+        );
+        let executor = WasmExecutor::new("sandboxed", hungry).unwrap();
+        let response = executor.execute(&call()).await.unwrap();
+        assert_eq!(response.status, ExecutionStatus::Failed);
+
+        let escaping = r#"(module (import "env" "open" (func (param i32))))"#;
+        assert!(matches!(
+            WasmExecutor::new("sandboxed", escaping),
+            Err(CybulousError::SandboxError(msg)) if msg.contains("env::open")
+        ));
+    }
+}
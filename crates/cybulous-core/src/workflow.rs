@@ -0,0 +1,397 @@
+//! Multi-step workflows of dependent tool calls
+//!
+//! A workflow is a DAG of nodes, each invoking one tool. String parameters
+//! may reference an upstream node's result with `{{node_id.path}}`
+//! templates. Workflows can be validated without executing any node.
+//!
+//! When executed, nodes run in topological order. A template referencing
+//! an upstream node resolves against that node's serialized
+//! `ToolResponse`, so `{{fetch.result.title}}` reads `title` from the
+//! `fetch` node's result. A template that makes up a whole string keeps the
+//! referenced value's JSON type.
+//!
+//! A failed node either aborts the run, skipping everything not yet run, or
+//! lets the run continue, skipping only the nodes downstream of it.
+
+use crate::orchestration::ToolResponse;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// What a node's failure does to the rest of the run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FailureMode {
+    /// Stop the run; nodes not yet run are skipped
+    #[default]
+    Abort,
+    /// Skip only the nodes that depend on the failed one
+    Continue,
+}
+
+/// A single tool invocation within a workflow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowNode {
+    /// Unique node identifier within the workflow
+    pub id: String,
+    /// Tool to invoke
+    pub tool_name: String,
+    /// Tool parameters, possibly containing template references
+    pub parameters: serde_json::Value,
+    /// Nodes that must complete before this one
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// What this node's failure does to the rest of the run
+    #[serde(default)]
+    pub on_failure: FailureMode,
+}
+
+/// A DAG of dependent tool calls
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Workflow {
+    /// Workflow nodes
+    pub nodes: Vec<WorkflowNode>,
+}
+
+/// Diagnostics produced by validating a workflow without executing it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkflowValidation {
+    /// Problems affecting the workflow as a whole
+    pub diagnostics: Vec<String>,
+    /// Problems keyed by node id
+    pub nodes: HashMap<String, Vec<String>>,
+}
+
+/// Result of executing a workflow
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkflowRun {
+    /// Responses of nodes that succeeded, by node id
+    pub outputs: HashMap<String, ToolResponse>,
+    /// Why each failed node failed, by node id
+    pub failures: HashMap<String, String>,
+    /// Nodes not run because of an upstream failure or an abort, in
+    /// topological order
+    pub skipped: Vec<String>,
+    /// Whether a node's failure aborted the run
+    pub aborted: bool,
+}
+
+impl WorkflowRun {
+    /// Whether every node succeeded
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty() && self.skipped.is_empty()
+    }
+}
+
+impl WorkflowValidation {
+    /// Whether no problems were found
+    pub fn is_valid(&self) -> bool {
+        self.diagnostics.is_empty() && self.nodes.values().all(Vec::is_empty)
+    }
+
+    /// Record a problem with a node
+    pub fn node_error(&mut self, node_id: &str, message: impl Into<String>) {
+        self.nodes
+            .entry(node_id.to_string())
+            .or_default()
+            .push(message.into());
+    }
+}
+
+impl Workflow {
+    /// Check DAG structure and template references
+    ///
+    /// Executor- and consent-dependent checks are added by
+    /// [`crate::Orchestrator::validate_workflow`].
+    pub fn validate_structure(&self) -> WorkflowValidation {
+        let mut validation = WorkflowValidation::default();
+        let mut by_id: HashMap<&str, &WorkflowNode> = HashMap::new();
+
+        for node in &self.nodes {
+            validation.nodes.entry(node.id.clone()).or_default();
+            if by_id.insert(node.id.as_str(), node).is_some() {
+                validation
+                    .diagnostics
+                    .push(format!("duplicate node id {}", node.id));
+            }
+        }
+
+        for node in &self.nodes {
+            for dep in &node.depends_on {
+                if !by_id.contains_key(dep.as_str()) {
+                    validation.node_error(&node.id, format!("depends on unknown node {}", dep));
+                }
+            }
+        }
+
+        for node_id in self.cyclic_nodes(&by_id) {
+            validation.node_error(node_id, "part of a dependency cycle");
+        }
+
+        for node in &self.nodes {
+            let ancestors = Self::ancestors(node, &by_id);
+            for reference in template_references(&node.parameters) {
+                let target = reference.split('.').next().unwrap_or_default();
+                if !by_id.contains_key(target) {
+                    validation.node_error(
+                        &node.id,
+                        format!("template {{{{{}}}}} references unknown node", reference),
+                    );
+                } else if !ancestors.contains(target) {
+                    validation.node_error(
+                        &node.id,
+                        format!(
+                            "template {{{{{}}}}} references {} which is not upstream",
+                            reference, target
+                        ),
+                    );
+                }
+            }
+        }
+
+        validation
+    }
+
+    /// Nodes ordered so each follows its dependencies, ties broken by
+    /// declaration order
+    ///
+    /// Assumes the workflow passed [`Workflow::validate_structure`].
+    pub fn topological_order(&self) -> Vec<&WorkflowNode> {
+        let mut done: HashSet<&str> = HashSet::new();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while order.len() < self.nodes.len() {
+            let Some(next) = self.nodes.iter().find(|node| {
+                !done.contains(node.id.as_str())
+                    && node.depends_on.iter().all(|d| done.contains(d.as_str()))
+            }) else {
+                break;
+            };
+            done.insert(&next.id);
+            order.push(next);
+        }
+        order
+    }
+
+    /// Nodes that cannot be topologically ordered
+    fn cyclic_nodes<'a>(&'a self, by_id: &HashMap<&'a str, &'a WorkflowNode>) -> Vec<&'a str> {
+        let mut indegree: HashMap<&str, usize> = by_id.keys().map(|id| (*id, 0)).collect();
+        let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+        for node in by_id.values() {
+            for dep in node
+                .depends_on
+                .iter()
+                .filter(|d| by_id.contains_key(d.as_str()))
+            {
+                *indegree.entry(node.id.as_str()).or_default() += 1;
+                dependents.entry(dep.as_str()).or_default().push(&node.id);
+            }
+        }
+
+        let mut ready: VecDeque<&str> = indegree
+            .iter()
+            .filter(|(_, n)| **n == 0)
+            .map(|(id, _)| *id)
+            .collect();
+        while let Some(id) = ready.pop_front() {
+            for dependent in dependents.get(id).into_iter().flatten() {
+                let n = indegree.entry(dependent).or_default();
+                *n -= 1;
+                if *n == 0 {
+                    ready.push_back(dependent);
+                }
+            }
+        }
+
+        let mut cyclic: Vec<&str> = indegree
+            .into_iter()
+            .filter(|(_, n)| *n > 0)
+            .map(|(id, _)| id)
+            .collect();
+        cyclic.sort_unstable();
+        cyclic
+    }
+
+    /// Transitive dependencies of a node
+    fn ancestors<'a>(
+        node: &'a WorkflowNode,
+        by_id: &HashMap<&'a str, &'a WorkflowNode>,
+    ) -> HashSet<&'a str> {
+        let mut seen = HashSet::new();
+        let mut stack: Vec<&str> = node.depends_on.iter().map(String::as_str).collect();
+        while let Some(id) = stack.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            if let Some(dep) = by_id.get(id) {
+                stack.extend(dep.depends_on.iter().map(String::as_str));
+            }
+        }
+        seen
+    }
+}
+
+/// Replace template references in `parameters` with upstream outputs
+///
+/// Each output is the node's serialized response. References to missing
+/// values resolve to `null`.
+pub fn resolve_templates(
+    parameters: &serde_json::Value,
+    outputs: &HashMap<String, serde_json::Value>,
+) -> serde_json::Value {
+    let lookup = |reference: &str| {
+        let mut segments = reference.split('.');
+        let mut value = outputs.get(segments.next().unwrap_or_default())?;
+        for segment in segments {
+            value = match value {
+                serde_json::Value::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+                _ => value.get(segment)?,
+            };
+        }
+        Some(value.clone())
+    };
+
+    match parameters {
+        serde_json::Value::String(s) => {
+            let trimmed = s.trim();
+            if let Some(reference) = trimmed
+                .strip_prefix("{{")
+                .and_then(|r| r.strip_suffix("}}"))
+                .filter(|r| !r.contains("{{"))
+            {
+                return lookup(reference.trim()).unwrap_or(serde_json::Value::Null);
+            }
+
+            let mut rendered = String::new();
+            let mut rest = s.as_str();
+            while let Some(start) = rest.find("{{") {
+                let Some(len) = rest[start + 2..].find("}}") else {
+                    break;
+                };
+                rendered.push_str(&rest[..start]);
+                match lookup(rest[start + 2..start + 2 + len].trim()) {
+                    Some(serde_json::Value::String(value)) => rendered.push_str(&value),
+                    Some(value) => rendered.push_str(&value.to_string()),
+                    None => rendered.push_str("null"),
+                }
+                rest = &rest[start + 2 + len + 2..];
+            }
+            rendered.push_str(rest);
+            serde_json::Value::String(rendered)
+        }
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|item| resolve_templates(item, outputs))
+            .collect(),
+        serde_json::Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| (key.clone(), resolve_templates(value, outputs)))
+            .collect(),
+        other => other.clone(),
+    }
+}
+
+/// Collect `{{...}}` references from string values in `parameters`
+pub fn template_references(parameters: &serde_json::Value) -> Vec<String> {
+    let mut references = Vec::new();
+    let mut pending = vec![parameters];
+    while let Some(value) = pending.pop() {
+        match value {
+            serde_json::Value::String(s) => {
+                let mut rest = s.as_str();
+                while let Some(start) = rest.find("{{") {
+                    let Some(len) = rest[start + 2..].find("}}") else {
+                        break;
+                    };
+                    references.push(rest[start + 2..start + 2 + len].trim().to_string());
+                    rest = &rest[start + 2 + len + 2..];
+                }
+            }
+            serde_json::Value::Array(items) => pending.extend(items),
+            serde_json::Value::Object(map) => pending.extend(map.values()),
+            _ => {}
+        }
+    }
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, depends_on: &[&str], parameters: serde_json::Value) -> WorkflowNode {
+        WorkflowNode {
+            id: id.to_string(),
+            tool_name: "search".to_string(),
+            parameters,
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            on_failure: FailureMode::Abort,
+        }
+    }
+
+    #[test]
+    fn test_cycle_is_reported_per_node() {
+        let workflow = Workflow {
+            nodes: vec![
+                node("a", &["c"], serde_json::json!({})),
+                node("b", &["a"], serde_json::json!({})),
+                node("c", &["b"], serde_json::json!({})),
+                node("d", &[], serde_json::json!({})),
+            ],
+        };
+        let validation = workflow.validate_structure();
+        assert!(!validation.is_valid());
+        for id in ["a", "b", "c"] {
+            assert_eq!(validation.nodes[id], vec!["part of a dependency cycle"]);
+        }
+        assert!(validation.nodes["d"].is_empty());
+    }
+
+    #[test]
+    fn test_template_references() {
+        let params = serde_json::json!({
+            "query": "{{ fetch.result.title }} and {{rank.score}}",
+            "nested": ["{{fetch.id}}"],
+        });
+        let mut refs = template_references(&params);
+        refs.sort();
+        assert_eq!(refs, vec!["fetch.id", "fetch.result.title", "rank.score"]);
+    }
+
+    #[test]
+    fn test_topological_order_follows_dependencies() {
+        let workflow = Workflow {
+            nodes: vec![
+                node("rank", &["fetch", "score"], serde_json::json!({})),
+                node("score", &["fetch"], serde_json::json!({})),
+                node("fetch", &[], serde_json::json!({})),
+            ],
+        };
+        let order: Vec<_> = workflow
+            .topological_order()
+            .iter()
+            .map(|n| n.id.as_str())
+            .collect();
+        assert_eq!(order, vec!["fetch", "score", "rank"]);
+    }
+
+    #[test]
+    fn test_resolve_templates_keeps_whole_value_types() {
+        let outputs = HashMap::from([(
+            "fetch".to_string(),
+            serde_json::json!({ "result": { "title": "Rust", "ids": [4, 7] } }),
+        )]);
+        let params = serde_json::json!({
+            "ids": "{{fetch.result.ids}}",
+            "first": "{{ fetch.result.ids.1 }}",
+            "query": "about {{fetch.result.title}}",
+            "missing": "{{fetch.result.author}}",
+        });
+        assert_eq!(
+            resolve_templates(&params, &outputs),
+            serde_json::json!({
+                "ids": [4, 7],
+                "first": 7,
+                "query": "about Rust",
+                "missing": null,
+            })
+        );
+    }
+}